@@ -1,4 +1,6 @@
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::event::{
+    DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
+};
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
@@ -15,7 +17,7 @@ use std::fs;
 use std::io;
 use std::io::{BufRead, Write};
 use std::path::PathBuf;
-use tui_textarea::{CursorMove, Input, Key, TextArea};
+use tui_textarea::{CursorMove, EditorEvent, Input, Key, TextArea};
 
 macro_rules! error {
     ($fmt: expr $(, $args:tt)*) => {{
@@ -157,7 +159,12 @@ impl Editor<'_> {
         }
         let mut stdout = io::stdout();
         enable_raw_mode()?;
-        crossterm::execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+        crossterm::execute!(
+            stdout,
+            EnterAlternateScreen,
+            EnableMouseCapture,
+            EnableBracketedPaste
+        )?;
         let backend = CrosstermBackend::new(stdout);
         let term = Terminal::new(backend)?;
         Ok(Self {
@@ -306,7 +313,16 @@ impl Editor<'_> {
                     }
                 }
             } else {
-                match crossterm::event::read()?.into() {
+                let input = match EditorEvent::from(crossterm::event::read()?) {
+                    EditorEvent::Input(input) => input,
+                    event => {
+                        // Pasted text is inserted as one edit
+                        let buffer = &mut self.buffers[self.current];
+                        buffer.modified = buffer.textarea.handle_event(event);
+                        continue;
+                    }
+                };
+                match input {
                     Input {
                         key: Key::Char('q'),
                         ctrl: true,
@@ -355,7 +371,8 @@ impl Drop for Editor<'_> {
         crossterm::execute!(
             self.term.backend_mut(),
             LeaveAlternateScreen,
-            DisableMouseCapture
+            DisableMouseCapture,
+            DisableBracketedPaste
         )
         .unwrap();
     }
@@ -1,5 +1,5 @@
 use crossterm::{
-    event::{self, Event, KeyCode, MouseEventKind, EnableMouseCapture, DisableMouseCapture},
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
@@ -12,6 +12,7 @@ use ratatui::{
 };
 use std::io::{self, Write};
 use std::fs::OpenOptions;
+use std::time::Duration;
 use tui_textarea::TextArea;
 
 fn main() -> io::Result<()> {
@@ -46,8 +47,8 @@ fn main() -> io::Result<()> {
     textarea.insert_str("- In the middle of words\n");
     textarea.insert_str("- On empty lines\n");
     textarea.insert_str("\n");
-    textarea.insert_str("Text wrapping demo (enable with 'w' key):\n");
-    textarea.insert_str("This is a very long line of text that will demonstrate how mouse clicking works with text wrapping when it is enabled. You can toggle wrapping with the 'w' key.");
+    textarea.insert_str("Text wrapping demo (enable with Ctrl+w):\n");
+    textarea.insert_str("This is a very long line of text that will demonstrate how mouse clicking works with text wrapping when it is enabled. You can toggle wrapping with Ctrl+w.");
     
     // Start with cursor at the beginning
     textarea.move_cursor(tui_textarea::CursorMove::Top);
@@ -81,9 +82,10 @@ fn main() -> io::Result<()> {
             
             let info_text = format!(
                 "Controls:\n\
-                 • Click anywhere in the text area to position cursor\n\
-                 • 'w' - Toggle text wrapping (current: {})\n\
-                 • 'l' - Toggle line numbers\n\
+                 • Click to position cursor, double/triple-click to select word/line\n\
+                 • Drag to select, shift+click to extend, alt+drag for rectangle\n\
+                 • 'Ctrl+w' - Toggle text wrapping (current: {})\n\
+                 • 'Ctrl+l' - Toggle line numbers\n\
                  • 'Ctrl+q' - Quit\n\
                  \n\
                  Cursor: ({}, {})\n\
@@ -100,15 +102,21 @@ fn main() -> io::Result<()> {
             f.render_widget(info_paragraph, chunks[1]);
         })?;
 
+        if !event::poll(Duration::from_millis(50))? {
+            // Keep scrolling while the mouse button is held outside the text area
+            textarea.handle_mouse_tick();
+            continue;
+        }
+
         let event = event::read()?;
         writeln!(log_file, "Event received: {:?}", event)?;
         
         if let Event::Key(key) = event {
-            match (key.code,  {
+            match (key.code, key.modifiers) {
                 (KeyCode::Char('q'), KeyModifiers::CONTROL) => {
                     break;
                 }
-                KeyCode::Char('w') => {
+                (KeyCode::Char('w'), KeyModifiers::CONTROL) => {
                     #[cfg(feature = "wrap")]
                     {
                         textarea.set_wrap(!textarea.wrap_enabled());
@@ -119,7 +127,7 @@ fn main() -> io::Result<()> {
                         last_click_info = "Wrapping feature not enabled".to_string();
                     }
                 }
-                KeyCode::Char('l') => {
+                (KeyCode::Char('l'), KeyModifiers::CONTROL) => {
                     if textarea.line_number_style().is_some() {
                         textarea.remove_line_number();
                         last_click_info = "Line numbers disabled".to_string();
@@ -134,54 +142,16 @@ fn main() -> io::Result<()> {
             }
         } else if let Event::Mouse(mouse) = event {
             writeln!(log_file, "Mouse event: {:?}", mouse)?;
-            match mouse.kind {
-                MouseEventKind::Down(crossterm::event::MouseButton::Left) => {
-                    writeln!(log_file, "Left mouse button down at ({}, {})", mouse.column, mouse.row)?;
-                    
-                    // Get the widget area that matches what we use in rendering
-                    let size = terminal.size()?;
-                    let full_area = ratatui::layout::Rect::new(0, 0, size.width, size.height);
-                    let chunks = Layout::default()
-                        .direction(Direction::Vertical)
-                        .constraints([Constraint::Min(3), Constraint::Length(5)])
-                        .split(full_area);
-                    let widget_area = chunks[0]; // This is the full widget area including borders
-                    
-                    writeln!(log_file, "Widget area: {:?}", widget_area)?;
-                    writeln!(log_file, "Terminal size: {:?}", size)?;
-                    
-                    #[cfg(feature = "mouse")]
-                    {
-                        writeln!(log_file, "Calling handle_mouse_click...")?;
-                        let cursor_before = textarea.cursor();
-                        let result = textarea.handle_mouse_click(mouse.column, mouse.row, widget_area);
-                        let cursor_after = textarea.cursor();
-                        writeln!(log_file, "handle_mouse_click returned: {}", result)?;
-                        writeln!(log_file, "Cursor before: {:?}, after: {:?}", cursor_before, cursor_after)?;
-                        
-                        if result {
-                            last_click_info = format!(
-                                "Clicked at screen ({}, {}) -> cursor ({}, {})",
-                                mouse.column, mouse.row, cursor_after.0, cursor_after.1
-                            );
-                        } else {
-                            last_click_info = format!(
-                                "Click at ({}, {}) was outside text area",
-                                mouse.column, mouse.row
-                            );
-                        }
-                    }
-                    #[cfg(not(feature = "mouse"))]
-                    {
-                        writeln!(log_file, "Mouse feature not enabled")?;
-                        last_click_info = "Mouse feature not enabled - compile with --features mouse".to_string();
-                    }
-                }
-                _ => {
-                    // Handle other mouse events normally (like scrolling)
-                    textarea.input(mouse);
-                }
-            }
+            // Clicks, drags, shift+click, alt+drag and wheels are all handled by `TextArea::input`. Positions are
+            // mapped with the area where the textarea was rendered last time.
+            textarea.input(mouse);
+            let (row, col) = textarea.cursor();
+            last_click_info = format!(
+                "{:?} at screen ({}, {}) -> cursor ({}, {})",
+                mouse.kind, mouse.column, mouse.row, row, col
+            );
+        } else {
+            textarea.input(event);
         }
     }
 
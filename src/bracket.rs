@@ -0,0 +1,157 @@
+use std::iter;
+
+// Pairs of brackets matched by `CursorMove::MatchingBracket`
+const BRACKETS: &[(char, char)] = &[('(', ')'), ('[', ']'), ('{', '}')];
+
+// Quotes skipped on matching brackets by default. Single quote is not included since it is used for other purposes
+// than quoting in some languages (e.g. lifetimes in Rust)
+pub(crate) const DEFAULT_BRACKET_QUOTES: &[char] = &['"'];
+
+// Flag for each character in the line which is true when the character is in a quoted region. Quoted regions don't
+// span lines and a quote escaped with backslash doesn't close the region
+fn quoted_chars(line: &str, quotes: &[char]) -> Vec<bool> {
+    let mut flags = Vec::with_capacity(line.len());
+    let mut open = None;
+    let mut escaped = false;
+    for c in line.chars() {
+        match open {
+            Some(q) => {
+                flags.push(true);
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == q {
+                    open = None;
+                }
+            }
+            None => {
+                let is_quote = quotes.contains(&c);
+                if is_quote {
+                    open = Some(c);
+                }
+                flags.push(is_quote);
+            }
+        }
+    }
+    flags
+}
+
+// Find the bracket under the cursor, or the bracket before the cursor when no bracket is under the cursor, and its
+// matching bracket. The positions of both brackets are returned. Brackets in quoted regions are matched only with
+// brackets in quoted regions, and brackets outside them are matched only with brackets outside them
+pub(crate) fn find_matching_bracket(
+    lines: &[String],
+    (row, col): (usize, usize),
+    quotes: &[char],
+) -> Option<((usize, usize), (usize, usize))> {
+    let chars: Vec<char> = lines[row].chars().collect();
+    let (col, target, other, forward) =
+        iter::once(col).chain(col.checked_sub(1)).find_map(|col| {
+            let c = *chars.get(col)?;
+            BRACKETS.iter().find_map(|&(open, close)| {
+                if c == open {
+                    Some((col, close, open, true))
+                } else if c == close {
+                    Some((col, open, close, false))
+                } else {
+                    None
+                }
+            })
+        })?;
+    let in_quote = quoted_chars(&lines[row], quotes)[col];
+
+    let mut depth = 0usize;
+    let mut visit = |c: char, quoted: bool| {
+        if quoted != in_quote {
+            return false;
+        }
+        if c == other {
+            depth += 1;
+        } else if c == target {
+            if depth == 0 {
+                return true;
+            }
+            depth -= 1;
+        }
+        false
+    };
+
+    if forward {
+        for (r, line) in lines.iter().enumerate().skip(row) {
+            let quoted = quoted_chars(line, quotes);
+            let skip = if r == row { col + 1 } else { 0 };
+            for (i, c) in line.chars().enumerate().skip(skip) {
+                if visit(c, quoted[i]) {
+                    return Some(((row, col), (r, i)));
+                }
+            }
+        }
+    } else {
+        for (r, line) in lines.iter().enumerate().take(row + 1).rev() {
+            let quoted = quoted_chars(line, quotes);
+            let chars: Vec<char> = line.chars().collect();
+            let end = if r == row { col } else { chars.len() };
+            for i in (0..end).rev() {
+                if visit(chars[i], quoted[i]) {
+                    return Some(((row, col), (r, i)));
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn quoted_regions() {
+        let flags = quoted_chars(r#"a"b\"c"d'e'"#, &['"']);
+        let want = [
+            false, true, true, true, true, true, true, false, false, false, false,
+        ];
+        assert_eq!(flags, want);
+        assert_eq!(quoted_chars("'a'", &[]), [false, false, false]);
+    }
+
+    #[test]
+    fn matching_bracket() {
+        let text = lines(&["f(a, [b]) {", "  \"(\" )", "}"]);
+        let quotes = ['"'];
+        for (cursor, want) in [
+            ((0, 1), Some(((0, 1), (0, 8)))),
+            ((0, 8), Some(((0, 8), (0, 1)))),
+            ((0, 9), Some(((0, 8), (0, 1)))),
+            ((0, 5), Some(((0, 5), (0, 7)))),
+            ((0, 10), Some(((0, 10), (2, 0)))),
+            ((2, 0), Some(((2, 0), (0, 10)))),
+            ((2, 1), Some(((2, 0), (0, 10)))),
+            ((0, 3), None),
+            ((0, 0), None),
+            // Unmatched close bracket
+            ((1, 7), None),
+        ] {
+            assert_eq!(
+                find_matching_bracket(&text, cursor, &quotes),
+                want,
+                "{:?}",
+                cursor,
+            );
+        }
+
+        // Without quotes, the bracket in the string is matched
+        assert_eq!(
+            find_matching_bracket(&text, (1, 3), &[]),
+            Some(((1, 3), (1, 6))),
+        );
+        // Brackets in quoted regions are matched with each other
+        let text = lines(&["\"(\" x \")\""]);
+        assert_eq!(
+            find_matching_bracket(&text, (0, 1), &quotes),
+            Some(((0, 1), (0, 7))),
+        );
+    }
+}
@@ -0,0 +1,379 @@
+use crate::input::{Input, Key};
+use crate::keymap::EditorCommand;
+use crate::textarea::TextArea;
+use crate::util::Clock;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Callback function run by a key sequence bound with [`ChordDispatcher::bind_callback`]. It returns if the text
+/// contents were modified or not, like [`TextArea::input`].
+pub type ChordCallback = Arc<dyn Fn(&mut TextArea<'_>) -> bool + Send + Sync>;
+
+/// Action run when a key sequence bound in [`ChordDispatcher`] is completed.
+#[derive(Clone)]
+pub enum ChordAction {
+    /// Run the editor command with [`TextArea::execute`].
+    Command(EditorCommand),
+    /// Run the application-defined callback.
+    Callback(ChordCallback),
+}
+
+impl fmt::Debug for ChordAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Command(c) => f.debug_tuple("Command").field(c).finish(),
+            Self::Callback(_) => f.write_str("Callback(..)"),
+        }
+    }
+}
+
+/// Result of feeding one key input to [`ChordDispatcher`].
+pub(crate) enum Dispatch {
+    /// The input is not a part of any key sequence. It should be handled by the keymap.
+    Ignored,
+    /// The input was buffered as a prefix of some key sequence.
+    Pending,
+    /// The pending key sequence was canceled with `Esc`, timed out, or did not match any binding.
+    Canceled,
+    /// The key sequence was completed.
+    Action(ChordAction),
+}
+
+/// Dispatcher of multi-key sequences (chords) such as `Ctrl+X Ctrl+S` used by [`TextArea::input`].
+///
+/// Key inputs which are prefixes of bound sequences are buffered until the sequence is completed. The buffered prefix
+/// can be obtained with [`TextArea::pending_chord`] for showing a hint to users. The pending sequence is canceled
+/// when `Esc` key is input, when the next input does not continue any bound sequence, or when no input came within
+/// the timeout. Inputs which do not start any sequence are handled with [`Keymap`](crate::Keymap) as usual.
+///
+/// When a sequence is bound and its prefix is also bound, the prefix is never dispatched because it is always
+/// buffered as pending.
+/// ```
+/// use std::sync::atomic::{AtomicBool, Ordering};
+/// use std::sync::Arc;
+/// use tui_textarea::{EditorCommand, Input, Key, TextArea};
+///
+/// let ctrl = |c| Input { key: Key::Char(c), ctrl: true, ..Default::default() };
+///
+/// let mut textarea = TextArea::from(["hello"]);
+///
+/// // Ctrl+X U runs undo
+/// textarea.chords_mut().bind([ctrl('x'), Input { key: Key::Char('u'), ..Default::default() }], EditorCommand::Undo);
+///
+/// // Ctrl+X Ctrl+S runs the application's save function
+/// let saved = Arc::new(AtomicBool::new(false));
+/// let flag = saved.clone();
+/// textarea.chords_mut().bind_callback([ctrl('x'), ctrl('s')], move |_textarea| {
+///     flag.store(true, Ordering::Relaxed);
+///     false
+/// });
+///
+/// textarea.input(ctrl('x'));
+/// assert_eq!(textarea.pending_chord(), [ctrl('x')]);
+/// textarea.input(ctrl('s'));
+/// assert!(textarea.pending_chord().is_empty());
+/// assert!(saved.load(Ordering::Relaxed));
+/// ```
+#[derive(Clone)]
+pub struct ChordDispatcher {
+    bindings: HashMap<Vec<Input>, ChordAction>,
+    pending: Vec<Input>,
+    last_input: Option<Instant>,
+    timeout: Option<Duration>,
+    clock: Clock,
+}
+
+impl Default for ChordDispatcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ChordDispatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChordDispatcher")
+            .field("bindings", &self.bindings)
+            .field("pending", &self.pending)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChordDispatcher {
+    /// Create a dispatcher with no binding. The timeout of pending key sequences is 1 second by default.
+    pub fn new() -> Self {
+        Self {
+            bindings: HashMap::new(),
+            pending: vec![],
+            last_input: None,
+            timeout: Some(Duration::from_secs(1)),
+            clock: Arc::new(Instant::now),
+        }
+    }
+
+    /// Bind the key sequence to the editor command. When the sequence was already bound, the previous action is
+    /// overridden and returned. Binding an empty sequence does nothing.
+    pub fn bind<I>(&mut self, keys: I, command: EditorCommand) -> Option<ChordAction>
+    where
+        I: IntoIterator<Item = Input>,
+    {
+        self.bind_action(keys, ChordAction::Command(command))
+    }
+
+    /// Bind the key sequence to the callback. The callback receives the textarea which handled the last key input.
+    /// When the sequence was already bound, the previous action is overridden and returned. Binding an empty sequence
+    /// does nothing.
+    pub fn bind_callback<I, F>(&mut self, keys: I, callback: F) -> Option<ChordAction>
+    where
+        I: IntoIterator<Item = Input>,
+        F: Fn(&mut TextArea<'_>) -> bool + Send + Sync + 'static,
+    {
+        self.bind_action(keys, ChordAction::Callback(Arc::new(callback)))
+    }
+
+    /// Bind the key sequence to the action. See [`ChordDispatcher::bind`] and [`ChordDispatcher::bind_callback`].
+    pub fn bind_action<I>(&mut self, keys: I, action: ChordAction) -> Option<ChordAction>
+    where
+        I: IntoIterator<Item = Input>,
+    {
+        let keys: Vec<_> = keys.into_iter().collect();
+        if keys.is_empty() {
+            return None;
+        }
+        self.bindings.insert(keys, action)
+    }
+
+    /// Remove the binding of the key sequence. The removed action is returned.
+    pub fn unbind(&mut self, keys: &[Input]) -> Option<ChordAction> {
+        self.bindings.remove(keys)
+    }
+
+    /// Get the action bound to the key sequence.
+    pub fn get(&self, keys: &[Input]) -> Option<&ChordAction> {
+        self.bindings.get(keys)
+    }
+
+    /// Remove all bindings. The pending key sequence is also canceled.
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+        self.cancel();
+    }
+
+    /// Cancel the pending key sequence.
+    pub fn cancel(&mut self) {
+        self.pending.clear();
+        self.last_input = None;
+    }
+
+    /// Set the timeout of the pending key sequence. When no key is input within the timeout after the last input of
+    /// the sequence, the sequence is canceled. `None` disables the timeout.
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) {
+        self.timeout = timeout;
+    }
+
+    /// Get the timeout of the pending key sequence.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Set the clock used for measuring the timeout. By default [`Instant::now`] is used. This is useful for testing.
+    pub fn set_clock<F>(&mut self, clock: F)
+    where
+        F: Fn() -> Instant + Send + Sync + 'static,
+    {
+        self.clock = Arc::new(clock);
+    }
+
+    /// Get the pending key sequence which is a prefix of some bound sequences. An empty slice is returned when no
+    /// sequence is pending or the pending sequence already timed out.
+    pub fn pending(&self) -> &[Input] {
+        if self.is_timed_out((self.clock)()) {
+            &[]
+        } else {
+            &self.pending
+        }
+    }
+
+    fn is_timed_out(&self, now: Instant) -> bool {
+        match (self.last_input, self.timeout) {
+            (Some(last), Some(timeout)) => now.saturating_duration_since(last) > timeout,
+            _ => false,
+        }
+    }
+
+    fn is_prefix(&self, keys: &[Input]) -> bool {
+        self.bindings
+            .keys()
+            .any(|k| k.len() > keys.len() && k.starts_with(keys))
+    }
+
+    pub(crate) fn dispatch(&mut self, input: &Input) -> Dispatch {
+        let now = (self.clock)();
+        if self.is_timed_out(now) {
+            self.cancel();
+        }
+
+        if self.pending.is_empty() && self.bindings.is_empty() {
+            return Dispatch::Ignored;
+        }
+
+        if !self.pending.is_empty() && input.key == Key::Esc {
+            self.cancel();
+            return Dispatch::Canceled;
+        }
+
+        self.pending.push(input.clone());
+
+        if self.is_prefix(&self.pending) {
+            self.last_input = Some(now);
+            return Dispatch::Pending;
+        }
+
+        let action = self.bindings.get(&self.pending).cloned();
+        let continued = self.pending.len() > 1;
+        self.cancel();
+        match action {
+            Some(action) => Dispatch::Action(action),
+            // The input which does not continue the pending sequence is discarded with the sequence
+            None if continued => Dispatch::Canceled,
+            None => Dispatch::Ignored,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::tests::input;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    fn ctrl(c: char) -> Input {
+        input(Key::Char(c), true, false, false)
+    }
+
+    fn key(c: char) -> Input {
+        input(Key::Char(c), false, false, false)
+    }
+
+    fn manual_clock(textarea: &mut TextArea<'_>) -> Arc<Mutex<Instant>> {
+        let now = Arc::new(Mutex::new(Instant::now()));
+        let clock = now.clone();
+        textarea.chords_mut().set_clock(move || *clock.lock().unwrap());
+        now
+    }
+
+    #[test]
+    fn dispatch_command() {
+        let mut t = TextArea::from(["abc"]);
+        t.chords_mut().bind([ctrl('x'), ctrl('d')], EditorCommand::DeleteNextChar);
+
+        assert!(!t.input(ctrl('x')));
+        assert_eq!(t.pending_chord(), [ctrl('x')]);
+        assert_eq!(t.lines(), ["abc"]);
+        assert!(t.input(ctrl('d')));
+        assert!(t.pending_chord().is_empty());
+        assert_eq!(t.lines(), ["bc"]);
+
+        // Inputs which don't start any sequence are handled by keymap
+        assert!(t.input(ctrl('d')));
+        assert_eq!(t.lines(), ["c"]);
+        assert!(t.input(key('x')));
+        assert_eq!(t.lines(), ["xc"]);
+    }
+
+    #[test]
+    fn dispatch_callback() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let c = count.clone();
+        let mut t = TextArea::from(["abc"]);
+        t.chords_mut()
+            .bind_callback([ctrl('k'), ctrl('c'), key('!')], move |t| {
+                c.fetch_add(1, Ordering::Relaxed);
+                t.insert_str("// ")
+            });
+
+        assert!(!t.input(ctrl('k')));
+        assert!(!t.input(ctrl('c')));
+        assert_eq!(t.pending_chord(), [ctrl('k'), ctrl('c')]);
+        assert!(t.input(key('!')));
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert_eq!(t.lines(), ["// abc"]);
+    }
+
+    #[test]
+    fn cancel_with_esc() {
+        let mut t = TextArea::from(["abc"]);
+        t.chords_mut().bind([ctrl('x'), key('u')], EditorCommand::Undo);
+
+        t.input(ctrl('x'));
+        assert!(!t.input(input(Key::Esc, false, false, false)));
+        assert!(t.pending_chord().is_empty());
+        assert!(t.input(key('u')));
+        assert_eq!(t.lines(), ["uabc"]);
+    }
+
+    #[test]
+    fn cancel_with_unbound_input() {
+        let mut t = TextArea::from(["abc"]);
+        t.chords_mut().bind([ctrl('x'), key('u')], EditorCommand::Undo);
+
+        t.input(ctrl('x'));
+        // The unbound sequence is discarded
+        assert!(!t.input(key('a')));
+        assert!(t.pending_chord().is_empty());
+        assert_eq!(t.lines(), ["abc"]);
+    }
+
+    #[test]
+    fn cancel_with_timeout() {
+        let mut t = TextArea::from(["abc"]);
+        t.chords_mut().bind([ctrl('x'), key('u')], EditorCommand::Undo);
+        let now = manual_clock(&mut t);
+
+        t.input(ctrl('x'));
+        *now.lock().unwrap() += Duration::from_millis(1000);
+        assert_eq!(t.pending_chord(), [ctrl('x')]);
+        *now.lock().unwrap() += Duration::from_millis(1);
+        assert!(t.pending_chord().is_empty());
+        // The input after the timeout is handled as a new input
+        assert!(t.input(key('u')));
+        assert_eq!(t.lines(), ["uabc"]);
+
+        t.chords_mut().set_timeout(None);
+        t.input(ctrl('x'));
+        *now.lock().unwrap() += Duration::from_secs(60);
+        assert_eq!(t.pending_chord(), [ctrl('x')]);
+        assert!(t.input(key('u')));
+        assert_eq!(t.lines(), ["abc"]);
+    }
+
+    #[test]
+    fn prefix_binding_is_shadowed() {
+        let mut t = TextArea::from(["abc"]);
+        t.chords_mut().bind([ctrl('x')], EditorCommand::DeleteNextChar);
+        t.chords_mut().bind([ctrl('x'), ctrl('x')], EditorCommand::DeleteChar);
+
+        assert!(!t.input(ctrl('x')));
+        assert_eq!(t.pending_chord(), [ctrl('x')]);
+
+        t.chords_mut().unbind(&[ctrl('x'), ctrl('x')]);
+        t.chords_mut().cancel();
+        assert!(t.input(ctrl('x')));
+        assert_eq!(t.lines(), ["bc"]);
+    }
+
+    #[test]
+    fn bind_empty_sequence() {
+        let mut d = ChordDispatcher::new();
+        assert!(d.bind([], EditorCommand::Undo).is_none());
+        assert!(d.get(&[]).is_none());
+        assert!(d.bind([ctrl('x')], EditorCommand::Undo).is_none());
+        assert!(matches!(
+            d.bind([ctrl('x')], EditorCommand::Redo),
+            Some(ChordAction::Command(EditorCommand::Undo)),
+        ));
+    }
+}
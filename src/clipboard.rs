@@ -0,0 +1,238 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+/// Clipboard shared with [`TextArea`](crate::TextArea) via [`TextArea::set_clipboard`](crate::TextArea::set_clipboard).
+///
+/// When a clipboard is set, text yanked by copy, cut, and deletion is also written to the clipboard, and pasting
+/// reads the clipboard. When the clipboard has text which is different from the last yanked text (e.g. it was copied
+/// in another application), the text is pushed to the kill ring and pasted. Without a clipboard, only the kill ring in
+/// the textarea is used.
+///
+/// Methods take `&self` so that a clipboard can be shared between multiple textareas. Use interior mutability to
+/// store the state.
+/// ```
+/// use std::sync::Mutex;
+/// use tui_textarea::{ClipboardProvider, TextArea};
+///
+/// // Fake clipboard which records the copied texts
+/// #[derive(Default)]
+/// struct Recorder(Mutex<Vec<String>>);
+///
+/// impl ClipboardProvider for Recorder {
+///     fn set_text(&self, text: &str) {
+///         self.0.lock().unwrap().push(text.to_string());
+///     }
+///     fn get_text(&self) -> Option<String> {
+///         self.0.lock().unwrap().last().cloned()
+///     }
+/// }
+///
+/// let mut textarea = TextArea::from(["hello"]);
+/// textarea.set_clipboard(Recorder::default());
+///
+/// textarea.select_all();
+/// textarea.copy();
+/// assert_eq!(textarea.clipboard().unwrap().get_text().as_deref(), Some("hello"));
+/// ```
+pub trait ClipboardProvider: Send + Sync {
+    /// Write the text to the clipboard.
+    fn set_text(&self, text: &str);
+    /// Read the text in the clipboard. `None` means the clipboard is empty or is not readable.
+    fn get_text(&self) -> Option<String>;
+}
+
+/// In-memory clipboard. Cloned values share the same clipboard so it can be used to share yanked text between
+/// multiple textareas.
+/// ```
+/// use tui_textarea::{MemoryClipboard, TextArea};
+///
+/// let clipboard = MemoryClipboard::new();
+/// let mut src = TextArea::from(["hello"]);
+/// src.set_clipboard(clipboard.clone());
+/// let mut dst = TextArea::default();
+/// dst.set_clipboard(clipboard);
+///
+/// src.select_all();
+/// src.copy();
+/// dst.paste();
+/// assert_eq!(dst.lines(), ["hello"]);
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct MemoryClipboard {
+    text: Arc<Mutex<Option<String>>>,
+}
+
+impl MemoryClipboard {
+    /// Create a new empty clipboard.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ClipboardProvider for MemoryClipboard {
+    fn set_text(&self, text: &str) {
+        *self.text.lock().unwrap() = Some(text.to_string());
+    }
+
+    fn get_text(&self) -> Option<String> {
+        self.text.lock().unwrap().clone()
+    }
+}
+
+/// Clipboard which copies text to the host system's clipboard with OSC 52 escape sequence. Since the sequence is
+/// handled by the terminal, copied text reaches the user's desktop even over SSH.
+///
+/// Copied text is not written to the terminal immediately since the escape sequence would be interleaved with the
+/// output of the terminal backend. Instead, the sequences are queued and written by
+/// [`Osc52Clipboard::write_pending`] to the writer of the backend, typically after drawing a frame. Cloned values share
+/// the same queue so keep a clone to write the sequences.
+///
+/// Most terminals don't allow reading the clipboard with OSC 52, so this clipboard remembers the last copied text and
+/// returns it on pasting.
+/// ```
+/// use std::io::{self, Write};
+/// use tui_textarea::{Osc52Clipboard, TextArea};
+///
+/// // Terminal backends such as `CrosstermBackend` implement `Write`
+/// fn draw<B: Write>(backend: &mut B, textarea: &TextArea, clipboard: &Osc52Clipboard) -> io::Result<()> {
+///     // Draw the textarea with the backend...
+///
+///     // Write the escape sequences through the same backend after the frame was drawn
+///     clipboard.write_pending(backend)
+/// }
+///
+/// let clipboard = Osc52Clipboard::new();
+/// let mut textarea = TextArea::from(["hello"]);
+/// textarea.set_clipboard(clipboard.clone());
+///
+/// textarea.select_all();
+/// textarea.copy();
+///
+/// let mut backend = vec![];
+/// draw(&mut backend, &textarea, &clipboard).unwrap();
+/// assert_eq!(backend, b"\x1b]52;c;aGVsbG8=\x07");
+/// ```
+#[derive(Clone, Default)]
+pub struct Osc52Clipboard {
+    pending: Arc<Mutex<Vec<String>>>,
+    last: Arc<Mutex<Option<String>>>,
+}
+
+impl Osc52Clipboard {
+    /// Create a clipboard with an empty queue of OSC 52 escape sequences.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Write the OSC 52 escape sequences of the texts copied since the last call to the writer and flush it. Pass the
+    /// terminal backend (e.g. `Terminal::backend_mut`) so that the sequences are not interleaved with its output. The
+    /// sequences which failed to be written are dropped.
+    pub fn write_pending<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        let pending = std::mem::take(&mut *self.pending.lock().unwrap());
+        if pending.is_empty() {
+            return Ok(());
+        }
+        for seq in pending {
+            writer.write_all(seq.as_bytes())?;
+        }
+        writer.flush()
+    }
+
+    /// Return whether some escape sequences are waiting for [`Osc52Clipboard::write_pending`].
+    pub fn has_pending(&self) -> bool {
+        !self.pending.lock().unwrap().is_empty()
+    }
+}
+
+impl fmt::Debug for Osc52Clipboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Osc52Clipboard")
+            .field("pending", &self.pending.lock().unwrap().len())
+            .field("last", &self.last)
+            .finish()
+    }
+}
+
+impl ClipboardProvider for Osc52Clipboard {
+    fn set_text(&self, text: &str) {
+        let seq = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+        self.pending.lock().unwrap().push(seq);
+        *self.last.lock().unwrap() = Some(text.to_string());
+    }
+
+    fn get_text(&self) -> Option<String> {
+        self.last.lock().unwrap().clone()
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as usize) << 16 | (b[1] as usize) << 8 | b[2] as usize;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(TABLE[(n >> (18 - i * 6)) & 0x3f] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_encode() {
+        for (input, want) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+            ("あ\n", "44GCCg=="),
+        ] {
+            assert_eq!(base64(input.as_bytes()), want, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn osc52_sequence() {
+        let clipboard = Osc52Clipboard::new();
+        assert_eq!(clipboard.get_text(), None);
+        clipboard.clone().set_text("foo");
+        clipboard.set_text("hello\nworld");
+        assert!(clipboard.has_pending());
+        let mut buf = vec![];
+        clipboard.write_pending(&mut buf).unwrap();
+        let written = String::from_utf8(buf).unwrap();
+        assert_eq!(written, "\x1b]52;c;Zm9v\x07\x1b]52;c;aGVsbG8Kd29ybGQ=\x07");
+        assert_eq!(clipboard.get_text().as_deref(), Some("hello\nworld"));
+
+        // Written sequences are not written again
+        assert!(!clipboard.has_pending());
+        let mut buf = vec![];
+        clipboard.write_pending(&mut buf).unwrap();
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn memory_clipboard_shared() {
+        let a = MemoryClipboard::new();
+        let b = a.clone();
+        assert_eq!(b.get_text(), None);
+        a.set_text("foo");
+        assert_eq!(b.get_text().as_deref(), Some("foo"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::bracket::{find_matching_bracket, DEFAULT_BRACKET_QUOTES};
 use crate::widget::Viewport;
 use crate::word::{
     find_word_inclusive_end_forward, find_word_start_backward, find_word_start_forward,
@@ -263,7 +264,9 @@ pub enum CursorMove {
     /// use tui_textarea::{TextArea, CursorMove};
     ///
     /// let mut textarea = TextArea::from(["This is a very long line that will wrap"]);
+    /// # #[cfg(feature = "wrap")]
     /// textarea.set_wrap(true);
+    /// # #[cfg(feature = "wrap")]
     /// textarea.set_wrap_width(Some(20));
     ///
     /// // Move to end of text
@@ -280,13 +283,36 @@ pub enum CursorMove {
     /// use tui_textarea::{TextArea, CursorMove};
     ///
     /// let mut textarea = TextArea::from(["This is a very long line that will wrap"]);
+    /// # #[cfg(feature = "wrap")]
     /// textarea.set_wrap(true);
+    /// # #[cfg(feature = "wrap")]
     /// textarea.set_wrap_width(Some(20));
     ///
     /// // Move down one visual line
     /// textarea.move_cursor(CursorMove::VisualDown);
     /// ```
     VisualDown,
+    /// Move cursor to the bracket matching with the bracket under the cursor. When no bracket is under the cursor, the
+    /// bracket before the cursor is used. `()`, `[]`, and `{}` are matched across lines. Brackets in quoted regions are
+    /// skipped (see [`TextArea::set_bracket_quotes`](crate::TextArea::set_bracket_quotes)). When no matching bracket
+    /// is found, the cursor does not move.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {", "    g(\")\");", "}"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 7));
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (0, 7));
+    ///
+    /// // The bracket in the string literal is skipped
+    /// textarea.move_cursor(CursorMove::Jump(1, 5));
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (1, 9));
+    /// ```
+    MatchingBracket,
 }
 
 impl CursorMove {
@@ -411,6 +437,11 @@ impl CursorMove {
                 // The actual visual movement logic will be implemented in TextArea's move_cursor method.
                 Down.next_cursor((row, col), lines, viewport)
             }
+            MatchingBracket => {
+                // Quotes to skip are configured on `TextArea`. The movement with them is implemented in TextArea's
+                // move_cursor method. Here the default quotes are used.
+                find_matching_bracket(lines, (row, col), DEFAULT_BRACKET_QUOTES).map(|(_, to)| to)
+            }
         }
     }
 }
@@ -12,6 +12,7 @@ use unicode_width::UnicodeWidthChar as _;
 
 enum Boundary {
     Cursor(Style),
+    MatchingBracket(Style),
     Select(Style),
     #[cfg(feature = "search")]
     Search(Style),
@@ -22,7 +23,8 @@ impl Boundary {
     fn cmp(&self, other: &Boundary) -> Ordering {
         fn rank(b: &Boundary) -> u8 {
             match b {
-                Boundary::Cursor(_) => 3,
+                Boundary::Cursor(_) => 4,
+                Boundary::MatchingBracket(_) => 3,
                 #[cfg(feature = "search")]
                 Boundary::Search(_) => 2,
                 Boundary::Select(_) => 1,
@@ -35,6 +37,7 @@ impl Boundary {
     fn style(&self) -> Option<Style> {
         match self {
             Boundary::Cursor(s) => Some(*s),
+            Boundary::MatchingBracket(s) => Some(*s),
             Boundary::Select(s) => Some(*s),
             #[cfg(feature = "search")]
             Boundary::Search(s) => Some(*s),
@@ -146,6 +149,14 @@ impl<'a> LineHighlighter<'a> {
         self.style_begin = style;
     }
 
+    pub fn matching_bracket(&mut self, col: usize, style: Style) {
+        if let Some((start, c)) = self.line.char_indices().nth(col) {
+            self.boundaries
+                .push((Boundary::MatchingBracket(style), start));
+            self.boundaries.push((Boundary::End, start + c.len_utf8()));
+        }
+    }
+
     #[cfg(feature = "search")]
     pub fn search(&mut self, matches: impl Iterator<Item = (usize, usize)>, style: Style) {
         for (start, end) in matches {
@@ -252,6 +263,7 @@ impl<'a> LineHighlighter<'a> {
 }
 
 /// Extract spans that correspond to a character range within a highlighted line
+#[cfg(feature = "wrap")]
 pub fn extract_segment_spans<'a>(
     highlighted_line: Line<'a>,
     segment_start_char: usize,
@@ -462,6 +474,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn into_spans_matching_bracket() {
+        const BRACKET: Style = Style::new().bg(Color::Magenta);
+        let tests = [
+            (
+                "(a)",
+                0,
+                &[0, 2][..],
+                &[("(", CUR), ("a", LINE), (")", BRACKET)][..],
+            ),
+            (
+                "(a)",
+                3,
+                &[0, 2][..],
+                &[("(", BRACKET), ("a", LINE), (")", BRACKET), (" ", CUR)][..],
+            ),
+            (
+                "あ(\t)",
+                0,
+                &[1][..],
+                &[("あ", CUR), ("(", BRACKET), (" )", LINE)][..],
+            ),
+        ];
+        for test in tests {
+            let (line, col, brackets, want) = test;
+            let mut lh = LineHighlighter::new(line, CUR, 4, None, SEL);
+            lh.cursor_line(col, LINE);
+            for &b in brackets {
+                lh.matching_bracket(b, BRACKET);
+            }
+            assert_spans(lh, want, test);
+        }
+    }
+
     #[cfg(feature = "search")]
     #[test]
     fn into_spans_search() {
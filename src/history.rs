@@ -122,11 +122,14 @@ impl Edit {
     }
 }
 
+// Edits pushed while a group is open are undone and redone at once
 #[derive(Clone, Debug)]
 pub struct History {
     index: usize,
     max_items: usize,
-    edits: VecDeque<Edit>,
+    edits: VecDeque<Vec<Edit>>,
+    group_depth: usize,
+    group_started: bool,
 }
 
 impl History {
@@ -135,6 +138,8 @@ impl History {
             index: 0,
             max_items,
             edits: VecDeque::new(),
+            group_depth: 0,
+            group_started: false,
         }
     }
 
@@ -143,6 +148,13 @@ impl History {
             return;
         }
 
+        if self.group_started {
+            if let Some(group) = self.edits.back_mut() {
+                group.push(edit);
+                return;
+            }
+        }
+
         if self.edits.len() == self.max_items {
             self.edits.pop_front();
             self.index = self.index.saturating_sub(1);
@@ -153,24 +165,46 @@ impl History {
         }
 
         self.index += 1;
-        self.edits.push_back(edit);
+        self.edits.push_back(vec![edit]);
+        self.group_started = self.group_depth > 0;
+    }
+
+    // Start grouping edits. Groups can be nested and the outermost group is recorded as one entry
+    pub fn begin_group(&mut self) {
+        if self.group_depth == 0 {
+            self.group_started = false;
+        }
+        self.group_depth += 1;
+    }
+
+    pub fn end_group(&mut self) {
+        self.group_depth = self.group_depth.saturating_sub(1);
+        if self.group_depth == 0 {
+            self.group_started = false;
+        }
     }
 
     pub fn redo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
+        self.group_started = false;
         if self.index == self.edits.len() {
             return None;
         }
-        let edit = &self.edits[self.index];
-        edit.redo(lines);
+        let group = &self.edits[self.index];
+        for edit in group.iter() {
+            edit.redo(lines);
+        }
         self.index += 1;
-        Some(edit.cursor_after())
+        group.last().map(Edit::cursor_after)
     }
 
     pub fn undo(&mut self, lines: &mut Vec<String>) -> Option<(usize, usize)> {
+        self.group_started = false;
         self.index = self.index.checked_sub(1)?;
-        let edit = &self.edits[self.index];
-        edit.undo(lines);
-        Some(edit.cursor_before())
+        let group = &self.edits[self.index];
+        for edit in group.iter().rev() {
+            edit.undo(lines);
+        }
+        group.first().map(Edit::cursor_before)
     }
 
     pub fn max_items(&self) -> usize {
@@ -647,4 +681,47 @@ mod tests {
             assert_eq!(&lines, &before, "{test:?}");
         }
     }
+
+    #[test]
+    fn undo_redo_group() {
+        let insert = |row, col| {
+            Edit::new(
+                EditKind::InsertChar('x'),
+                Pos::new(row, col, col),
+                Pos::new(row, col + 1, col + 1),
+            )
+        };
+        let mut lines = vec!["".to_string()];
+        let mut history = History::new(10);
+
+        let push = |history: &mut History, lines: &mut Vec<String>, col| {
+            let edit = insert(0, col);
+            edit.redo(lines);
+            history.push(edit);
+        };
+
+        push(&mut history, &mut lines, 0);
+        history.begin_group();
+        push(&mut history, &mut lines, 1);
+        history.begin_group(); // Nested group is merged into the outer group
+        push(&mut history, &mut lines, 2);
+        history.end_group();
+        push(&mut history, &mut lines, 3);
+        history.end_group();
+        push(&mut history, &mut lines, 4);
+        assert_eq!(lines, ["xxxxx"]);
+
+        assert_eq!(history.undo(&mut lines), Some((0, 4)));
+        assert_eq!(lines, ["xxxx"]);
+        assert_eq!(history.undo(&mut lines), Some((0, 1)));
+        assert_eq!(lines, ["x"]);
+        assert_eq!(history.redo(&mut lines), Some((0, 4)));
+        assert_eq!(lines, ["xxxx"]);
+
+        // Empty group adds no entry
+        history.begin_group();
+        history.end_group();
+        assert_eq!(history.undo(&mut lines), Some((0, 1)));
+        assert_eq!(lines, ["x"]);
+    }
 }
@@ -0,0 +1,216 @@
+/// Rule to decide indentation on auto-indent. Set a rule with
+/// [`TextArea::set_indent_rule`](crate::TextArea::set_indent_rule). The rule is used only when auto-indent is enabled
+/// by [`TextArea::set_auto_indent`](crate::TextArea::set_auto_indent).
+///
+/// Both methods return `false` by default so that only the necessary one needs to be implemented.
+/// ```
+/// use tui_textarea::{IndentRule, TextArea};
+///
+/// // Indent after `begin`
+/// struct Keyword;
+///
+/// impl IndentRule for Keyword {
+///     fn indent_after(&self, line: &str) -> bool {
+///         line.trim_end().ends_with("begin")
+///     }
+/// }
+///
+/// let mut textarea = TextArea::from(["begin"]);
+/// textarea.set_auto_indent(true);
+/// textarea.set_indent_rule(Keyword);
+///
+/// textarea.move_cursor(tui_textarea::CursorMove::End);
+/// textarea.insert_newline();
+/// assert_eq!(textarea.lines(), ["begin", "    "]);
+/// ```
+pub trait IndentRule: Send + Sync {
+    /// Return `true` when the next line of the line should be indented one more level. `line` is the text before the
+    /// cursor when inserting a newline.
+    fn indent_after(&self, line: &str) -> bool {
+        let _ = line;
+        false
+    }
+
+    /// Return `true` when typing the character at the head of line should dedent the line by one level.
+    fn dedent_on(&self, c: char) -> bool {
+        let _ = c;
+        false
+    }
+}
+
+/// Indent rule for bracket-based languages. By default, the next line is indented after a line ending with `{`, `(`,
+/// `[`, or `:`, and the line is dedented when typing `}`, `)`, or `]` at the head of line.
+/// ```
+/// use tui_textarea::{BracketIndent, CursorMove, TextArea};
+///
+/// let mut textarea = TextArea::from(["fn f() {"]);
+/// textarea.set_auto_indent(true);
+/// textarea.set_indent_rule(BracketIndent::default());
+///
+/// textarea.move_cursor(CursorMove::End);
+/// textarea.insert_newline();
+/// textarea.insert_str("x");
+/// textarea.insert_newline();
+/// textarea.insert_char('}');
+/// assert_eq!(textarea.lines(), ["fn f() {", "    x", "}"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BracketIndent {
+    openers: Vec<char>,
+    closers: Vec<char>,
+}
+
+impl BracketIndent {
+    /// Create a rule which indents after lines ending with one of `openers` and dedents on typing one of `closers`.
+    /// ```
+    /// use tui_textarea::BracketIndent;
+    ///
+    /// // Only curly brackets
+    /// let rule = BracketIndent::new(['{'], ['}']);
+    /// ```
+    pub fn new<O, C>(openers: O, closers: C) -> Self
+    where
+        O: IntoIterator<Item = char>,
+        C: IntoIterator<Item = char>,
+    {
+        Self {
+            openers: openers.into_iter().collect(),
+            closers: closers.into_iter().collect(),
+        }
+    }
+}
+
+impl Default for BracketIndent {
+    fn default() -> Self {
+        Self::new(['{', '(', '[', ':'], ['}', ')', ']'])
+    }
+}
+
+impl IndentRule for BracketIndent {
+    fn indent_after(&self, line: &str) -> bool {
+        line.trim_end()
+            .chars()
+            .last()
+            .map_or(false, |c| self.openers.contains(&c))
+    }
+
+    fn dedent_on(&self, c: char) -> bool {
+        self.closers.contains(&c)
+    }
+}
+
+// Leading whitespaces of the line
+pub(crate) fn leading_whitespace(line: &str) -> &str {
+    let end = line
+        .find(|c| c != ' ' && c != '\t')
+        .unwrap_or(line.len());
+    &line[..end]
+}
+
+// Whether the line has only spaces and tabs
+pub(crate) fn is_blank(line: &str) -> bool {
+    leading_whitespace(line).len() == line.len()
+}
+
+// Display width of the indentation. A tab advances to the next tab stop
+pub(crate) fn indent_width(indent: &str, tab_len: u8) -> usize {
+    indent.chars().fold(0, |width, c| match c {
+        '\t' if tab_len > 0 => width + tab_len as usize - width % tab_len as usize,
+        '\t' => width,
+        _ => width + 1,
+    })
+}
+
+// Indentation string for the display width. With hard tab indent, tabs are used as much as possible
+pub(crate) fn indent_string(width: usize, tab_len: u8, hard_tab: bool) -> String {
+    if hard_tab && tab_len > 0 {
+        let tab_len = tab_len as usize;
+        let mut s = "\t".repeat(width / tab_len);
+        s.push_str(&" ".repeat(width % tab_len));
+        s
+    } else {
+        " ".repeat(width)
+    }
+}
+
+// Number of characters removed from the head of the line by dedenting one level. Spaces up to the tab length or one
+// tab are removed
+pub(crate) fn dedent_len(line: &str, tab_len: u8) -> usize {
+    let mut width = 0;
+    for (i, c) in leading_whitespace(line).chars().enumerate() {
+        if c == '\t' {
+            return i + 1;
+        }
+        width += 1;
+        if width >= tab_len as usize {
+            return i + 1;
+        }
+    }
+    width
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn indent_width_and_string() {
+        for (indent, tab_len, width) in [
+            ("", 4, 0),
+            ("    ", 4, 4),
+            ("\t", 4, 4),
+            ("  \t", 4, 4),
+            ("\t  ", 4, 6),
+            ("\t\t", 2, 4),
+            ("\t", 0, 0),
+        ] {
+            assert_eq!(indent_width(indent, tab_len), width, "{:?}", indent);
+        }
+
+        assert_eq!(indent_string(6, 4, false), "      ");
+        assert_eq!(indent_string(6, 4, true), "\t  ");
+        assert_eq!(indent_string(8, 4, true), "\t\t");
+        assert_eq!(indent_string(3, 0, true), "   ");
+    }
+
+    #[test]
+    fn bracket_indent_rule() {
+        let rule = BracketIndent::default();
+        assert!(rule.indent_after("fn main() {"));
+        assert!(rule.indent_after("if x:  "));
+        assert!(rule.indent_after("f("));
+        assert!(!rule.indent_after("f()"));
+        assert!(!rule.indent_after(""));
+        assert!(rule.dedent_on('}'));
+        assert!(!rule.dedent_on('{'));
+
+        let rule = BracketIndent::new(['{'], ['}']);
+        assert!(!rule.indent_after("if x:"));
+        assert!(!rule.dedent_on(')'));
+    }
+
+    #[test]
+    fn dedent_len_of_line() {
+        for (line, tab_len, want) in [
+            ("        foo", 4, 4),
+            ("  foo", 4, 2),
+            ("\t\tfoo", 4, 1),
+            ("  \tfoo", 4, 3),
+            ("foo", 4, 0),
+            ("", 4, 0),
+            ("   ", 2, 2),
+        ] {
+            assert_eq!(dedent_len(line, tab_len), want, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn leading_whitespace_of_line() {
+        assert_eq!(leading_whitespace("  \tfoo  "), "  \t");
+        assert_eq!(leading_whitespace("foo"), "");
+        assert_eq!(leading_whitespace("   "), "   ");
+        assert!(is_blank(" \t"));
+        assert!(is_blank(""));
+        assert!(!is_blank("  a"));
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Input, Key};
+use super::{EditorEvent, Input, Key};
 use crate::crossterm::event::{
     Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent, MouseEventKind,
 };
@@ -14,6 +14,17 @@ impl From<Event> for Input {
     }
 }
 
+impl From<Event> for EditorEvent {
+    /// Convert [`crossterm::event::Event`] into [`EditorEvent`]. Bracketed paste events are converted into
+    /// [`EditorEvent::Paste`] and other events are converted into [`EditorEvent::Input`].
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Paste(text) => Self::Paste(text),
+            event => Self::Input(Input::from(event)),
+        }
+    }
+}
+
 impl From<KeyCode> for Key {
     /// Convert [`crossterm::event::KeyCode`] into [`Key`].
     fn from(code: KeyCode) -> Self {
@@ -25,8 +36,9 @@ impl From<KeyCode> for Key {
             KeyCode::Right => Key::Right,
             KeyCode::Up => Key::Up,
             KeyCode::Down => Key::Down,
-            KeyCode::Tab => Key::Tab,
+            KeyCode::Tab | KeyCode::BackTab => Key::Tab,
             KeyCode::Delete => Key::Delete,
+            KeyCode::Insert => Key::Insert,
             KeyCode::Home => Key::Home,
             KeyCode::End => Key::End,
             KeyCode::PageUp => Key::PageUp,
@@ -49,7 +61,8 @@ impl From<KeyEvent> for Input {
 
         let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
         let alt = key.modifiers.contains(KeyModifiers::ALT);
-        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        // Some terminals report Shift+Tab as `BackTab` without the shift modifier
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT) || key.code == KeyCode::BackTab;
         let key = Key::from(key.code);
 
         Self {
@@ -67,6 +80,10 @@ impl From<MouseEventKind> for Key {
         match kind {
             MouseEventKind::ScrollDown => Key::MouseScrollDown,
             MouseEventKind::ScrollUp => Key::MouseScrollUp,
+            #[cfg(feature = "crossterm")]
+            MouseEventKind::ScrollLeft => Key::MouseScrollLeft,
+            #[cfg(feature = "crossterm")]
+            MouseEventKind::ScrollRight => Key::MouseScrollRight,
             _ => Key::Null,
         }
     }
@@ -151,6 +168,14 @@ mod tests {
                 key_event(KeyCode::Home, KeyModifiers::ALT),
                 input(Key::Home, false, true, false),
             ),
+            (
+                key_event(KeyCode::Insert, KeyModifiers::empty()),
+                input(Key::Insert, false, false, false),
+            ),
+            (
+                key_event(KeyCode::BackTab, KeyModifiers::empty()),
+                input(Key::Tab, false, false, true),
+            ),
             (
                 key_event(
                     KeyCode::F(1),
@@ -193,6 +218,16 @@ mod tests {
                 ),
                 input(Key::MouseScrollUp, true, true, false),
             ),
+            #[cfg(feature = "crossterm")]
+            (
+                mouse_event(MouseEventKind::ScrollLeft, KeyModifiers::empty()),
+                input(Key::MouseScrollLeft, false, false, false),
+            ),
+            #[cfg(feature = "crossterm")]
+            (
+                mouse_event(MouseEventKind::ScrollRight, KeyModifiers::SHIFT),
+                input(Key::MouseScrollRight, false, false, true),
+            ),
             (
                 mouse_event(MouseEventKind::Moved, KeyModifiers::CONTROL),
                 input(Key::Null, true, false, false),
@@ -244,6 +279,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn event_to_editor_event() {
+        for (from, to) in [
+            (
+                Event::Key(key_event(KeyCode::Char('a'), KeyModifiers::empty())),
+                EditorEvent::Input(input(Key::Char('a'), false, false, false)),
+            ),
+            (
+                Event::Paste("foo\nbar".into()),
+                EditorEvent::Paste("foo\nbar".into()),
+            ),
+            (
+                Event::FocusLost,
+                EditorEvent::Input(input(Key::Null, false, false, false)),
+            ),
+        ] {
+            assert_eq!(EditorEvent::from(from.clone()), to, "{:?} -> {:?}", from, to);
+        }
+    }
+
     // Regression for https://github.com/rhysd/tui-textarea/issues/14
     #[test]
     fn ignore_key_release_event() {
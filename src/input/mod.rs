@@ -4,12 +4,15 @@ mod crossterm;
 mod termion;
 #[cfg(feature = "termwiz")]
 mod termwiz;
+mod notation;
 
 #[cfg(feature = "arbitrary")]
 use arbitrary::Arbitrary;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+pub use notation::ParseInputError;
+
 /// Backend-agnostic key input kind.
 ///
 /// This type is marked as `#[non_exhaustive]` since more keys may be supported in the future.
@@ -38,6 +41,8 @@ pub enum Key {
     Tab,
     /// Delete key
     Delete,
+    /// Insert key
+    Insert,
     /// Home key
     Home,
     /// End key
@@ -58,6 +63,10 @@ pub enum Key {
     MouseScrollDown,
     /// Virtual key to scroll up by mouse
     MouseScrollUp,
+    /// Virtual key to scroll left by mouse (horizontal wheel)
+    MouseScrollLeft,
+    /// Virtual key to scroll right by mouse (horizontal wheel)
+    MouseScrollRight,
     /// Mouse click at position (column, row). This key is supported when `mouse` feature is enabled
     #[cfg(feature = "mouse")]
     MouseClick(u16, u16),
@@ -126,8 +135,67 @@ pub struct Input {
     pub shift: bool,
 }
 
+impl Input {
+    // Input in the canonical form used for key bindings. Terminals report Shift+Z as `Z` with Shift key, so a lowercase
+    // letter with Shift key is converted to uppercase. A letter whose uppercase is not one character (e.g. 'ß') is kept
+    pub(crate) fn canonical(&self) -> Self {
+        let key = match self.key {
+            Key::Char(c) if self.shift && c.is_lowercase() => {
+                let mut upper = c.to_uppercase();
+                match (upper.next(), upper.next()) {
+                    (Some(u), None) => Key::Char(u),
+                    _ => self.key,
+                }
+            }
+            key => key,
+        };
+        Self {
+            key,
+            ..self.clone()
+        }
+    }
+}
+
+/// Backend-agnostic input event which carries pasted text in addition to key inputs.
+///
+/// Terminals supporting bracketed paste report pasted text as a single event. Passing it to
+/// [`TextArea::handle_event`](crate::TextArea::handle_event) inserts the whole text as one edit instead of a long
+/// sequence of key inputs. When `crossterm` or `termwiz` features are enabled, converting respective event types into
+/// this type is defined. Note that bracketed paste needs to be enabled in the terminal (e.g.
+/// `crossterm::event::EnableBracketedPaste`).
+/// ```no_run
+/// use tui_textarea::{TextArea, EditorEvent};
+/// use crossterm::event::read;
+///
+/// let mut textarea = TextArea::default();
+///
+/// match EditorEvent::from(read().unwrap()) {
+///     EditorEvent::Paste(text) => println!("pasted: {}", text),
+///     EditorEvent::Input(input) => println!("key: {}", input),
+///     _ => {}
+/// }
+/// ```
+///
+/// This type is marked as `#[non_exhaustive]` since more events may be supported in the future.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EditorEvent {
+    /// Key or mouse input.
+    Input(Input),
+    /// Text pasted at once.
+    Paste(String),
+}
+
+impl From<Input> for EditorEvent {
+    fn from(input: Input) -> Self {
+        Self::Input(input)
+    }
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
     #[allow(dead_code)]
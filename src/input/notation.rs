@@ -0,0 +1,289 @@
+use super::{Input, Key};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when parsing a key notation like `"ctrl+shift+z"` into [`Input`] failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseInputError {
+    notation: String,
+    reason: &'static str,
+}
+
+impl ParseInputError {
+    fn new(notation: &str, reason: &'static str) -> Self {
+        Self {
+            notation: notation.to_string(),
+            reason,
+        }
+    }
+}
+
+impl fmt::Display for ParseInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid key notation {:?}: {}", self.notation, self.reason)
+    }
+}
+
+impl Error for ParseInputError {}
+
+// Names of keys. The first name is used for formatting
+const KEY_NAMES: &[(&str, Key)] = &[
+    ("space", Key::Char(' ')),
+    ("backspace", Key::Backspace),
+    ("enter", Key::Enter),
+    ("return", Key::Enter),
+    ("left", Key::Left),
+    ("right", Key::Right),
+    ("up", Key::Up),
+    ("down", Key::Down),
+    ("tab", Key::Tab),
+    ("delete", Key::Delete),
+    ("del", Key::Delete),
+    ("insert", Key::Insert),
+    ("ins", Key::Insert),
+    ("home", Key::Home),
+    ("end", Key::End),
+    ("pageup", Key::PageUp),
+    ("pagedown", Key::PageDown),
+    ("esc", Key::Esc),
+    ("escape", Key::Esc),
+    ("copy", Key::Copy),
+    ("cut", Key::Cut),
+    ("paste", Key::Paste),
+    ("wheeldown", Key::MouseScrollDown),
+    ("wheelup", Key::MouseScrollUp),
+    ("wheelleft", Key::MouseScrollLeft),
+    ("wheelright", Key::MouseScrollRight),
+    ("null", Key::Null),
+];
+
+fn parse_key(name: &str) -> Option<Key> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(Key::Char(c));
+    }
+
+    let lower = name.to_ascii_lowercase();
+    if let Some((_, key)) = KEY_NAMES.iter().find(|(n, _)| *n == lower) {
+        return Some(*key);
+    }
+    if let Some(num) = lower.strip_prefix('f') {
+        if let Ok(n) = num.parse() {
+            return Some(Key::F(n));
+        }
+    }
+    None
+}
+
+/// Parse a human-friendly key notation into [`Input`]. The notation consists of modifiers (`ctrl`, `alt`, `shift`)
+/// and a key joined with `+`. Modifiers and key names are case-insensitive. A single character means the character
+/// key (case-sensitive). Key names are `space`, `backspace`, `enter`, `left`, `right`, `up`, `down`, `tab`, `delete`,
+/// `insert`, `home`, `end`, `pageup`, `pagedown`, `esc`, `f1`...`f255`, `copy`, `cut`, `paste`, `wheeldown`, `wheelup`,
+/// `wheelleft`, `wheelright`, and `null`.
+///
+/// With `shift`, a lowercase letter is converted to uppercase since terminals report Shift+Z as `Z` with Shift key.
+///
+/// ```
+/// use tui_textarea::{Input, Key};
+///
+/// let input: Input = "ctrl+shift+z".parse().unwrap();
+/// assert_eq!(input, Input { key: Key::Char('Z'), ctrl: true, alt: false, shift: true });
+///
+/// let input: Input = "Alt+PageDown".parse().unwrap();
+/// assert_eq!(input, Input { key: Key::PageDown, ctrl: false, alt: true, shift: false });
+///
+/// let input: Input = "ctrl++".parse().unwrap();
+/// assert_eq!(input, Input { key: Key::Char('+'), ctrl: true, alt: false, shift: false });
+///
+/// assert!("ctrl+foo".parse::<Input>().is_err());
+/// ```
+impl FromStr for Input {
+    type Err = ParseInputError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut input = Input::default();
+        let mut rest = s;
+        loop {
+            let (token, next) = match rest.find('+') {
+                // "+" at the head of the rest is the '+' key itself (e.g. "ctrl++")
+                Some(0) | None => (rest, None),
+                Some(i) => (&rest[..i], Some(&rest[i + 1..])),
+            };
+            let next = match next {
+                Some(next) => next,
+                None => {
+                    input.key = match parse_key(token) {
+                        Some(key) => key,
+                        None if token.is_empty() => return Err(ParseInputError::new(s, "key is missing")),
+                        None => return Err(ParseInputError::new(s, "unknown key")),
+                    };
+                    return Ok(input.canonical());
+                }
+            };
+            let modifier = match token.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => &mut input.ctrl,
+                "alt" | "meta" => &mut input.alt,
+                "shift" => &mut input.shift,
+                _ => return Err(ParseInputError::new(s, "unknown modifier")),
+            };
+            if *modifier {
+                return Err(ParseInputError::new(s, "duplicate modifier"));
+            }
+            *modifier = true;
+            rest = next;
+        }
+    }
+}
+
+// Lowercase letter of the uppercase letter which is converted back to it by `Input::canonical`
+fn unshifted(c: char) -> Option<char> {
+    let mut lower = c.to_lowercase();
+    let l = match (lower.next(), lower.next()) {
+        (Some(l), None) if l != c => l,
+        _ => return None,
+    };
+    let mut upper = l.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(u), None) if u == c => Some(l),
+        _ => None,
+    }
+}
+
+/// Format [`Input`] in the key notation which can be parsed with [`str::parse`]. Modifiers are put in the order of
+/// `ctrl`, `alt`, and `shift`. A letter with `shift` is written in lowercase.
+///
+/// ```
+/// use tui_textarea::{Input, Key};
+///
+/// let input = Input { key: Key::Char('z'), ctrl: true, alt: false, shift: true };
+/// assert_eq!(input.to_string(), "ctrl+shift+z");
+///
+/// let input = Input { key: Key::F(1), ctrl: false, alt: true, shift: false };
+/// assert_eq!(input.to_string(), "alt+f1");
+/// ```
+impl fmt::Display for Input {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("ctrl+")?;
+        }
+        if self.alt {
+            f.write_str("alt+")?;
+        }
+        if self.shift {
+            f.write_str("shift+")?;
+        }
+        match self.key {
+            Key::Char(' ') => f.write_str("space"),
+            // Shifted letter is written in lowercase like "ctrl+shift+z"
+            Key::Char(c) if self.shift => write!(f, "{}", unshifted(c).unwrap_or(c)),
+            Key::Char(c) => write!(f, "{}", c),
+            Key::F(n) => write!(f, "f{}", n),
+            key => match KEY_NAMES.iter().find(|(_, k)| *k == key) {
+                Some((name, _)) => f.write_str(name),
+                // Keys with mouse positions have no notation
+                None => write!(f, "{:?}", key),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::tests::input;
+
+    #[test]
+    fn parse_notation() {
+        for (notation, want) in [
+            ("a", input(Key::Char('a'), false, false, false)),
+            ("A", input(Key::Char('A'), false, false, false)),
+            ("ctrl+a", input(Key::Char('a'), true, false, false)),
+            ("CTRL+ALT+a", input(Key::Char('a'), true, true, false)),
+            ("shift+ctrl+z", input(Key::Char('Z'), true, false, true)),
+            ("shift+Z", input(Key::Char('Z'), false, false, true)),
+            ("shift+ß", input(Key::Char('ß'), false, false, true)),
+            ("shift+1", input(Key::Char('1'), false, false, true)),
+            ("control+meta+shift+Enter", input(Key::Enter, true, true, true)),
+            ("+", input(Key::Char('+'), false, false, false)),
+            ("alt++", input(Key::Char('+'), false, true, false)),
+            ("space", input(Key::Char(' '), false, false, false)),
+            (" ", input(Key::Char(' '), false, false, false)),
+            ("f12", input(Key::F(12), false, false, false)),
+            ("F1", input(Key::F(1), false, false, false)),
+            ("f", input(Key::Char('f'), false, false, false)),
+            ("del", input(Key::Delete, false, false, false)),
+            ("ins", input(Key::Insert, false, false, false)),
+            ("shift+wheelup", input(Key::MouseScrollUp, false, false, true)),
+            ("あ", input(Key::Char('あ'), false, false, false)),
+        ] {
+            assert_eq!(notation.parse::<Input>(), Ok(want), "{:?}", notation);
+        }
+    }
+
+    #[test]
+    fn parse_invalid_notation() {
+        for (notation, reason) in [
+            ("", "key is missing"),
+            ("ctrl+", "key is missing"),
+            ("ctrl", "unknown key"),
+            ("foo", "unknown key"),
+            ("f256", "unknown key"),
+            ("hyper+a", "unknown modifier"),
+            ("ctrl+ctrl+a", "duplicate modifier"),
+            ("a+b", "unknown modifier"),
+        ] {
+            let err = notation.parse::<Input>().unwrap_err();
+            assert_eq!(err.reason, reason, "{:?}", notation);
+        }
+    }
+
+    #[test]
+    fn format_notation_round_trip() {
+        let mut keys = vec![Key::Char('A'), Key::Char('+'), Key::Char(' '), Key::Char('\n'), Key::F(3)];
+        keys.extend(KEY_NAMES.iter().map(|(_, k)| *k));
+        for key in keys {
+            for ctrl in [false, true] {
+                for alt in [false, true] {
+                    for shift in [false, true] {
+                        let i = input(key, ctrl, alt, shift);
+                        let s = i.to_string();
+                        assert_eq!(s.parse::<Input>(), Ok(i), "{:?}", s);
+                    }
+                }
+            }
+        }
+
+        // Letter with Shift is formatted in lowercase and parsed into uppercase letter
+        let want = input(Key::Char('A'), true, false, true);
+        for c in ['a', 'A'] {
+            let i = input(Key::Char(c), true, false, true);
+            assert_eq!(i.to_string(), "ctrl+shift+a");
+            assert_eq!(i.to_string().parse::<Input>(), Ok(want.clone()));
+        }
+    }
+
+    #[cfg(feature = "crossterm")]
+    #[test]
+    fn notation_matches_crossterm_event() {
+        use crate::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        use crate::cursor::CursorMove;
+        use crate::keymap::{EditorCommand, Keymap};
+
+        let mut keymap = Keymap::new();
+        keymap.bind("ctrl+shift+z".parse().unwrap(), EditorCommand::Redo);
+        keymap.bind("alt+shift+left".parse().unwrap(), EditorCommand::Undo);
+
+        // Crossterm reports Ctrl+Shift+Z as uppercase 'Z' with Shift modifier
+        let modifiers = KeyModifiers::CONTROL | KeyModifiers::SHIFT;
+        let event = KeyEvent::new(KeyCode::Char('Z'), modifiers);
+        assert_eq!(keymap.command(&Input::from(event)), Some(EditorCommand::Redo));
+        let event = KeyEvent::new(KeyCode::Left, KeyModifiers::ALT | KeyModifiers::SHIFT);
+        assert_eq!(keymap.command(&Input::from(event)), Some(EditorCommand::Undo));
+
+        // Default bindings of Shift with letters also match
+        let event = KeyEvent::new(KeyCode::Char('A'), modifiers);
+        let want = EditorCommand::Select(CursorMove::Head);
+        assert_eq!(Keymap::default().command(&Input::from(event)), Some(want));
+    }
+}
@@ -39,7 +39,8 @@ impl From<KeyEvent> for Input {
             KeyEvent::ShiftUp
             | KeyEvent::ShiftRight
             | KeyEvent::ShiftDown
-            | KeyEvent::ShiftLeft => (false, false, true),
+            | KeyEvent::ShiftLeft
+            | KeyEvent::BackTab => (false, false, true),
             _ => (false, false, false),
         };
 
@@ -47,6 +48,7 @@ impl From<KeyEvent> for Input {
         let (ctrl, alt, shift) = match key {
             KeyEvent::Ctrl(_) => (true, false, false),
             KeyEvent::Alt(_) => (false, true, false),
+            KeyEvent::BackTab => (false, false, true),
             _ => (false, false, false),
         };
 
@@ -71,6 +73,7 @@ impl From<KeyEvent> for Input {
             KeyEvent::PageDown => Key::PageDown,
             KeyEvent::BackTab => Key::Tab,
             KeyEvent::Delete => Key::Delete,
+            KeyEvent::Insert => Key::Insert,
             KeyEvent::Esc => Key::Esc,
             KeyEvent::F(x) => Key::F(x),
             _ => Key::Null,
@@ -91,6 +94,7 @@ impl From<KeyEvent> for Input {
             KeyEvent::PageDown => Key::PageDown,
             KeyEvent::BackTab => Key::Tab,
             KeyEvent::Delete => Key::Delete,
+            KeyEvent::Insert => Key::Insert,
             KeyEvent::Esc => Key::Esc,
             KeyEvent::F(x) => Key::F(x),
             _ => Key::Null,
@@ -111,6 +115,10 @@ impl From<MouseButton> for Key {
         match button {
             MouseButton::WheelUp => Key::MouseScrollUp,
             MouseButton::WheelDown => Key::MouseScrollDown,
+            #[cfg(feature = "termion")]
+            MouseButton::WheelLeft => Key::MouseScrollLeft,
+            #[cfg(feature = "termion")]
+            MouseButton::WheelRight => Key::MouseScrollRight,
             _ => Key::Null,
         }
     }
@@ -118,11 +126,24 @@ impl From<MouseButton> for Key {
 
 impl From<MouseEvent> for Input {
     /// Convert [`termion::event::MouseEvent`] into [`Input`].
+    ///
+    /// When `mouse` feature is enabled, left button press, hold and release are converted into [`Key::MouseClick`],
+    /// [`Key::MouseDrag`] and [`Key::MouseUp`]. termion's positions are 1-based so they are converted into 0-based
+    /// positions as other backends. termion does not report which button was released, so releasing any button is
+    /// converted into [`Key::MouseUp`].
     fn from(mouse: MouseEvent) -> Self {
-        let key = if let MouseEvent::Press(button, ..) = mouse {
-            Key::from(button)
-        } else {
-            Key::Null
+        let key = match mouse {
+            #[cfg(feature = "mouse")]
+            MouseEvent::Press(MouseButton::Left, x, y) => {
+                Key::MouseClick(x.saturating_sub(1), y.saturating_sub(1))
+            }
+            #[cfg(feature = "mouse")]
+            MouseEvent::Hold(x, y) => Key::MouseDrag(x.saturating_sub(1), y.saturating_sub(1)),
+            #[cfg(feature = "mouse")]
+            MouseEvent::Release(x, y) => Key::MouseUp(x.saturating_sub(1), y.saturating_sub(1)),
+            MouseEvent::Press(button, ..) => Key::from(button),
+            #[cfg(not(feature = "mouse"))]
+            _ => Key::Null,
         };
         Self {
             key,
@@ -156,7 +177,8 @@ mod tests {
             (KeyEvent::Char('\n'), input(Key::Enter, false, false, false)),
             (KeyEvent::Char('\r'), input(Key::Enter, false, false, false)),
             (KeyEvent::F(1), input(Key::F(1), false, false, false)),
-            (KeyEvent::BackTab, input(Key::Tab, false, false, false)),
+            (KeyEvent::BackTab, input(Key::Tab, false, false, true)),
+            (KeyEvent::Insert, input(Key::Insert, false, false, false)),
             (KeyEvent::Null, input(Key::Null, false, false, false)),
             #[cfg(feature = "termion")]
             (KeyEvent::ShiftDown, input(Key::Down, false, false, true)),
@@ -182,18 +204,55 @@ mod tests {
                 MouseEvent::Press(MouseButton::WheelUp, 1, 1),
                 input(Key::MouseScrollUp, false, false, false),
             ),
+            #[cfg(feature = "termion")]
+            (
+                MouseEvent::Press(MouseButton::WheelLeft, 1, 1),
+                input(Key::MouseScrollLeft, false, false, false),
+            ),
+            #[cfg(feature = "termion")]
+            (
+                MouseEvent::Press(MouseButton::WheelRight, 1, 1),
+                input(Key::MouseScrollRight, false, false, false),
+            ),
+            (
+                MouseEvent::Press(MouseButton::Right, 1, 1),
+                input(Key::Null, false, false, false),
+            ),
+            #[cfg(not(feature = "mouse"))]
             (
                 MouseEvent::Press(MouseButton::Left, 1, 1),
                 input(Key::Null, false, false, false),
             ),
+            #[cfg(not(feature = "mouse"))]
             (
                 MouseEvent::Release(1, 1),
                 input(Key::Null, false, false, false),
             ),
+            #[cfg(not(feature = "mouse"))]
             (
                 MouseEvent::Hold(1, 1),
                 input(Key::Null, false, false, false),
             ),
+            #[cfg(feature = "mouse")]
+            (
+                MouseEvent::Press(MouseButton::Left, 11, 6),
+                input(Key::MouseClick(10, 5), false, false, false),
+            ),
+            #[cfg(feature = "mouse")]
+            (
+                MouseEvent::Hold(1, 1),
+                input(Key::MouseDrag(0, 0), false, false, false),
+            ),
+            #[cfg(feature = "mouse")]
+            (
+                MouseEvent::Release(3, 2),
+                input(Key::MouseUp(2, 1), false, false, false),
+            ),
+            #[cfg(feature = "mouse")]
+            (
+                MouseEvent::Press(MouseButton::Left, 0, 0),
+                input(Key::MouseClick(0, 0), false, false, false),
+            ),
         ] {
             assert_eq!(Input::from(from), to, "{:?} -> {:?}", from, to);
         }
@@ -210,6 +269,11 @@ mod tests {
                 Event::Mouse(MouseEvent::Press(MouseButton::WheelDown, 1, 1)),
                 input(Key::MouseScrollDown, false, false, false),
             ),
+            #[cfg(feature = "mouse")]
+            (
+                Event::Mouse(MouseEvent::Press(MouseButton::Left, 1, 1)),
+                input(Key::MouseClick(0, 0), false, false, false),
+            ),
             (
                 Event::Unsupported(vec![]),
                 input(Key::Null, false, false, false),
@@ -1,4 +1,4 @@
-use super::{Input, Key};
+use super::{EditorEvent, Input, Key};
 use termwiz::input::{
     InputEvent, KeyCode, KeyEvent, Modifiers, MouseButtons, MouseEvent, PixelMouseEvent,
 };
@@ -15,6 +15,17 @@ impl From<InputEvent> for Input {
     }
 }
 
+impl From<InputEvent> for EditorEvent {
+    /// Convert [`termwiz::input::InputEvent`] into [`EditorEvent`]. Paste events are converted into
+    /// [`EditorEvent::Paste`] and other events are converted into [`EditorEvent::Input`].
+    fn from(input: InputEvent) -> Self {
+        match input {
+            InputEvent::Paste(text) => Self::Paste(text),
+            input => Self::Input(Input::from(input)),
+        }
+    }
+}
+
 impl From<KeyCode> for Key {
     /// Convert [`termwiz::input::KeyCode`] into [`Key`].
     fn from(key: KeyCode) -> Self {
@@ -33,6 +44,7 @@ impl From<KeyCode> for Key {
             KeyCode::UpArrow => Key::Up,
             KeyCode::DownArrow => Key::Down,
             KeyCode::Delete => Key::Delete,
+            KeyCode::Insert => Key::Insert,
             KeyCode::Function(x) => Key::F(x),
             KeyCode::Copy => Key::Copy,
             KeyCode::Cut => Key::Cut,
@@ -69,6 +81,12 @@ impl From<MouseButtons> for Key {
             } else {
                 Key::MouseScrollDown
             }
+        } else if buttons.contains(MouseButtons::HORZ_WHEEL) {
+            if buttons.contains(MouseButtons::WHEEL_POSITIVE) {
+                Key::MouseScrollLeft
+            } else {
+                Key::MouseScrollRight
+            }
         } else {
             Key::Null
         }
@@ -77,12 +95,30 @@ impl From<MouseButtons> for Key {
 
 impl From<MouseEvent> for Input {
     /// Convert [`termwiz::input::MouseEvent`] into [`Input`].
+    ///
+    /// When `mouse` feature is enabled, the left button is converted into [`Key::MouseDrag`] and no button is converted
+    /// into [`Key::MouseUp`] since termwiz reports the state of buttons instead of press and release. The first
+    /// [`Key::MouseDrag`] starts a selection as [`Key::MouseClick`] and [`Key::MouseUp`] without preceding press is
+    /// ignored by [`TextArea`](crate::TextArea). termwiz's positions are 1-based so they are converted into 0-based
+    /// positions as other backends.
     fn from(mouse: MouseEvent) -> Self {
+        #[cfg(feature = "mouse")]
+        let (x, y) = (mouse.x.saturating_sub(1), mouse.y.saturating_sub(1));
         let MouseEvent {
             mouse_buttons,
             modifiers,
             ..
         } = mouse;
+
+        #[cfg(feature = "mouse")]
+        let key = if mouse_buttons == MouseButtons::LEFT {
+            Key::MouseDrag(x, y)
+        } else if mouse_buttons == MouseButtons::NONE {
+            Key::MouseUp(x, y)
+        } else {
+            Key::from(mouse_buttons)
+        };
+        #[cfg(not(feature = "mouse"))]
         let key = Key::from(mouse_buttons);
         let ctrl = modifiers.contains(Modifiers::CTRL);
         let alt = modifiers.contains(Modifiers::ALT);
@@ -170,6 +206,10 @@ mod tests {
                 key_event(KeyCode::Home, Modifiers::ALT),
                 input(Key::Home, false, true, false),
             ),
+            (
+                key_event(KeyCode::Insert, Modifiers::empty()),
+                input(Key::Insert, false, false, false),
+            ),
             (
                 key_event(
                     KeyCode::Function(1),
@@ -219,6 +259,22 @@ mod tests {
                 ),
                 input(Key::MouseScrollDown, true, true, true),
             ),
+            (
+                mouse_event(
+                    MouseButtons::HORZ_WHEEL | MouseButtons::WHEEL_POSITIVE,
+                    Modifiers::empty(),
+                ),
+                input(Key::MouseScrollLeft, false, false, false),
+            ),
+            (
+                mouse_event(MouseButtons::HORZ_WHEEL, Modifiers::SHIFT),
+                input(Key::MouseScrollRight, false, false, true),
+            ),
+            (
+                mouse_event(MouseButtons::RIGHT, Modifiers::empty()),
+                input(Key::Null, false, false, false),
+            ),
+            #[cfg(not(feature = "mouse"))]
             (
                 mouse_event(MouseButtons::LEFT, Modifiers::empty()),
                 input(Key::Null, false, false, false),
@@ -231,6 +287,56 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "mouse")]
+    fn mouse_button_to_input() {
+        for (from, to) in [
+            (
+                MouseEvent {
+                    mouse_buttons: MouseButtons::LEFT,
+                    modifiers: Modifiers::empty(),
+                    x: 11,
+                    y: 6,
+                },
+                input(Key::MouseDrag(10, 5), false, false, false),
+            ),
+            (
+                MouseEvent {
+                    mouse_buttons: MouseButtons::LEFT,
+                    modifiers: Modifiers::SHIFT,
+                    x: 1,
+                    y: 1,
+                },
+                input(Key::MouseDrag(0, 0), false, false, true),
+            ),
+            (
+                MouseEvent {
+                    mouse_buttons: MouseButtons::NONE,
+                    modifiers: Modifiers::empty(),
+                    x: 3,
+                    y: 2,
+                },
+                input(Key::MouseUp(2, 1), false, false, false),
+            ),
+            (
+                MouseEvent {
+                    mouse_buttons: MouseButtons::LEFT,
+                    modifiers: Modifiers::ALT,
+                    x: 0,
+                    y: 0,
+                },
+                input(Key::MouseDrag(0, 0), false, true, false),
+            ),
+        ] {
+            assert_eq!(Input::from(from.clone()), to, "{:?} -> {:?}", from, to);
+        }
+
+        // Buttons cannot be converted into positions from pixel positions
+        let from = pixel_mouse_event(MouseButtons::LEFT, Modifiers::empty());
+        let to = input(Key::Null, false, false, false);
+        assert_eq!(Input::from(from.clone()), to, "{:?} -> {:?}", from, to);
+    }
+
     #[test]
     fn event_to_input() {
         for (from, to) in [
@@ -257,4 +363,20 @@ mod tests {
             assert_eq!(Input::from(from.clone()), to, "{:?} -> {:?}", from, to);
         }
     }
+
+    #[test]
+    fn event_to_editor_event() {
+        for (from, to) in [
+            (
+                InputEvent::Key(key_event(KeyCode::Char('a'), Modifiers::empty())),
+                EditorEvent::Input(input(Key::Char('a'), false, false, false)),
+            ),
+            (
+                InputEvent::Paste("foo\nbar".into()),
+                EditorEvent::Paste("foo\nbar".into()),
+            ),
+        ] {
+            assert_eq!(EditorEvent::from(from.clone()), to, "{:?} -> {:?}", from, to);
+        }
+    }
 }
@@ -0,0 +1,508 @@
+use crate::cursor::CursorMove;
+use crate::input::{Input, Key};
+use crate::scroll::Scrolling;
+use crate::text_object::TextObject;
+use crate::transform::Case;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+/// Editor operation which can be bound to key inputs with [`Keymap`]. Each command corresponds to an operation which
+/// [`TextArea::input`](crate::TextArea::input) performs. Commands can be run directly with
+/// [`TextArea::execute`](crate::TextArea::execute).
+///
+/// This type is marked as `#[non_exhaustive]` since more commands may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum EditorCommand {
+    /// Insert the character. Character inputs without Ctrl and Alt keys are converted into this command when they are
+    /// not bound in the keymap. See [`TextArea::insert_char`](crate::TextArea::insert_char).
+    InsertChar(char),
+    /// Insert a newline. See [`TextArea::insert_newline`](crate::TextArea::insert_newline).
+    InsertNewline,
+    /// Insert a tab or spaces. See [`TextArea::insert_tab`](crate::TextArea::insert_tab). When text is selected, the
+    /// selected lines are indented instead. See [`TextArea::indent_lines`](crate::TextArea::indent_lines).
+    InsertTab,
+    /// Indent the selected lines or the cursor line. See [`TextArea::indent_lines`](crate::TextArea::indent_lines).
+    IndentLines,
+    /// Dedent the selected lines or the cursor line. See [`TextArea::dedent_lines`](crate::TextArea::dedent_lines).
+    DedentLines,
+    /// Delete one character before cursor. See [`TextArea::delete_char`](crate::TextArea::delete_char).
+    DeleteChar,
+    /// Delete one character next to cursor. See [`TextArea::delete_next_char`](crate::TextArea::delete_next_char).
+    DeleteNextChar,
+    /// Delete from cursor until the end of line. See
+    /// [`TextArea::delete_line_by_end`](crate::TextArea::delete_line_by_end).
+    DeleteLineByEnd,
+    /// Delete from cursor until the head of line. See
+    /// [`TextArea::delete_line_by_head`](crate::TextArea::delete_line_by_head).
+    DeleteLineByHead,
+    /// Delete one word before cursor. See [`TextArea::delete_word`](crate::TextArea::delete_word).
+    DeleteWord,
+    /// Delete one word next to cursor. See [`TextArea::delete_next_word`](crate::TextArea::delete_next_word).
+    DeleteNextWord,
+    /// Move the cursor. Ongoing text selection is canceled.
+    MoveCursor(CursorMove),
+    /// Move the cursor with selecting text. Text selection starts at the cursor when it is not ongoing.
+    Select(CursorMove),
+    /// Scroll the textarea. Ongoing text selection is canceled when the cursor moves.
+    Scroll(Scrolling),
+    /// Scroll the textarea with selecting text. Text selection starts at the cursor when it is not ongoing.
+    SelectScroll(Scrolling),
+    /// Undo the last edit. See [`TextArea::undo`](crate::TextArea::undo).
+    Undo,
+    /// Redo the last undone edit. See [`TextArea::redo`](crate::TextArea::redo).
+    Redo,
+    /// Copy the selected text. See [`TextArea::copy`](crate::TextArea::copy).
+    Copy,
+    /// Cut the selected text. See [`TextArea::cut`](crate::TextArea::cut).
+    Cut,
+    /// Paste the yanked text. See [`TextArea::paste`](crate::TextArea::paste).
+    Paste,
+    /// Replace the pasted text with the previous text in the kill ring. See
+    /// [`TextArea::yank_pop`](crate::TextArea::yank_pop).
+    YankPop,
+    /// Copy the selected text to the register. See
+    /// [`TextArea::copy_to_register`](crate::TextArea::copy_to_register).
+    CopyToRegister(char),
+    /// Cut the selected text to the register. See [`TextArea::cut_to_register`](crate::TextArea::cut_to_register).
+    CutToRegister(char),
+    /// Paste the text in the register. See
+    /// [`TextArea::paste_from_register`](crate::TextArea::paste_from_register).
+    PasteFromRegister(char),
+    /// Select the text object at the cursor. See
+    /// [`TextArea::select_text_object`](crate::TextArea::select_text_object).
+    SelectTextObject(TextObject),
+    /// Toggle insert mode and overwrite mode. See
+    /// [`TextArea::toggle_overwrite`](crate::TextArea::toggle_overwrite).
+    ToggleOverwrite,
+    /// Move the selected lines or the cursor line up. See
+    /// [`TextArea::move_lines_up`](crate::TextArea::move_lines_up).
+    MoveLinesUp,
+    /// Move the selected lines or the cursor line down. See
+    /// [`TextArea::move_lines_down`](crate::TextArea::move_lines_down).
+    MoveLinesDown,
+    /// Duplicate the selected lines or the cursor line. See
+    /// [`TextArea::duplicate_lines`](crate::TextArea::duplicate_lines).
+    DuplicateLines,
+    /// Join the selected lines or the cursor line and the next line. See
+    /// [`TextArea::join_lines`](crate::TextArea::join_lines).
+    JoinLines,
+    /// Sort the selected lines. See [`TextArea::sort_lines`](crate::TextArea::sort_lines).
+    SortLines,
+    /// Reverse the selected lines. See [`TextArea::reverse_lines`](crate::TextArea::reverse_lines).
+    ReverseLines,
+    /// Remove duplicates in the selected lines. See [`TextArea::dedup_lines`](crate::TextArea::dedup_lines).
+    DedupLines,
+    /// Convert the case of the selected text or the word at the cursor. See
+    /// [`TextArea::convert_case`](crate::TextArea::convert_case).
+    ConvertCase(Case),
+    /// Toggle the line comment of the selected lines or the cursor line. See
+    /// [`TextArea::toggle_line_comment`](crate::TextArea::toggle_line_comment).
+    ToggleLineComment,
+    /// Toggle the block comment around the selected lines or the cursor line. See
+    /// [`TextArea::toggle_block_comment`](crate::TextArea::toggle_block_comment).
+    ToggleBlockComment,
+    /// Reflow the paragraph at the cursor or the selected lines. See
+    /// [`TextArea::reflow_paragraph`](crate::TextArea::reflow_paragraph).
+    ReflowParagraph,
+    /// Remove trailing whitespaces of the selected lines or all lines. See
+    /// [`TextArea::trim_trailing_whitespace`](crate::TextArea::trim_trailing_whitespace).
+    TrimTrailingWhitespace,
+    /// Convert indentation of the selected lines or all lines to tabs or spaces. See
+    /// [`TextArea::normalize_indent`](crate::TextArea::normalize_indent).
+    NormalizeIndent,
+    /// Collapse consecutive blank lines in the selected lines or all lines. See
+    /// [`TextArea::collapse_blank_lines`](crate::TextArea::collapse_blank_lines).
+    CollapseBlankLines,
+    /// Make the text end with exactly one newline. See
+    /// [`TextArea::ensure_final_newline`](crate::TextArea::ensure_final_newline).
+    EnsureFinalNewline,
+}
+
+/// Mappings from key inputs to [`EditorCommand`]s used by [`TextArea::input`](crate::TextArea::input).
+///
+/// [`Keymap::default`] returns the default key mappings described in [the module document](./index.html).
+/// [`Keymap::new`] returns an empty keymap. Bindings can be added, overridden, and removed.
+///
+/// Character inputs without Ctrl and Alt keys which are not bound in the keymap insert the character. Mouse events
+/// with positions (clicks, drags and releases) are not handled with keymaps.
+/// ```
+/// use tui_textarea::{EditorCommand, Input, Key, Keymap, TextArea};
+///
+/// let ctrl = |c| Input { key: Key::Char(c), ctrl: true, ..Default::default() };
+///
+/// let mut keymap = Keymap::default();
+/// // Rebind undo and redo
+/// keymap.bind(ctrl('z'), EditorCommand::Undo);
+/// keymap.bind(ctrl('y'), EditorCommand::Redo);
+/// // Free Ctrl+K for the application
+/// keymap.unbind(&ctrl('k'));
+///
+/// let mut textarea = TextArea::default();
+/// textarea.set_keymap(keymap);
+///
+/// textarea.insert_str("hello");
+/// textarea.input(ctrl('z'));
+/// assert_eq!(textarea.lines(), [""]);
+/// textarea.input(ctrl('y'));
+/// assert_eq!(textarea.lines(), ["hello"]);
+/// ```
+///
+/// With `serde` feature, a keymap is serialized as a map from key notations (see [`Input`]'s `FromStr` implementation)
+/// to commands.
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # {
+/// use tui_textarea::{EditorCommand, Input, Keymap};
+///
+/// let keymap: Keymap = serde_json::from_str(r#"{"ctrl+z": "Undo", "ctrl+shift+z": "Redo"}"#).unwrap();
+/// let input: Input = "ctrl+shift+z".parse().unwrap();
+/// assert_eq!(keymap.get(&input), Some(EditorCommand::Redo));
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keymap {
+    bindings: HashMap<Input, EditorCommand>,
+}
+
+impl Keymap {
+    /// Create an empty keymap. Only character inputs without Ctrl and Alt keys are handled with the keymap.
+    /// ```
+    /// use tui_textarea::{Input, Key, Keymap, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_keymap(Keymap::new());
+    ///
+    /// textarea.input(Input { key: Key::Char('a'), ..Default::default() });
+    /// textarea.input(Input { key: Key::Enter, ..Default::default() });
+    /// assert_eq!(textarea.lines(), ["a"]);
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            bindings: HashMap::new(),
+        }
+    }
+
+    /// Bind the key input to the command. When the input was already bound, the previous command is overridden and
+    /// returned. A lowercase letter with Shift key is the same input as its uppercase letter with Shift key since
+    /// terminals report Shift+Z as `Z`.
+    /// ```
+    /// use tui_textarea::{EditorCommand, Input, Key, Keymap};
+    ///
+    /// let mut keymap = Keymap::default();
+    /// let input = Input { key: Key::Char('z'), ctrl: true, ..Default::default() };
+    ///
+    /// assert_eq!(keymap.bind(input.clone(), EditorCommand::Undo), None);
+    /// assert_eq!(keymap.bind(input, EditorCommand::Redo), Some(EditorCommand::Undo));
+    /// ```
+    pub fn bind(&mut self, input: Input, command: EditorCommand) -> Option<EditorCommand> {
+        self.bindings.insert(input.canonical(), command)
+    }
+
+    /// Remove the binding of the key input and return the command which was bound.
+    /// ```
+    /// use tui_textarea::{EditorCommand, Input, Key, Keymap};
+    ///
+    /// let mut keymap = Keymap::default();
+    /// let input = Input { key: Key::Char('k'), ctrl: true, ..Default::default() };
+    ///
+    /// assert_eq!(keymap.unbind(&input), Some(EditorCommand::DeleteLineByEnd));
+    /// assert_eq!(keymap.get(&input), None);
+    /// ```
+    pub fn unbind(&mut self, input: &Input) -> Option<EditorCommand> {
+        self.bindings.remove(&input.canonical())
+    }
+
+    /// Get the command bound to the key input.
+    /// ```
+    /// use tui_textarea::{CursorMove, EditorCommand, Input, Key, Keymap};
+    ///
+    /// let keymap = Keymap::default();
+    ///
+    /// let input = Input { key: Key::Down, ..Default::default() };
+    /// assert_eq!(keymap.get(&input), Some(EditorCommand::MoveCursor(CursorMove::Down)));
+    /// let input = Input { key: Key::Down, shift: true, ..Default::default() };
+    /// assert_eq!(keymap.get(&input), Some(EditorCommand::Select(CursorMove::Down)));
+    /// ```
+    pub fn get(&self, input: &Input) -> Option<EditorCommand> {
+        self.bindings.get(&input.canonical()).copied()
+    }
+
+    /// Remove all bindings.
+    pub fn clear(&mut self) {
+        self.bindings.clear();
+    }
+
+    /// Iterate over all bindings in arbitrary order.
+    pub fn bindings(&self) -> impl Iterator<Item = (&Input, &EditorCommand)> {
+        self.bindings.iter()
+    }
+
+    /// Get the command to run for the key input. Unbound character inputs without Ctrl and Alt keys are converted
+    /// into [`EditorCommand::InsertChar`].
+    pub(crate) fn command(&self, input: &Input) -> Option<EditorCommand> {
+        if let Some(command) = self.get(input) {
+            return Some(command);
+        }
+        match input {
+            Input {
+                key: Key::Char(c),
+                ctrl: false,
+                alt: false,
+                ..
+            } if *c != '\n' && *c != '\r' => Some(EditorCommand::InsertChar(*c)),
+            _ => None,
+        }
+    }
+
+    // Bind the key with the modifiers regardless of Shift key
+    fn bind_any_shift(&mut self, key: Key, ctrl: bool, alt: bool, command: EditorCommand) {
+        for shift in [false, true] {
+            self.bind(
+                Input {
+                    key,
+                    ctrl,
+                    alt,
+                    shift,
+                },
+                command,
+            );
+        }
+    }
+
+    // Bind the key regardless of any modifiers
+    fn bind_any_modifiers(&mut self, key: Key, command: EditorCommand) {
+        for ctrl in [false, true] {
+            for alt in [false, true] {
+                self.bind_any_shift(key, ctrl, alt, command);
+            }
+        }
+    }
+
+    // Bind the key to the cursor move. With Shift key, the cursor move selects text
+    fn bind_move(&mut self, key: Key, ctrl: bool, alt: bool, m: CursorMove) {
+        let input = Input {
+            key,
+            ctrl,
+            alt,
+            shift: false,
+        };
+        self.bind(input.clone(), EditorCommand::MoveCursor(m));
+        self.bind(Input { shift: true, ..input }, EditorCommand::Select(m));
+    }
+
+    // Bind the key to the scroll. With Shift key, the scroll selects text
+    fn bind_scroll(&mut self, key: Key, ctrl: bool, alt: bool, s: Scrolling) {
+        let input = Input {
+            key,
+            ctrl,
+            alt,
+            shift: false,
+        };
+        self.bind(input.clone(), EditorCommand::Scroll(s));
+        self.bind(Input { shift: true, ..input }, EditorCommand::SelectScroll(s));
+    }
+}
+
+impl Default for Keymap {
+    /// Create the default keymap. See [the module document](./index.html) for the mappings.
+    fn default() -> Self {
+        use CursorMove::*;
+        use EditorCommand::*;
+
+        let mut keymap = Self::new();
+
+        keymap.bind_any_shift(Key::Char('m'), true, false, InsertNewline);
+        keymap.bind_any_shift(Key::Char('\n'), false, false, InsertNewline);
+        keymap.bind_any_shift(Key::Char('\r'), false, false, InsertNewline);
+        keymap.bind_any_modifiers(Key::Enter, InsertNewline);
+        let tab = Input {
+            key: Key::Tab,
+            ..Default::default()
+        };
+        keymap.bind(tab.clone(), InsertTab);
+        keymap.bind(Input { shift: true, ..tab }, DedentLines);
+        keymap.bind_any_shift(Key::Char('h'), true, false, DeleteChar);
+        keymap.bind_any_shift(Key::Backspace, false, false, DeleteChar);
+        keymap.bind_any_shift(Key::Char('d'), true, false, DeleteNextChar);
+        keymap.bind_any_shift(Key::Delete, false, false, DeleteNextChar);
+        keymap.bind_any_shift(Key::Char('k'), true, false, DeleteLineByEnd);
+        keymap.bind_any_shift(Key::Char('j'), true, false, DeleteLineByHead);
+        keymap.bind_any_shift(Key::Char('w'), true, false, DeleteWord);
+        keymap.bind_any_shift(Key::Char('h'), false, true, DeleteWord);
+        keymap.bind_any_shift(Key::Backspace, false, true, DeleteWord);
+        keymap.bind_any_shift(Key::Delete, false, true, DeleteNextWord);
+        keymap.bind_any_shift(Key::Char('d'), false, true, DeleteNextWord);
+
+        keymap.bind_move(Key::Char('n'), true, false, Down);
+        keymap.bind_move(Key::Down, false, false, Down);
+        keymap.bind_move(Key::Char('p'), true, false, Up);
+        keymap.bind_move(Key::Up, false, false, Up);
+        keymap.bind_move(Key::Char('f'), true, false, Forward);
+        keymap.bind_move(Key::Right, false, false, Forward);
+        keymap.bind_move(Key::Char('b'), true, false, Back);
+        keymap.bind_move(Key::Left, false, false, Back);
+        keymap.bind_move(Key::Char('a'), true, false, Head);
+        keymap.bind_move(Key::Left, true, true, Head);
+        keymap.bind_move(Key::Char('b'), true, true, Head);
+        keymap.bind_move(Key::Char('e'), true, false, End);
+        keymap.bind_move(Key::Right, true, true, End);
+        keymap.bind_move(Key::Char('f'), true, true, End);
+        for ctrl in [false, true] {
+            for alt in [false, true] {
+                keymap.bind_move(Key::Home, ctrl, alt, Head);
+                keymap.bind_move(Key::End, ctrl, alt, End);
+            }
+        }
+        keymap.bind_move(Key::Char('<'), false, true, Top);
+        keymap.bind_move(Key::Up, true, true, Top);
+        keymap.bind_move(Key::Char('p'), true, true, Top);
+        keymap.bind_move(Key::Char('>'), false, true, Bottom);
+        keymap.bind_move(Key::Down, true, true, Bottom);
+        keymap.bind_move(Key::Char('n'), true, true, Bottom);
+        keymap.bind_move(Key::Char('f'), false, true, WordForward);
+        keymap.bind_move(Key::Right, true, false, WordForward);
+        keymap.bind_move(Key::Char('b'), false, true, WordBack);
+        keymap.bind_move(Key::Left, true, false, WordBack);
+        keymap.bind_move(Key::Char(']'), false, true, ParagraphForward);
+        keymap.bind_move(Key::Char('n'), false, true, ParagraphForward);
+        keymap.bind_move(Key::Down, true, false, ParagraphForward);
+        keymap.bind_move(Key::Char('['), false, true, ParagraphBack);
+        keymap.bind_move(Key::Char('p'), false, true, ParagraphBack);
+        keymap.bind_move(Key::Up, true, false, ParagraphBack);
+
+        keymap.bind_any_shift(Key::Char('u'), true, false, Undo);
+        keymap.bind_any_shift(Key::Char('r'), true, false, Redo);
+        keymap.bind_any_shift(Key::Char('y'), true, false, Paste);
+        keymap.bind_any_modifiers(Key::Paste, Paste);
+        keymap.bind_any_shift(Key::Char('y'), false, true, YankPop);
+        keymap.bind_any_shift(Key::Char('x'), true, false, Cut);
+        keymap.bind_any_modifiers(Key::Cut, Cut);
+        keymap.bind_any_shift(Key::Char('c'), true, false, Copy);
+        keymap.bind_any_modifiers(Key::Copy, Copy);
+        keymap.bind_any_shift(Key::Insert, false, false, ToggleOverwrite);
+        // Terminals often send `Ctrl+/` as `Ctrl+_` or `Ctrl+7`
+        for c in ['/', '_', '7'] {
+            keymap.bind_any_shift(Key::Char(c), true, false, ToggleLineComment);
+        }
+        keymap.bind_any_shift(Key::Char('q'), false, true, ReflowParagraph);
+
+        keymap.bind_scroll(Key::Char('v'), true, false, Scrolling::PageDown);
+        keymap.bind_scroll(Key::Char('v'), false, true, Scrolling::PageUp);
+        for ctrl in [false, true] {
+            for alt in [false, true] {
+                keymap.bind_scroll(Key::PageDown, ctrl, alt, Scrolling::PageDown);
+                keymap.bind_scroll(Key::PageUp, ctrl, alt, Scrolling::PageUp);
+                keymap.bind_scroll(Key::MouseScrollDown, ctrl, alt, (1, 0).into());
+                keymap.bind_scroll(Key::MouseScrollUp, ctrl, alt, (-1, 0).into());
+                keymap.bind_scroll(Key::MouseScrollRight, ctrl, alt, (0, 1).into());
+                keymap.bind_scroll(Key::MouseScrollLeft, ctrl, alt, (0, -1).into());
+            }
+        }
+
+        keymap
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Keymap {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Sort the bindings by their notations for stable outputs
+        let bindings: BTreeMap<_, _> = self
+            .bindings
+            .iter()
+            .map(|(input, command)| (input.to_string(), command))
+            .collect();
+        bindings.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Keymap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bindings = HashMap::<String, EditorCommand>::deserialize(deserializer)?;
+        let mut keymap = Self::new();
+        for (notation, command) in bindings {
+            let input = notation.parse().map_err(de::Error::custom)?;
+            keymap.bind(input, command);
+        }
+        Ok(keymap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::tests::input;
+
+    #[test]
+    fn default_bindings() {
+        use CursorMove::*;
+        use EditorCommand::*;
+
+        let keymap = Keymap::default();
+        for (i, want) in [
+            (input(Key::Enter, true, true, true), Some(InsertNewline)),
+            (input(Key::Char('m'), true, false, false), Some(InsertNewline)),
+            (input(Key::Char('\r'), false, false, true), Some(InsertNewline)),
+            (input(Key::Char('\n'), true, false, false), None),
+            (input(Key::Char('a'), false, false, false), Some(InsertChar('a'))),
+            (input(Key::Char('A'), false, false, true), Some(InsertChar('A'))),
+            (input(Key::Char('a'), true, false, false), Some(MoveCursor(Head))),
+            (input(Key::Char('a'), true, false, true), Some(Select(Head))),
+            (input(Key::Char('a'), false, true, false), None),
+            (input(Key::Tab, false, false, false), Some(InsertTab)),
+            (input(Key::Tab, false, false, true), Some(DedentLines)),
+            (input(Key::Tab, true, false, false), None),
+            (input(Key::Backspace, false, true, false), Some(DeleteWord)),
+            (input(Key::Home, true, true, false), Some(MoveCursor(Head))),
+            (input(Key::End, false, true, true), Some(Select(End))),
+            (input(Key::Right, true, true, false), Some(MoveCursor(End))),
+            (input(Key::Right, true, false, false), Some(MoveCursor(WordForward))),
+            (input(Key::Up, true, false, true), Some(Select(ParagraphBack))),
+            (input(Key::Char('c'), true, false, false), Some(Copy)),
+            (input(Key::Char('/'), true, false, false), Some(ToggleLineComment)),
+            (input(Key::Char('_'), true, false, true), Some(ToggleLineComment)),
+            (input(Key::Char('7'), true, false, false), Some(ToggleLineComment)),
+            (input(Key::Char('7'), false, false, false), Some(InsertChar('7'))),
+            (input(Key::Char('q'), false, true, false), Some(ReflowParagraph)),
+            (input(Key::Paste, false, true, false), Some(Paste)),
+            (input(Key::PageDown, true, false, true), Some(SelectScroll(Scrolling::PageDown))),
+            (input(Key::Char('v'), false, true, false), Some(Scroll(Scrolling::PageUp))),
+            (input(Key::MouseScrollLeft, false, false, false), Some(Scroll((0, -1).into()))),
+            (input(Key::F(1), false, false, false), None),
+            (input(Key::Null, false, false, false), None),
+        ] {
+            assert_eq!(keymap.command(&i), want, "{:?}", i);
+        }
+    }
+
+    #[test]
+    fn edit_bindings() {
+        let mut keymap = Keymap::default();
+        let ctrl_k = input(Key::Char('k'), true, false, false);
+        let f2 = input(Key::F(2), false, false, false);
+        let plain_x = input(Key::Char('x'), false, false, false);
+
+        assert_eq!(keymap.unbind(&ctrl_k), Some(EditorCommand::DeleteLineByEnd));
+        assert_eq!(keymap.command(&ctrl_k), None);
+        assert_eq!(keymap.unbind(&ctrl_k), None);
+
+        assert_eq!(keymap.bind(f2.clone(), EditorCommand::Undo), None);
+        assert_eq!(keymap.command(&f2), Some(EditorCommand::Undo));
+
+        // Bound character inputs are not inserted
+        keymap.bind(plain_x.clone(), EditorCommand::Cut);
+        assert_eq!(keymap.command(&plain_x), Some(EditorCommand::Cut));
+
+        keymap.clear();
+        assert_eq!(keymap.bindings().count(), 0);
+        assert_eq!(keymap.command(&f2), None);
+        assert_eq!(keymap.command(&plain_x), Some(EditorCommand::InsertChar('x')));
+        assert_eq!(keymap, Keymap::new());
+    }
+}
@@ -7,21 +7,34 @@
 #[cfg(all(feature = "ratatui", feature = "tuirs"))]
 compile_error!("ratatui support and tui-rs support are exclusive. only one of them can be enabled at the same time. see https://github.com/rhysd/tui-textarea#installation");
 
+mod bracket;
+mod chord;
+mod clipboard;
 mod cursor;
 mod highlight;
 mod history;
+mod indent;
 mod input;
+mod keymap;
+mod macros;
+mod reflow;
 mod scroll;
 #[cfg(feature = "search")]
 mod search;
+mod text_object;
 mod textarea;
+mod transform;
 mod util;
+mod whitespace;
 mod widget;
 mod word;
 #[cfg(feature = "wrap")]
 mod wrap;
+mod yank;
 #[cfg(feature = "mouse")]
 mod mouse;
+#[cfg(feature = "vim")]
+mod vim;
 
 #[cfg(feature = "ratatui")]
 #[allow(clippy::single_component_path_imports)]
@@ -41,7 +54,16 @@ use termion;
 #[cfg(feature = "tuirs-termion")]
 use termion_15 as termion;
 
+pub use chord::{ChordAction, ChordCallback, ChordDispatcher};
+pub use clipboard::{ClipboardProvider, MemoryClipboard, Osc52Clipboard};
 pub use cursor::CursorMove;
-pub use input::{Input, Key};
+pub use indent::{BracketIndent, IndentRule};
+pub use input::{EditorEvent, Input, Key, ParseInputError};
+pub use keymap::{EditorCommand, Keymap};
+pub use macros::Macro;
 pub use scroll::Scrolling;
+pub use text_object::TextObject;
 pub use textarea::TextArea;
+pub use transform::Case;
+#[cfg(feature = "vim")]
+pub use vim::{ExCommandHandler, Vim, VimMode, VimOperator};
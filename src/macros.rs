@@ -0,0 +1,109 @@
+use crate::input::{Input, Key};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::iter::FromIterator;
+
+/// Keyboard macro which is a sequence of key inputs recorded by [`TextArea::start_recording`](crate::TextArea::start_recording).
+/// Macros are stored in the textarea by name and replayed with [`TextArea::replay_macro`](crate::TextArea::replay_macro).
+///
+/// With `serde` feature, a macro is serialized as an array of [`Input`].
+/// ```
+/// use tui_textarea::{Input, Key, Macro, TextArea};
+///
+/// let mut textarea = TextArea::default();
+///
+/// let inputs = "hi!".chars().map(|c| Input { key: Key::Char(c), ..Default::default() });
+/// textarea.macros_mut().insert("greet".to_string(), inputs.collect());
+///
+/// textarea.replay_macro("greet", 2);
+/// assert_eq!(textarea.lines(), ["hi!hi!"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Macro {
+    inputs: Vec<Input>,
+}
+
+impl Macro {
+    /// Create a macro from key inputs.
+    pub fn new(inputs: Vec<Input>) -> Self {
+        Self { inputs }
+    }
+
+    /// Key inputs in the macro.
+    pub fn inputs(&self) -> &[Input] {
+        &self.inputs
+    }
+
+    /// Return `true` when the macro has no key input.
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+}
+
+impl From<Vec<Input>> for Macro {
+    fn from(inputs: Vec<Input>) -> Self {
+        Self::new(inputs)
+    }
+}
+
+impl FromIterator<Input> for Macro {
+    fn from_iter<I: IntoIterator<Item = Input>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub(crate) struct MacroState {
+    pub(crate) macros: BTreeMap<String, Macro>,
+    recording: Option<(String, Macro)>,
+    // Names of the macros being replayed. This prevents infinite recursion
+    replaying: Vec<String>,
+}
+
+impl MacroState {
+    pub(crate) fn start(&mut self, name: String) {
+        self.recording = Some((name, Macro::default()));
+    }
+
+    pub(crate) fn stop(&mut self) -> bool {
+        match self.recording.take() {
+            Some((name, recorded)) => {
+                self.macros.insert(name, recorded);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub(crate) fn recording(&self) -> Option<&str> {
+        self.recording.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    // Inputs replayed from another macro are not recorded since the input which triggered the replay is recorded
+    pub(crate) fn record(&mut self, inputs: &[Input]) {
+        if !self.replaying.is_empty() {
+            return;
+        }
+        if let Some((_, recorded)) = &mut self.recording {
+            // Mouse inputs with positions never reach here since they are handled before key dispatch
+            let recordable = inputs.iter().filter(|i| i.key != Key::Null);
+            recorded.inputs.extend(recordable.cloned());
+        }
+    }
+
+    pub(crate) fn begin_replay(&mut self, name: &str) -> Option<Vec<Input>> {
+        if self.replaying.iter().any(|n| n == name) {
+            return None;
+        }
+        let inputs = self.macros.get(name)?.inputs.clone();
+        self.replaying.push(name.to_string());
+        Some(inputs)
+    }
+
+    pub(crate) fn end_replay(&mut self) {
+        self.replaying.pop();
+    }
+}
@@ -1,15 +1,69 @@
 #![cfg(feature = "mouse")]
 
 use crate::cursor::CursorMove;
-use crate::input::Key;
+use crate::input::{Input, Key};
+use crate::ratatui::layout::{Alignment, Rect};
 use crate::textarea::TextArea;
+use crate::util::{col_at_display_width, display_width, num_digits, Clock};
+use crate::widget::calculate_horizontal_range;
+use crate::word::find_word_range;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 #[cfg(feature = "wrap")]
 use textwrap::Options;
 
+/// Unit by which a mouse selection grows. It is decided by how many times the mouse button was clicked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SelectUnit {
+    Char,
+    Word,
+    Line,
+}
+
+/// State of the mouse for detecting multiple clicks and extending selections by words or lines while dragging
+#[derive(Clone)]
+pub(crate) struct MouseState {
+    clock: Clock,
+    double_click_interval: Duration,
+    last_click: Option<(Instant, u16, u16)>,
+    click_count: u8,
+    unit: SelectUnit,
+    anchor: ((usize, usize), (usize, usize)),
+    drag: Option<(u16, u16, Rect)>,
+}
+
+impl Default for MouseState {
+    fn default() -> Self {
+        Self {
+            clock: Arc::new(Instant::now),
+            double_click_interval: Duration::from_millis(500),
+            last_click: None,
+            click_count: 0,
+            unit: SelectUnit::Char,
+            anchor: ((0, 0), (0, 0)),
+            drag: None,
+        }
+    }
+}
+
+impl fmt::Debug for MouseState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MouseState")
+            .field("double_click_interval", &self.double_click_interval)
+            .field("last_click", &self.last_click)
+            .field("click_count", &self.click_count)
+            .field("unit", &self.unit)
+            .field("anchor", &self.anchor)
+            .field("drag", &self.drag)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Mouse handling functionality for TextArea
 impl<'a> TextArea<'a> {
     /// Handle mouse events and route to appropriate handler
-    pub fn handle_mouse_event(&mut self, key: Key, widget_area: crate::ratatui::layout::Rect) -> bool {
+    pub fn handle_mouse_event(&mut self, key: Key, widget_area: Rect) -> bool {
         match key {
             Key::MouseClick(x, y) => self.handle_mouse_click(x, y, widget_area),
             Key::MouseDrag(x, y) => self.handle_mouse_drag(x, y, widget_area),
@@ -18,134 +72,434 @@ impl<'a> TextArea<'a> {
         }
     }
 
-    /// Handle mouse click events for text selection and cursor positioning
-    pub fn handle_mouse_click(&mut self, screen_x: u16, screen_y: u16, widget_area: crate::ratatui::layout::Rect) -> bool {
-        // Calculate the actual text area, accounting for block borders if present
-        let text_area = if let Some(block) = self.block() {
-            block.inner(widget_area)
-        } else {
-            widget_area
+    /// Handle mouse events with modifier keys. In addition to [`TextArea::handle_mouse_event`],
+    ///
+    /// - Shift+click extends the current selection (or starts a selection at the cursor) to the clicked position
+    /// - Alt+click starts a rectangular selection and following drags extend the rectangle. Note that some terminals
+    ///   don't report Alt key on mouse events
+    ///
+    /// [`TextArea::input`] calls this method with the area where the textarea was rendered last time, so usually
+    /// you don't need to call this method directly.
+    /// ```
+    /// use ratatui::layout::Rect;
+    /// use tui_textarea::{Input, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello", "world"]);
+    /// let area = Rect::new(0, 0, 10, 2);
+    ///
+    /// textarea.handle_mouse_input(Input { key: Key::MouseClick(1, 0), ..Default::default() }, area);
+    /// textarea.handle_mouse_input(Input { key: Key::MouseClick(3, 1), shift: true, ..Default::default() }, area);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 1), (1, 3))));
+    ///
+    /// // Alt+drag selects the rectangle
+    /// textarea.handle_mouse_input(Input { key: Key::MouseClick(1, 0), alt: true, ..Default::default() }, area);
+    /// textarea.handle_mouse_input(Input { key: Key::MouseDrag(4, 1), alt: true, ..Default::default() }, area);
+    /// textarea.copy();
+    /// assert_eq!(textarea.yank_text(), "ell\norl");
+    /// ```
+    pub fn handle_mouse_input(&mut self, input: impl Into<Input>, widget_area: Rect) -> bool {
+        let mut input = input.into();
+        if let Key::MouseDrag(x, y) = input.key {
+            if self.mouse.drag.is_none() {
+                // Backends which don't report button press start dragging without click
+                input.key = Key::MouseClick(x, y);
+            }
+        }
+        match input {
+            Input {
+                key: Key::MouseClick(x, y),
+                shift: true,
+                ..
+            } => self.handle_mouse_shift_click(x, y, widget_area),
+            Input {
+                key: Key::MouseClick(x, y),
+                alt: true,
+                ..
+            } => {
+                // Multiple clicks are not supported for rectangular selection
+                self.mouse.last_click = None;
+                let handled = self.handle_mouse_click(x, y, widget_area);
+                self.rect_selection = handled;
+                handled
+            }
+            input => self.handle_mouse_event(input.key, widget_area),
+        }
+    }
+
+    /// Handle mouse click events for text selection and cursor positioning. Clicking twice in a row at the same
+    /// position selects the word under the pointer and clicking three times selects the whole line. Subsequent drags
+    /// extend the selection by words or lines respectively. See [`TextArea::set_double_click_interval`].
+    pub fn handle_mouse_click(&mut self, screen_x: u16, screen_y: u16, widget_area: Rect) -> bool {
+        let (row, col) = match self.mouse_to_logical_position(screen_x, screen_y, widget_area) {
+            Some(pos) => pos,
+            None => return false,
         };
-        
-        // Convert screen coordinates to text area relative coordinates
-        let rel_x = screen_x.saturating_sub(text_area.x);
-        let rel_y = screen_y.saturating_sub(text_area.y);
-        
-        // Check if click is within the text area bounds
-        if rel_x >= text_area.width || rel_y >= text_area.height {
-            return false;
+
+        let now = (self.mouse.clock)();
+        let count = match self.mouse.last_click {
+            Some((prev, x, y))
+                if (x, y) == (screen_x, screen_y)
+                    && now.saturating_duration_since(prev) <= self.mouse.double_click_interval =>
+            {
+                self.mouse.click_count % 3 + 1
+            }
+            _ => 1,
+        };
+        self.mouse.last_click = Some((now, screen_x, screen_y));
+        self.mouse.click_count = count;
+        self.mouse.unit = match count {
+            1 => SelectUnit::Char,
+            2 => SelectUnit::Word,
+            _ => SelectUnit::Line,
+        };
+
+        let (start, end) = self.select_unit_range(row, col);
+        self.mouse.anchor = (start, end);
+        self.mouse.drag = Some((screen_x, screen_y, widget_area));
+        self.rect_selection = false;
+        // Start selection on mouse down
+        self.selection_start = Some(start);
+        self.move_cursor(CursorMove::Jump(end.0 as u16, end.1 as u16));
+        true
+    }
+
+    /// Extend the current selection to the clicked position. When no text is selected, the selection starts at the
+    /// cursor position.
+    fn handle_mouse_shift_click(&mut self, screen_x: u16, screen_y: u16, widget_area: Rect) -> bool {
+        let (row, col) = match self.mouse_to_logical_position(screen_x, screen_y, widget_area) {
+            Some(pos) => pos,
+            None => return false,
+        };
+        let start = self.selection_start.unwrap_or_else(|| self.cursor());
+        self.mouse.last_click = None;
+        self.mouse.click_count = 1;
+        self.mouse.unit = SelectUnit::Char;
+        self.mouse.anchor = (start, start);
+        self.mouse.drag = Some((screen_x, screen_y, widget_area));
+        self.extend_mouse_selection(row, col);
+        true
+    }
+
+    /// Handle mouse drag events for extending text selection. When the pointer is outside the text area, the
+    /// textarea is scrolled toward the pointer (horizontally only when wrapping is disabled) and the selection is
+    /// extended to the edge of the text area. See [`TextArea::handle_mouse_tick`] to keep scrolling while the pointer
+    /// stays still.
+    ///
+    /// When the mouse button was not pressed yet, this method starts a selection as [`TextArea::handle_mouse_click`]
+    /// since some backends (e.g. termwiz) don't distinguish button press and drag.
+    pub fn handle_mouse_drag(&mut self, screen_x: u16, screen_y: u16, widget_area: Rect) -> bool {
+        if self.mouse.drag.is_none() {
+            return self.handle_mouse_click(screen_x, screen_y, widget_area);
         }
-        
-        if let Some((row, col)) = self.screen_to_logical_position(rel_x, rel_y, text_area.width, text_area.height) {
-            // Start selection on mouse down
-            self.selection_start = Some((row, col));
-            self.move_cursor(CursorMove::Jump(row as u16, col as u16));
+        if self.drag_to(screen_x, screen_y, widget_area) {
+            self.mouse.drag = Some((screen_x, screen_y, widget_area));
             true
         } else {
             false
         }
     }
 
-    /// Handle mouse drag events for extending text selection
-    pub fn handle_mouse_drag(&mut self, screen_x: u16, screen_y: u16, widget_area: crate::ratatui::layout::Rect) -> bool {
-        // Calculate the actual text area, accounting for block borders if present
-        let text_area = if let Some(block) = self.block() {
-            block.inner(widget_area)
-        } else {
-            widget_area
+    /// Handle mouse up events for finalizing text selection. This method does nothing when the mouse button was not
+    /// pressed.
+    pub fn handle_mouse_up(&mut self, screen_x: u16, screen_y: u16, widget_area: Rect) -> bool {
+        if self.mouse.drag.take().is_none() {
+            return false;
+        }
+        // Finalize selection at current position
+        self.drag_to(screen_x, screen_y, widget_area)
+    }
+
+    /// Continue scrolling the textarea while the mouse button is held at a position outside the text area. Since
+    /// terminals send no events while the pointer stays still, call this method periodically (e.g. on timeout of
+    /// polling events) to keep extending the selection. It returns `true` when the textarea was scrolled.
+    /// ```
+    /// use ratatui::buffer::Buffer;
+    /// use ratatui::layout::Rect;
+    /// use ratatui::widgets::Widget as _;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c", "d", "e"]);
+    /// let area = Rect::new(0, 0, 10, 2);
+    /// textarea.render(area, &mut Buffer::empty(area));
+    ///
+    /// // Start selection and drag the pointer below the text area
+    /// textarea.handle_mouse_click(0, 0, area);
+    /// textarea.handle_mouse_drag(0, 2, area);
+    /// assert_eq!(textarea.cursor(), (2, 0));
+    ///
+    /// // Scrolling continues while the pointer is outside
+    /// assert!(textarea.handle_mouse_tick());
+    /// assert_eq!(textarea.cursor(), (3, 0));
+    /// assert!(textarea.handle_mouse_tick());
+    /// assert_eq!(textarea.cursor(), (4, 0));
+    /// assert!(!textarea.handle_mouse_tick()); // Reached the bottom
+    ///
+    /// textarea.handle_mouse_up(0, 2, area);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (4, 0))));
+    /// assert!(!textarea.handle_mouse_tick());
+    /// ```
+    pub fn handle_mouse_tick(&mut self) -> bool {
+        let (x, y, widget_area) = match self.mouse.drag {
+            Some(drag) => drag,
+            None => return false,
         };
+        let text_area = self.mouse_text_area(widget_area);
+        let inside = (text_area.x..text_area.right()).contains(&x) && (text_area.y..text_area.bottom()).contains(&y);
+        if inside {
+            return false;
+        }
+        let before = self.viewport.scroll_top();
+        self.drag_to(x, y, widget_area);
+        self.viewport.scroll_top() != before
+    }
+
+    /// Set the maximum interval between clicks to be counted as a double-click or a triple-click. The default value
+    /// is 500 milliseconds.
+    /// ```
+    /// use std::time::Duration;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_double_click_interval(Duration::from_millis(300));
+    /// assert_eq!(textarea.double_click_interval(), Duration::from_millis(300));
+    /// ```
+    pub fn set_double_click_interval(&mut self, interval: Duration) {
+        self.mouse.double_click_interval = interval;
+    }
+
+    /// Get the maximum interval between clicks to be counted as a double-click or a triple-click.
+    pub fn double_click_interval(&self) -> Duration {
+        self.mouse.double_click_interval
+    }
+
+    /// Set the clock used for measuring intervals between mouse clicks. By default [`Instant::now`] is used. This is
+    /// useful for testing multiple clicks without waiting.
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use std::time::{Duration, Instant};
+    /// use ratatui::layout::Rect;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let now = Arc::new(Mutex::new(Instant::now()));
+    /// let mut textarea = TextArea::from(["hello world"]);
+    /// let clock = now.clone();
+    /// textarea.set_mouse_clock(move || *clock.lock().unwrap());
+    ///
+    /// let area = Rect::new(0, 0, 20, 1);
+    /// textarea.handle_mouse_click(1, 0, area);
+    /// textarea.handle_mouse_click(1, 0, area);
+    /// assert_eq!(textarea.mouse_click_count(), 2);
+    ///
+    /// // The third click comes too late to be counted as a triple-click
+    /// *now.lock().unwrap() += Duration::from_secs(1);
+    /// textarea.handle_mouse_click(1, 0, area);
+    /// assert_eq!(textarea.mouse_click_count(), 1);
+    /// ```
+    pub fn set_mouse_clock<F>(&mut self, clock: F)
+    where
+        F: Fn() -> Instant + Send + Sync + 'static,
+    {
+        self.mouse.clock = Arc::new(clock);
+    }
+
+    /// Get how many times the mouse button was clicked in a row at the last click. 1 means a single click, 2 means a
+    /// double-click, and 3 means a triple-click. 0 means no click happened yet.
+    /// ```
+    /// use ratatui::layout::Rect;
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["hello world"]);
+    /// let area = Rect::new(0, 0, 20, 1);
+    ///
+    /// assert_eq!(textarea.mouse_click_count(), 0);
+    /// textarea.handle_mouse_click(1, 0, area);
+    /// assert_eq!(textarea.mouse_click_count(), 1);
+    /// textarea.handle_mouse_click(1, 0, area);
+    /// assert_eq!(textarea.mouse_click_count(), 2);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 5))));
+    /// ```
+    pub fn mouse_click_count(&self) -> u8 {
+        self.mouse.click_count
+    }
+
+    /// Convert screen coordinates to the logical position when they are inside the text area of the widget
+    fn mouse_to_logical_position(&self, screen_x: u16, screen_y: u16, widget_area: Rect) -> Option<(usize, usize)> {
+        let text_area = self.mouse_text_area(widget_area);
         
         // Convert screen coordinates to text area relative coordinates
         let rel_x = screen_x.saturating_sub(text_area.x);
         let rel_y = screen_y.saturating_sub(text_area.y);
         
-        // Check if drag is within the text area bounds
+        // Check if the position is within the text area bounds
         if rel_x >= text_area.width || rel_y >= text_area.height {
-            return false;
+            return None;
         }
         
-        if let Some((row, col)) = self.screen_to_logical_position(rel_x, rel_y, text_area.width, text_area.height) {
-            // Extend selection to current drag position
-            self.move_cursor(CursorMove::Jump(row as u16, col as u16));
-            true
-        } else {
-            false
-        }
+        self.screen_to_logical_position(rel_x, rel_y, text_area.width, text_area.height)
     }
 
-    /// Handle mouse up events for finalizing text selection
-    pub fn handle_mouse_up(&mut self, screen_x: u16, screen_y: u16, widget_area: crate::ratatui::layout::Rect) -> bool {
-        // Calculate the actual text area, accounting for block borders if present
-        let text_area = if let Some(block) = self.block() {
+    /// Calculate the actual text area, accounting for block borders if present
+    fn mouse_text_area(&self, widget_area: Rect) -> Rect {
+        if let Some(block) = self.block() {
             block.inner(widget_area)
         } else {
             widget_area
-        };
-        
-        // Convert screen coordinates to text area relative coordinates
-        let rel_x = screen_x.saturating_sub(text_area.x);
-        let rel_y = screen_y.saturating_sub(text_area.y);
-        
-        // Check if release is within the text area bounds
-        if rel_x >= text_area.width || rel_y >= text_area.height {
+        }
+    }
+
+    /// Extend the selection to the dragged position. When the position is outside the text area, scroll the viewport
+    /// by the distance from the edge and extend the selection to the nearest position in the text area.
+    fn drag_to(&mut self, screen_x: u16, screen_y: u16, widget_area: Rect) -> bool {
+        let text_area = self.mouse_text_area(widget_area);
+        if text_area.width == 0 || text_area.height == 0 {
             return false;
         }
-        
-        if let Some((row, col)) = self.screen_to_logical_position(rel_x, rel_y, text_area.width, text_area.height) {
-            // Finalize selection at current position
-            self.move_cursor(CursorMove::Jump(row as u16, col as u16));
-            true
+        let (width, height) = (text_area.width, text_area.height);
+
+        let rows = if screen_y < text_area.y {
+            let (top_row, _) = self.viewport.scroll_top();
+            -((text_area.y - screen_y).min(top_row) as i16)
+        } else if screen_y >= text_area.bottom() {
+            // Scroll until the last line appears at the bottom
+            let remaining = match self.screen_to_logical_position(0, height - 1, width, height) {
+                Some((row, _)) => self.lines().len() - 1 - row,
+                None => 0,
+            };
+            (screen_y - text_area.bottom() + 1).min(remaining as u16) as i16
         } else {
-            false
+            0
+        };
+
+        #[cfg(feature = "wrap")]
+        let scroll_horizontally = !self.wrap_enabled();
+        #[cfg(not(feature = "wrap"))]
+        let scroll_horizontally = true;
+
+        let cols = if !scroll_horizontally {
+            0
+        } else if screen_x < text_area.x {
+            let (_, left_col) = self.viewport.scroll_top();
+            -((text_area.x - screen_x).min(left_col) as i16)
+        } else if screen_x >= text_area.right() {
+            // Scroll until the end of the longest visible line appears at the right edge
+            let (top_row, left_col) = self.viewport.scroll_top();
+            let text_width = width.saturating_sub(self.calculate_line_number_width()) as usize;
+            let bottom_row = (top_row as usize + height as usize).min(self.lines().len());
+            let max_width = self.lines()[top_row as usize..bottom_row]
+                .iter()
+                .map(|l| display_width(l, l.chars().count(), self.tab_length(), self.mask_char()) + 1)
+                .max()
+                .unwrap_or(0);
+            let remaining = max_width.saturating_sub(left_col as usize + text_width);
+            (screen_x - text_area.right() + 1).min(remaining as u16) as i16
+        } else {
+            0
+        };
+
+        if rows != 0 || cols != 0 {
+            self.viewport.scroll(rows, cols);
+        }
+
+        let x = screen_x.clamp(text_area.x, text_area.right() - 1);
+        let y = screen_y.clamp(text_area.y, text_area.bottom() - 1);
+        let pos = self.screen_to_logical_position(x - text_area.x, y - text_area.y, width, height);
+        let (row, col) = match pos {
+            Some(pos) => pos,
+            // Dragged after the last line
+            None => {
+                let row = self.lines().len() - 1;
+                (row, self.lines()[row].chars().count())
+            }
+        };
+        self.extend_mouse_selection(row, col);
+        true
+    }
+
+    /// Range of the unit (character, word, or line) at the position which is selected by clicks
+    fn select_unit_range(&self, row: usize, col: usize) -> ((usize, usize), (usize, usize)) {
+        match self.mouse.unit {
+            SelectUnit::Char => ((row, col), (row, col)),
+            SelectUnit::Word => match find_word_range(&self.lines()[row], col) {
+                Some((start, end)) => ((row, start), (row, end)),
+                None => ((row, col), (row, col)),
+            },
+            SelectUnit::Line => {
+                let end = if row + 1 < self.lines().len() {
+                    (row + 1, 0)
+                } else {
+                    (row, self.lines()[row].chars().count())
+                };
+                ((row, 0), end)
+            }
         }
     }
 
-    /// Convert screen coordinates to logical text position
+    /// Extend the selection started by clicks to the position by the unit of the selection
+    fn extend_mouse_selection(&mut self, row: usize, col: usize) {
+        let (anchor_start, anchor_end) = self.mouse.anchor;
+        let (start, end) = self.select_unit_range(row, col);
+        let (selection_start, cursor) = if start < anchor_start {
+            (anchor_end, start)
+        } else {
+            (anchor_start, end.max(anchor_end))
+        };
+        self.selection_start = Some(selection_start);
+        self.move_cursor(CursorMove::Jump(cursor.0 as u16, cursor.1 as u16));
+    }
+
+    /// Convert screen coordinates to logical text position. `rel_x` and `rel_y` are relative to the top-left corner of
+    /// the text area (inside the block). Tabs, wide characters, horizontal scrolling and text alignment are taken into
+    /// account in the same way as rendering.
     pub fn screen_to_logical_position(&self, rel_x: u16, rel_y: u16, area_width: u16, _area_height: u16) -> Option<(usize, usize)> {
         // Get the current viewport information
         let (top_row, _) = self.viewport.scroll_top();
         let display_line_index = rel_y as usize;
         
-        // Check if wrapping is enabled and handle accordingly
         #[cfg(feature = "wrap")]
-        let wrap_enabled = self.wrap_enabled();
-        #[cfg(not(feature = "wrap"))]
-        let wrap_enabled = false;
-        
-        if wrap_enabled {
-            #[cfg(feature = "wrap")]
+        if self.wrap_enabled() {
             return self.screen_to_logical_position_wrapped(rel_x, display_line_index, area_width, top_row as usize);
-        } else {
-            return self.screen_to_logical_position_unwrapped(rel_x, display_line_index, top_row as usize);
         }
         
-        #[cfg(not(feature = "wrap"))]
-        self.screen_to_logical_position_unwrapped(rel_x, display_line_index, top_row as usize)
+        self.screen_to_logical_position_unwrapped(rel_x, display_line_index, area_width, top_row as usize)
+    }
+
+    /// Calculate how far the rendered line at `row` is shifted by the text alignment when wrapping is disabled.
+    fn unwrapped_line_offset(&self, row: usize, area_width: u16) -> isize {
+        if self.alignment() == Alignment::Left {
+            return 0;
+        }
+        let lnum_width = self.calculate_line_number_width();
+        let line = &self.lines()[row];
+        let (_, col_left) = self.viewport.scroll_top();
+        let text_width = area_width.saturating_sub(lnum_width);
+        let (start, end, _) = calculate_horizontal_range(line, col_left, text_width, self.tab_length());
+        let visible = if start < line.len() { &line[start..end] } else { "" };
+        let lnum_len = num_digits(self.lines().len());
+        // Highlighting the matching brackets doesn't change the width
+        let line_width = self.line_spans(visible, row, lnum_len, None).width();
+        alignment_offset(self.alignment(), line_width, area_width as usize)
     }
 
     /// Convert screen coordinates to logical position when wrapping is disabled
-    fn screen_to_logical_position_unwrapped(&self, rel_x: u16, display_line_index: usize, top_row: usize) -> Option<(usize, usize)> {
+    fn screen_to_logical_position_unwrapped(&self, rel_x: u16, display_line_index: usize, area_width: u16, top_row: usize) -> Option<(usize, usize)> {
         let logical_row = top_row + display_line_index;
         
         // Check if the logical row exists
-        if logical_row >= self.lines().len() {
-            return None;
-        }
+        let line = self.lines().get(logical_row)?;
+        
+        // Position in the rendered line, before the alignment was applied
+        let x = rel_x as isize - self.unwrapped_line_offset(logical_row, area_width);
         
         // Account for line numbers if enabled
-        let lnum_width = self.calculate_line_number_width();
-        if lnum_width > 0 && rel_x < lnum_width {
-            // Click was on line numbers, position at start of line
+        let lnum_width = self.calculate_line_number_width() as isize;
+        if x < lnum_width {
+            // Click was on line numbers or before the aligned text, position at start of line
             return Some((logical_row, 0));
         }
         
-        let adjusted_x = if lnum_width > 0 {
-            rel_x.saturating_sub(lnum_width)
-        } else {
-            rel_x
-        };
-        
-        let logical_col = std::cmp::min(adjusted_x as usize, self.lines()[logical_row].chars().count());
+        let (_, left_col) = self.viewport.scroll_top();
+        let x = (x - lnum_width) as usize + left_col as usize;
+        let logical_col = col_at_display_width(line, x, self.tab_length(), self.mask_char());
         Some((logical_row, logical_col))
     }
 
@@ -173,33 +527,28 @@ impl<'a> TextArea<'a> {
                 let wrapped_line_offset = display_line_index - current_display_line;
                 
                 if wrapped_line_offset < wrapped_lines.len() {
-                    let wrapped_line = &wrapped_lines[wrapped_line_offset];
+                    // Calculate the absolute character position of the wrapped line in the original line
+                    let char_offset = wrapped_lines.iter()
+                        .take(wrapped_line_offset)
+                        .map(|line| line.chars().count())
+                        .sum::<usize>();
+                    let char_end = char_offset + wrapped_lines[wrapped_line_offset].chars().count();
+                    
+                    // Tab stops are relative to the head of the logical line as in rendering
+                    let (tab_len, mask) = (self.tab_length(), self.mask_char());
+                    let base_width = display_width(line_text, char_offset, tab_len, mask);
+                    let segment_width = display_width(line_text, char_end, tab_len, mask) - base_width;
+                    let line_width = lnum_width as usize + segment_width;
+                    let x = rel_x as isize - alignment_offset(self.alignment(), line_width, area_width as usize);
                     
                     // Account for line numbers if enabled
-                    if lnum_width > 0 && rel_x < lnum_width {
+                    if x < lnum_width as isize {
                         // Click was on line numbers
-                        let char_offset = wrapped_lines.iter()
-                            .take(wrapped_line_offset)
-                            .map(|line| line.chars().count())
-                            .sum::<usize>();
                         return Some((logical_row, char_offset));
                     }
                     
-                    let adjusted_x = if lnum_width > 0 {
-                        rel_x.saturating_sub(lnum_width)
-                    } else {
-                        rel_x
-                    };
-                    
-                    let char_in_wrapped_line = std::cmp::min(adjusted_x as usize, wrapped_line.chars().count());
-                    
-                    // Calculate the absolute character position in the original line
-                    let char_offset = wrapped_lines.iter()
-                        .take(wrapped_line_offset)
-                        .map(|line| line.chars().count())
-                        .sum::<usize>();
-                    
-                    let logical_col = char_offset + char_in_wrapped_line;
+                    let x = base_width + (x - lnum_width as isize) as usize;
+                    let logical_col = col_at_display_width(line_text, x, tab_len, mask).min(char_end);
                     return Some((logical_row, logical_col));
                 } else {
                     // Click was on an empty area after the last wrapped line
@@ -224,18 +573,10 @@ impl<'a> TextArea<'a> {
         }
         
         #[cfg(feature = "wrap")]
-        let wrap_enabled = self.wrap_enabled();
-        #[cfg(not(feature = "wrap"))]
-        let wrap_enabled = false;
-        
-        if wrap_enabled {
-            #[cfg(feature = "wrap")]
+        if self.wrap_enabled() {
             return self.logical_to_screen_position_wrapped(logical_row, logical_col, area_width, area_height, top_row as usize);
-        } else {
-            return self.logical_to_screen_position_unwrapped(logical_row, logical_col, area_width, area_height, top_row as usize, left_col);
         }
         
-        #[cfg(not(feature = "wrap"))]
         self.logical_to_screen_position_unwrapped(logical_row, logical_col, area_width, area_height, top_row as usize, left_col)
     }
 
@@ -248,24 +589,16 @@ impl<'a> TextArea<'a> {
         }
         
         // Get line and calculate visual position
-        if logical_row >= self.lines().len() {
-            return None;
-        }
-        
-        let line = &self.lines()[logical_row];
-        let visual_col = line.chars().take(logical_col).map(|c| {
-            if c == '\t' {
-                self.tab_len as usize
-            } else {
-                unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
-            }
-        }).sum::<usize>();
+        let line = self.lines().get(logical_row)?;
+        let visual_col = display_width(line, logical_col, self.tab_length(), self.mask_char());
         
-        // Account for horizontal scrolling and line numbers
+        // Account for horizontal scrolling, line numbers and alignment
         let lnum_width = self.calculate_line_number_width();
-        let screen_x = visual_col.saturating_sub(left_col as usize) + lnum_width as usize;
+        let screen_x = visual_col.checked_sub(left_col as usize)? as isize
+            + lnum_width as isize
+            + self.unwrapped_line_offset(logical_row, area_width);
         
-        if screen_x >= area_width as usize {
+        if screen_x < 0 || screen_x >= area_width as isize {
             return None; // Cursor is outside visible area horizontally
         }
         
@@ -298,68 +631,568 @@ impl<'a> TextArea<'a> {
         }
         
         // Handle the cursor's logical row
-        if logical_row < self.lines().len() {
-            let line = &self.lines()[logical_row];
-            let wrapped_lines = textwrap::wrap(line, &options);
+        let line = self.lines().get(logical_row)?;
+        let wrapped_lines = textwrap::wrap(line, &options);
+        
+        if wrapped_lines.is_empty() {
+            // Empty line case
+            let screen_y = current_display_line;
+            if screen_y >= area_height as usize {
+                return None;
+            }
+            let x = alignment_offset(self.alignment(), lnum_width as usize, area_width as usize);
+            return Some(((x + lnum_width as isize).max(0) as u16, screen_y as u16));
+        }
+        
+        // Find which wrapped line contains the cursor. When the cursor is beyond the end of the line, it is put at the
+        // end of the last wrapped line.
+        let (tab_len, mask) = (self.tab_length(), self.mask_char());
+        let last_wrapped_idx = wrapped_lines.len() - 1;
+        let mut char_count = 0;
+        for (wrapped_idx, wrapped_line) in wrapped_lines.iter().enumerate() {
+            let line_char_count = wrapped_line.chars().count();
             
-            if wrapped_lines.is_empty() {
-                // Empty line case
-                let screen_y = current_display_line;
+            if logical_col <= char_count + line_char_count || wrapped_idx == last_wrapped_idx {
+                // Cursor is in this wrapped line
+                let screen_y = current_display_line + wrapped_idx;
                 if screen_y >= area_height as usize {
                     return None;
                 }
-                return Some((lnum_width, screen_y as u16));
-            }
-            
-            // Find which wrapped line contains the cursor
-            let mut char_count = 0;
-            for (wrapped_idx, wrapped_line) in wrapped_lines.iter().enumerate() {
-                let line_char_count = wrapped_line.chars().count();
                 
-                if logical_col <= char_count + line_char_count {
-                    // Cursor is in this wrapped line
-                    let screen_y = current_display_line + wrapped_idx;
-                    if screen_y >= area_height as usize {
-                        return None;
-                    }
-                    
-                    let char_in_wrapped_line = logical_col - char_count;
-                    let visual_x = wrapped_line.chars().take(char_in_wrapped_line).map(|c| {
-                        if c == '\t' {
-                            self.tab_len as usize
-                        } else {
-                            unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
-                        }
-                    }).sum::<usize>();
-                    
-                    let screen_x = lnum_width as usize + visual_x;
-                    
-                    return Some((screen_x as u16, screen_y as u16));
-                }
+                let base_width = display_width(line, char_count, tab_len, mask);
+                let segment_width = display_width(line, char_count + line_char_count, tab_len, mask) - base_width;
+                let visual_x = display_width(line, logical_col.min(char_count + line_char_count), tab_len, mask) - base_width;
+                let offset = alignment_offset(self.alignment(), lnum_width as usize + segment_width, area_width as usize);
+                let screen_x = offset + (lnum_width as usize + visual_x) as isize;
                 
-                char_count += line_char_count;
-            }
-            
-            // Cursor is beyond the end of the line
-            let last_wrapped_idx = wrapped_lines.len() - 1;
-            let screen_y = current_display_line + last_wrapped_idx;
-            if screen_y >= area_height as usize {
-                return None;
+                return Some((screen_x.max(0) as u16, screen_y as u16));
             }
             
-            let last_wrapped_line = &wrapped_lines[last_wrapped_idx];
-            let visual_x = last_wrapped_line.chars().map(|c| {
-                if c == '\t' {
-                    self.tab_len as usize
-                } else {
-                    unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
-                }
-            }).sum::<usize>();
-            
-            let screen_x = lnum_width as usize + visual_x;
-            Some((screen_x as u16, screen_y as u16))
-        } else {
-            None
+            char_count += line_char_count;
+        }
+        
+        None
+    }
+}
+
+/// Calculate how far a rendered line is shifted by the text alignment. This mirrors how ratatui's `Line` renders
+/// aligned spans: a line narrower than the area is indented, and a line wider than the area is truncated on the left.
+fn alignment_offset(alignment: Alignment, line_width: usize, area_width: usize) -> isize {
+    let indent = area_width.saturating_sub(line_width) as isize;
+    let skip = line_width.saturating_sub(area_width) as isize;
+    match alignment {
+        Alignment::Left => 0,
+        Alignment::Center => indent / 2 - skip / 2,
+        Alignment::Right => indent - skip,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ratatui::buffer::Buffer;
+    use crate::ratatui::widgets::Widget as _;
+
+    fn render(textarea: &TextArea<'_>, area: Rect) {
+        let mut buf = Buffer::empty(area);
+        textarea.render(area, &mut buf);
+    }
+
+    // ((x, y), (row, col))
+    type Click = ((u16, u16), (usize, usize));
+
+    #[track_caller]
+    fn assert_clicks(textarea: &mut TextArea<'_>, area: Rect, clicks: &[Click]) {
+        for &((x, y), want) in clicks {
+            assert!(textarea.handle_mouse_click(x, y, area), "click at {:?}", (x, y));
+            assert_eq!(textarea.cursor(), want, "click at {:?} in {:?}", (x, y), textarea.lines());
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn click_on_tabs_and_wide_chars() {
+        let area = Rect::new(0, 0, 20, 5);
+        let mut textarea = TextArea::from(["a\tb", "あいう", "🐶a", "\t\tx"]);
+        render(&textarea, area);
+        assert_clicks(
+            &mut textarea,
+            area,
+            &[
+                ((0, 0), (0, 0)),
+                ((1, 0), (0, 1)),
+                ((3, 0), (0, 1)),
+                ((4, 0), (0, 2)),
+                ((5, 0), (0, 3)),
+                ((19, 0), (0, 3)),
+                ((0, 1), (1, 0)),
+                ((1, 1), (1, 0)),
+                ((2, 1), (1, 1)),
+                ((5, 1), (1, 2)),
+                ((6, 1), (1, 3)),
+                ((1, 2), (2, 0)),
+                ((2, 2), (2, 1)),
+                ((3, 3), (3, 0)),
+                ((4, 3), (3, 1)),
+                ((8, 3), (3, 2)),
+            ],
+        );
+
+        textarea.set_tab_length(2);
+        assert_clicks(&mut textarea, area, &[((2, 0), (0, 2)), ((2, 3), (3, 1))]);
+    }
+
+    #[test]
+    fn click_with_line_numbers() {
+        let area = Rect::new(0, 0, 20, 5);
+        let mut textarea = TextArea::from(["\tab"]);
+        textarea.set_line_number_style(Default::default());
+        render(&textarea, area);
+        // Line number takes " 1 " (3 cells)
+        assert_clicks(
+            &mut textarea,
+            area,
+            &[((1, 0), (0, 0)), ((3, 0), (0, 0)), ((6, 0), (0, 0)), ((7, 0), (0, 1))],
+        );
+    }
+
+    #[test]
+    fn click_with_horizontal_scroll() {
+        let area = Rect::new(0, 0, 6, 2);
+        let mut textarea = TextArea::from(["0123456789abcdef", "あいうえおかきく"]);
+        render(&textarea, area);
+        textarea.scroll((0, 4));
+        render(&textarea, area);
+        assert_eq!(textarea.viewport.scroll_top(), (0, 4));
+        assert_clicks(
+            &mut textarea,
+            area,
+            &[
+                ((0, 0), (0, 4)),
+                ((5, 0), (0, 9)),
+                ((0, 1), (1, 2)),
+                ((1, 1), (1, 2)),
+                ((2, 1), (1, 3)),
+            ],
+        );
+    }
+
+    #[test]
+    fn click_on_aligned_text() {
+        let area = Rect::new(0, 0, 10, 3);
+        let mut textarea = TextArea::from(["abc", "あい", "x"]);
+        textarea.move_cursor(CursorMove::Bottom);
+
+        // "abc" is rendered at x=3..6 and "あい" is rendered at x=3..7
+        textarea.set_alignment(Alignment::Center);
+        render(&textarea, area);
+        assert_clicks(
+            &mut textarea,
+            area,
+            &[
+                ((0, 0), (0, 0)),
+                ((3, 0), (0, 0)),
+                ((4, 0), (0, 1)),
+                ((5, 0), (0, 2)),
+                ((9, 0), (0, 3)),
+                ((4, 1), (1, 0)),
+                ((5, 1), (1, 1)),
+            ],
+        );
+
+        // "abc" is rendered at x=7..10. When the cursor is at the end of the line, the cursor cell at x=9 shifts the
+        // text to x=6..9
+        textarea.set_alignment(Alignment::Right);
+        textarea.move_cursor(CursorMove::Jump(2, 0));
+        render(&textarea, area);
+        assert_clicks(&mut textarea, area, &[((7, 0), (0, 0)), ((8, 0), (0, 1))]);
+        textarea.move_cursor(CursorMove::Jump(0, 3));
+        render(&textarea, area);
+        assert_clicks(&mut textarea, area, &[((9, 0), (0, 3)), ((6, 0), (0, 0))]);
+    }
+
+    #[test]
+    fn logical_to_screen_with_wide_chars() {
+        let area = Rect::new(0, 0, 20, 2);
+        let mut textarea = TextArea::from(["a\tあb"]);
+        render(&textarea, area);
+        for (col, want) in [(0, 0), (1, 1), (2, 4), (3, 6), (4, 7)] {
+            textarea.move_cursor(CursorMove::Jump(0, col));
+            assert_eq!(
+                textarea.logical_to_screen_position(area.width, area.height),
+                Some((want, 0)),
+                "col {}",
+                col,
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "wrap")]
+    fn click_on_wrapped_wide_chars() {
+        let area = Rect::new(0, 0, 4, 5);
+        let mut textarea = TextArea::from(["あいうえお"]);
+        textarea.set_wrap(true);
+        render(&textarea, area);
+        // Wrapped into "あい", "うえ", "お"
+        assert_clicks(
+            &mut textarea,
+            area,
+            &[
+                ((1, 0), (0, 0)),
+                ((2, 0), (0, 1)),
+                ((3, 1), (0, 3)),
+                ((0, 2), (0, 4)),
+                ((3, 2), (0, 5)),
+            ],
+        );
+    }
+
+    fn manual_clock(textarea: &mut TextArea<'_>) -> Arc<std::sync::Mutex<Instant>> {
+        let now = Arc::new(std::sync::Mutex::new(Instant::now()));
+        let clock = now.clone();
+        textarea.set_mouse_clock(move || *clock.lock().unwrap());
+        now
+    }
+
+    #[test]
+    fn double_click_selects_word() {
+        let area = Rect::new(0, 0, 20, 3);
+        let mut textarea = TextArea::from(["foo bar.baz", "  qux"]);
+        manual_clock(&mut textarea);
+        render(&textarea, area);
+
+        for (x, y, want) in [
+            (5, 0, ((0, 4), (0, 7))),
+            (7, 0, ((0, 7), (0, 8))),
+            (3, 0, ((0, 3), (0, 4))),
+            (0, 1, ((1, 0), (1, 2))),
+            (10, 0, ((0, 8), (0, 11))),
+            // Clicking after the end of line selects the last word
+            (15, 0, ((0, 8), (0, 11))),
+        ] {
+            textarea.handle_mouse_click(x, y, area);
+            textarea.handle_mouse_click(x, y, area);
+            assert_eq!(textarea.mouse_click_count(), 2);
+            assert_eq!(textarea.selection_range(), Some(want), "click at {:?}", (x, y));
+            // Reset the click count
+            textarea.handle_mouse_click(19, 2, area);
+        }
+    }
+
+    #[test]
+    fn triple_click_selects_line() {
+        let area = Rect::new(0, 0, 20, 3);
+        let mut textarea = TextArea::from(["foo bar", "baz"]);
+        manual_clock(&mut textarea);
+        render(&textarea, area);
+
+        for _ in 0..3 {
+            textarea.handle_mouse_click(1, 0, area);
+        }
+        assert_eq!(textarea.mouse_click_count(), 3);
+        assert_eq!(textarea.selection_range(), Some(((0, 0), (1, 0))));
+
+        // The last line has no newline to select
+        for _ in 0..3 {
+            textarea.handle_mouse_click(1, 1, area);
+        }
+        assert_eq!(textarea.selection_range(), Some(((1, 0), (1, 3))));
+
+        // Fourth click goes back to single click
+        textarea.handle_mouse_click(1, 1, area);
+        assert_eq!(textarea.mouse_click_count(), 1);
+        assert_eq!(textarea.cursor(), (1, 1));
+    }
+
+    #[test]
+    fn clicks_out_of_interval() {
+        let area = Rect::new(0, 0, 20, 3);
+        let mut textarea = TextArea::from(["foo bar"]);
+        let now = manual_clock(&mut textarea);
+        textarea.set_double_click_interval(Duration::from_millis(200));
+        render(&textarea, area);
+
+        textarea.handle_mouse_click(1, 0, area);
+        *now.lock().unwrap() += Duration::from_millis(200);
+        textarea.handle_mouse_click(1, 0, area);
+        assert_eq!(textarea.mouse_click_count(), 2);
+
+        *now.lock().unwrap() += Duration::from_millis(201);
+        textarea.handle_mouse_click(1, 0, area);
+        assert_eq!(textarea.mouse_click_count(), 1);
+        assert_eq!(textarea.selection_range(), Some(((0, 1), (0, 1))));
+
+        // Click at different position is not counted
+        textarea.handle_mouse_click(2, 0, area);
+        assert_eq!(textarea.mouse_click_count(), 1);
+    }
+
+    #[test]
+    fn drag_extends_by_words() {
+        let area = Rect::new(0, 0, 20, 3);
+        let mut textarea = TextArea::from(["foo bar baz", "qux"]);
+        manual_clock(&mut textarea);
+        render(&textarea, area);
+
+        textarea.handle_mouse_click(5, 0, area);
+        textarea.handle_mouse_click(5, 0, area);
+        textarea.handle_mouse_drag(9, 0, area);
+        assert_eq!(textarea.selection_range(), Some(((0, 4), (0, 11))));
+        assert_eq!(textarea.cursor(), (0, 11));
+
+        textarea.handle_mouse_drag(1, 0, area);
+        assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 7))));
+        assert_eq!(textarea.cursor(), (0, 0));
+
+        textarea.handle_mouse_up(1, 1, area);
+        assert_eq!(textarea.selection_range(), Some(((0, 4), (1, 3))));
+    }
+
+    #[test]
+    fn drag_extends_by_lines() {
+        let area = Rect::new(0, 0, 20, 4);
+        let mut textarea = TextArea::from(["foo", "bar", "baz"]);
+        manual_clock(&mut textarea);
+        render(&textarea, area);
+
+        for _ in 0..3 {
+            textarea.handle_mouse_click(1, 1, area);
+        }
+        textarea.handle_mouse_drag(0, 2, area);
+        assert_eq!(textarea.selection_range(), Some(((1, 0), (2, 3))));
+
+        textarea.handle_mouse_drag(2, 0, area);
+        assert_eq!(textarea.selection_range(), Some(((0, 0), (2, 0))));
+        assert_eq!(textarea.cursor(), (0, 0));
+    }
+
+    #[test]
+    fn drag_outside_scrolls_vertically() {
+        let area = Rect::new(0, 0, 10, 3);
+        let lines: Vec<String> = (0..10).map(|i| format!("line{}", i)).collect();
+        let mut textarea = TextArea::from(lines);
+        textarea.move_cursor(CursorMove::Jump(5, 0));
+        render(&textarea, area);
+        assert_eq!(textarea.viewport.scroll_top(), (3, 0));
+
+        textarea.handle_mouse_click(2, 1, area);
+        // Scroll by the distance from the edge
+        assert!(textarea.handle_mouse_drag(2, 6, area));
+        assert_eq!(textarea.viewport.scroll_top(), (7, 0));
+        assert_eq!(textarea.cursor(), (9, 2));
+
+        // Cannot scroll past the last line
+        assert!(!textarea.handle_mouse_tick());
+        assert_eq!(textarea.viewport.scroll_top(), (7, 0));
+
+        textarea.handle_mouse_up(2, 6, area);
+        assert_eq!(textarea.selection_range(), Some(((4, 2), (9, 2))));
+    }
+
+    #[test]
+    fn drag_outside_scrolls_up() {
+        let area = Rect::new(0, 2, 10, 3);
+        let lines: Vec<String> = (0..10).map(|i| format!("line{}", i)).collect();
+        let mut textarea = TextArea::from(lines);
+        textarea.move_cursor(CursorMove::Jump(5, 0));
+        render(&textarea, area);
+        assert_eq!(textarea.viewport.scroll_top(), (3, 0));
+
+        textarea.handle_mouse_click(2, 3, area);
+        assert!(textarea.handle_mouse_drag(1, 1, area));
+        assert_eq!(textarea.viewport.scroll_top(), (2, 0));
+        assert_eq!(textarea.cursor(), (2, 1));
+
+        assert!(textarea.handle_mouse_tick());
+        assert!(textarea.handle_mouse_tick());
+        assert!(!textarea.handle_mouse_tick());
+        assert_eq!(textarea.viewport.scroll_top(), (0, 0));
+        assert_eq!(textarea.selection_range(), Some(((0, 1), (4, 2))));
+    }
+
+    #[test]
+    fn drag_outside_scrolls_horizontally() {
+        let area = Rect::new(0, 0, 5, 2);
+        let mut textarea = TextArea::from(["0123456789", "abc"]);
+        render(&textarea, area);
+
+        textarea.handle_mouse_click(1, 0, area);
+        assert!(textarea.handle_mouse_drag(6, 0, area));
+        assert_eq!(textarea.viewport.scroll_top(), (0, 2));
+        assert_eq!(textarea.cursor(), (0, 6));
+
+        // Scrolling stops when the end of the longest line appears
+        assert!(textarea.handle_mouse_tick());
+        assert!(textarea.handle_mouse_tick());
+        assert!(!textarea.handle_mouse_tick());
+        assert_eq!(textarea.viewport.scroll_top(), (0, 6));
+        assert_eq!(textarea.cursor(), (0, 10));
+
+        // Pointer comes back inside the area
+        assert!(textarea.handle_mouse_drag(0, 0, area));
+        assert!(!textarea.handle_mouse_tick());
+        assert_eq!(textarea.cursor(), (0, 6));
+
+        textarea.handle_mouse_up(0, 1, area);
+        assert_eq!(textarea.selection_range(), Some(((0, 1), (1, 3))));
+        assert!(textarea.mouse.drag.is_none());
+    }
+
+    #[test]
+    fn drag_below_text_selects_to_end() {
+        let area = Rect::new(0, 0, 10, 5);
+        let mut textarea = TextArea::from(["foo", "bar"]);
+        render(&textarea, area);
+
+        textarea.handle_mouse_click(1, 0, area);
+        assert!(textarea.handle_mouse_drag(1, 4, area));
+        assert_eq!(textarea.selection_range(), Some(((0, 1), (1, 3))));
+        assert_eq!(textarea.viewport.scroll_top(), (0, 0));
+    }
+
+    fn mouse_input(key: Key, shift: bool, alt: bool) -> Input {
+        Input {
+            key,
+            ctrl: false,
+            alt,
+            shift,
+        }
+    }
+
+    #[test]
+    fn shift_click_extends_selection() {
+        let area = Rect::new(0, 0, 20, 3);
+        let mut textarea = TextArea::from(["foo bar baz", "qux"]);
+        render(&textarea, area);
+
+        // Without selection, the selection starts at the cursor
+        textarea.move_cursor(CursorMove::Jump(0, 4));
+        assert!(textarea.handle_mouse_input(mouse_input(Key::MouseClick(9, 0), true, false), area));
+        assert_eq!(textarea.selection_range(), Some(((0, 4), (0, 9))));
+
+        // Extend the existing selection backward and forward
+        textarea.handle_mouse_input(mouse_input(Key::MouseClick(1, 0), true, false), area);
+        assert_eq!(textarea.selection_range(), Some(((0, 1), (0, 4))));
+        assert_eq!(textarea.cursor(), (0, 1));
+        textarea.handle_mouse_input(mouse_input(Key::MouseClick(2, 1), true, false), area);
+        assert_eq!(textarea.selection_range(), Some(((0, 4), (1, 2))));
+
+        // Drag after shift+click continues extending
+        textarea.handle_mouse_input(mouse_input(Key::MouseDrag(5, 0), true, false), area);
+        assert_eq!(textarea.selection_range(), Some(((0, 4), (0, 5))));
+
+        // Shift+click is not counted as a multiple click
+        textarea.handle_mouse_input(mouse_input(Key::MouseClick(5, 0), true, false), area);
+        assert_eq!(textarea.mouse_click_count(), 1);
+
+        // Plain click starts a new selection
+        textarea.handle_mouse_input(mouse_input(Key::MouseClick(1, 1), false, false), area);
+        assert_eq!(textarea.selection_range(), Some(((1, 1), (1, 1))));
+    }
+
+    #[test]
+    fn alt_drag_selects_rectangle() {
+        let area = Rect::new(0, 0, 20, 4);
+        let mut textarea = TextArea::from(["abcdef", "\tx", "ab", "あいう"]);
+        textarea.set_tab_length(4);
+        render(&textarea, area);
+
+        textarea.handle_mouse_input(mouse_input(Key::MouseClick(1, 0), false, true), area);
+        textarea.handle_mouse_input(mouse_input(Key::MouseDrag(4, 3), false, true), area);
+        assert!(textarea.rect_selection);
+        assert_eq!(textarea.cursor(), (3, 2));
+
+        let positions: Vec<_> = textarea
+            .rect_selection_positions()
+            .unwrap()
+            .into_iter()
+            .map(|(s, e)| (s.row, s.col, e.col))
+            .collect();
+        assert_eq!(positions, [(0, 1, 4), (1, 0, 1), (2, 1, 2), (3, 0, 2)]);
+
+        textarea.copy();
+        assert_eq!(textarea.yank_text(), "bcd\n\t\nb\nあい");
+        assert!(!textarea.rect_selection);
+
+        textarea.handle_mouse_input(mouse_input(Key::MouseClick(1, 0), false, true), area);
+        textarea.handle_mouse_input(mouse_input(Key::MouseUp(4, 1), false, false), area);
+        assert!(textarea.cut());
+        assert_eq!(textarea.lines(), ["aef", "x", "ab", "あいう"]);
+        assert_eq!(textarea.yank_text(), "bcd\n\t");
+        assert_eq!(textarea.cursor(), (0, 1));
+
+        // Cut of all rows is reverted at once
+        assert!(textarea.undo());
+        assert_eq!(textarea.lines(), ["abcdef", "\tx", "ab", "あいう"]);
+
+        // Plain click goes back to normal selection
+        textarea.handle_mouse_input(mouse_input(Key::MouseClick(1, 0), false, true), area);
+        textarea.handle_mouse_input(mouse_input(Key::MouseClick(1, 0), false, false), area);
+        assert!(!textarea.rect_selection);
+    }
+
+    #[test]
+    fn input_routes_mouse_events() {
+        let area = Rect::new(2, 1, 20, 4);
+        let mut textarea = TextArea::from(["foo bar", "baz"]);
+        textarea.set_block(crate::ratatui::widgets::Block::default().borders(crate::ratatui::widgets::Borders::ALL));
+
+        // Mouse events before rendering are ignored
+        assert!(!textarea.input(mouse_input(Key::MouseClick(4, 2), false, false)));
+        assert_eq!(textarea.cursor(), (0, 0));
+
+        render(&textarea, area);
+        assert!(!textarea.input(mouse_input(Key::MouseClick(4, 2), false, false)));
+        assert_eq!(textarea.cursor(), (0, 1));
+        textarea.input(mouse_input(Key::MouseDrag(6, 2), false, false));
+        textarea.input(mouse_input(Key::MouseUp(6, 2), false, false));
+        assert_eq!(textarea.selection_range(), Some(((0, 1), (0, 3))));
+
+        textarea.input(mouse_input(Key::MouseClick(3, 3), true, false));
+        assert_eq!(textarea.selection_range(), Some(((0, 1), (1, 0))));
+
+        textarea.input_without_shortcuts(mouse_input(Key::MouseClick(5, 2), false, false));
+        assert_eq!(textarea.cursor(), (0, 2));
+    }
+
+    #[test]
+    fn horizontal_wheel() {
+        let area = Rect::new(0, 0, 5, 2);
+        let mut textarea = TextArea::from(["0123456789"]);
+        render(&textarea, area);
+
+        textarea.input(mouse_input(Key::MouseScrollRight, false, false));
+        textarea.input(mouse_input(Key::MouseScrollRight, false, false));
+        assert_eq!(textarea.viewport.scroll_top(), (0, 2));
+        assert_eq!(textarea.cursor(), (0, 2));
+        textarea.input_without_shortcuts(mouse_input(Key::MouseScrollLeft, false, false));
+        assert_eq!(textarea.viewport.scroll_top(), (0, 1));
+    }
+
+    #[test]
+    fn drag_without_press() {
+        let area = Rect::new(0, 0, 20, 3);
+        let mut textarea = TextArea::from(["foo bar", "baz"]);
+        render(&textarea, area);
+
+        // Release without press is ignored (e.g. pointer motion reported by termwiz)
+        assert!(!textarea.handle_mouse_up(2, 1, area));
+        assert_eq!(textarea.cursor(), (0, 0));
+        assert!(!textarea.is_selecting());
+
+        // First drag starts selection like a click
+        assert!(textarea.handle_mouse_drag(1, 0, area));
+        assert_eq!(textarea.mouse_click_count(), 1);
+        assert!(textarea.handle_mouse_drag(5, 0, area));
+        assert!(textarea.handle_mouse_up(5, 0, area));
+        assert_eq!(textarea.selection_range(), Some(((0, 1), (0, 5))));
+
+        // Modifiers are respected for the first drag
+        textarea.handle_mouse_input(mouse_input(Key::MouseDrag(1, 0), false, true), area);
+        assert!(textarea.rect_selection);
+        textarea.handle_mouse_input(mouse_input(Key::MouseDrag(2, 1), false, true), area);
+        textarea.handle_mouse_input(mouse_input(Key::MouseUp(2, 1), false, false), area);
+        textarea.copy();
+        assert_eq!(textarea.yank_text(), "o\na");
+    }
+}
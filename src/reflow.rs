@@ -0,0 +1,229 @@
+use crate::indent::leading_whitespace;
+use crate::util::display_width;
+use std::cmp;
+
+// Split the line into the prefix and the body. The prefix is the indentation optionally followed by the line comment
+// token and whitespaces after it. Characters of the token repeated after it are also part of the prefix so that `///`
+// or `##` is kept when the token is `//` or `#`
+fn split_prefix<'a>(line: &'a str, comment: Option<&str>) -> (&'a str, &'a str) {
+    let mut end = leading_whitespace(line).len();
+    if let Some(token) = comment.filter(|t| !t.is_empty()) {
+        if line[end..].starts_with(token) {
+            end += token.len();
+            end += line[end..]
+                .find(|c| !token.contains(c))
+                .unwrap_or(line.len() - end);
+            end += leading_whitespace(&line[end..]).len();
+        }
+    }
+    line.split_at(end)
+}
+
+// The prefix without whitespaces identifies the kind of the line. Lines in the same paragraph have the same kind
+fn kind<'a>(line: &'a str, comment: Option<&str>) -> Option<&'a str> {
+    let (prefix, body) = split_prefix(line, comment);
+    (!body.is_empty()).then(|| prefix.trim())
+}
+
+// Rows of the paragraph at the row. A paragraph is consecutive lines with the same kind which are not blank. A line
+// which has only a comment token is also a paragraph boundary
+pub(crate) fn paragraph_rows(
+    lines: &[String],
+    row: usize,
+    comment: Option<&str>,
+) -> Option<(usize, usize)> {
+    let k = kind(&lines[row], comment)?;
+    let same = |r: &usize| kind(&lines[*r], comment) == Some(k);
+    let first = (0..row).rev().take_while(same).last().unwrap_or(row);
+    let last = (row + 1..lines.len())
+        .take_while(same)
+        .last()
+        .unwrap_or(row);
+    Some((first, last))
+}
+
+// Reflow each paragraph in the lines so that every line fits in the width. Words in a paragraph are joined with one
+// space. The first line keeps its own prefix and the following lines use the prefix of the second line so that
+// hanging indentation is preserved. A word longer than the width is put on its own line. Lines which are not in any
+// paragraph are kept as-is
+pub(crate) fn reflow(
+    lines: &[String],
+    width: usize,
+    tab_len: u8,
+    comment: Option<&str>,
+) -> Vec<String> {
+    let mut reflowed = Vec::with_capacity(lines.len());
+    let mut row = 0;
+    while row < lines.len() {
+        let last = match paragraph_rows(&lines[row..], 0, comment) {
+            Some((_, last)) => row + last,
+            None => {
+                reflowed.push(lines[row].clone());
+                row += 1;
+                continue;
+            }
+        };
+
+        let first_prefix = split_prefix(&lines[row], comment).0;
+        let rest_prefix = split_prefix(&lines[cmp::min(row + 1, last)], comment).0;
+        let words = lines[row..=last]
+            .iter()
+            .flat_map(|line| split_prefix(line, comment).1.split_whitespace());
+        let mut line = first_prefix.to_string();
+        let mut empty = true;
+        for word in words {
+            if !empty {
+                let len = line.len();
+                line.push(' ');
+                line.push_str(word);
+                if display_width(&line, usize::MAX, tab_len, None) <= width {
+                    continue;
+                }
+                line.truncate(len);
+                reflowed.push(line);
+                line = rest_prefix.to_string();
+            }
+            line.push_str(word);
+            empty = false;
+        }
+        reflowed.push(line);
+        row = last + 1;
+    }
+    reflowed
+}
+
+// Number of non-whitespace characters in bodies of the lines before the position
+pub(crate) fn body_offset(
+    lines: &[String],
+    (row, col): (usize, usize),
+    comment: Option<&str>,
+) -> usize {
+    lines[..=row]
+        .iter()
+        .enumerate()
+        .map(|(r, line)| {
+            let (prefix, body) = split_prefix(line, comment);
+            let n = if r == row {
+                col.saturating_sub(prefix.chars().count())
+            } else {
+                usize::MAX
+            };
+            body.chars().take(n).filter(|c| !c.is_whitespace()).count()
+        })
+        .sum()
+}
+
+// Position of the character after the `offset` non-whitespace characters in bodies of the lines. This is the inverse
+// of `body_offset` and used to keep the cursor at the same character after reflowing
+pub(crate) fn body_position(
+    lines: &[String],
+    offset: usize,
+    comment: Option<&str>,
+) -> (usize, usize) {
+    let mut rest = offset;
+    for (row, line) in lines.iter().enumerate() {
+        let (prefix, body) = split_prefix(line, comment);
+        let head = prefix.chars().count();
+        for (i, c) in body.chars().enumerate() {
+            if c.is_whitespace() {
+                continue;
+            }
+            if rest == 0 {
+                return (row, head + i);
+            }
+            rest -= 1;
+        }
+    }
+    let row = lines.len() - 1;
+    (row, lines[row].chars().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn split_line_prefix() {
+        for (line, comment, want) in [
+            ("  foo bar", None, ("  ", "foo bar")),
+            ("  // foo", None, ("  ", "// foo")),
+            ("  // foo", Some("//"), ("  // ", "foo")),
+            ("/// foo", Some("//"), ("/// ", "foo")),
+            ("\t#  foo", Some("#"), ("\t#  ", "foo")),
+            ("//", Some("//"), ("//", "")),
+            ("foo // bar", Some("//"), ("", "foo // bar")),
+            ("", Some("//"), ("", "")),
+        ] {
+            assert_eq!(split_prefix(line, comment), want, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn find_paragraph() {
+        let text = lines(&["a", "b", "", "// c", "// d", "//", "// e", "f"]);
+        let comment = Some("//");
+        for (row, want) in [
+            (0, Some((0, 1))),
+            (1, Some((0, 1))),
+            (2, None),
+            (3, Some((3, 4))),
+            (5, None),
+            (6, Some((6, 6))),
+            (7, Some((7, 7))),
+        ] {
+            assert_eq!(paragraph_rows(&text, row, comment), want, "{}", row);
+        }
+        // Without comment token, comment lines are paragraphs of plain text
+        assert_eq!(paragraph_rows(&text, 4, None), Some((3, 7)));
+    }
+
+    #[test]
+    fn reflow_paragraphs() {
+        for (text, width, want) in [
+            (&["aaa bbb ccc ddd"][..], 8, &["aaa bbb", "ccc ddd"][..]),
+            (&["aaa", "bbb", "ccc"][..], 8, &["aaa bbb", "ccc"][..]),
+            (&["aaa   bbb  "][..], 80, &["aaa bbb"][..]),
+            (
+                &["  // aaa bbb", "  // ccc", "", "ddd eee"][..],
+                10,
+                &["  // aaa", "  // bbb", "  // ccc", "", "ddd eee"][..],
+            ),
+            (
+                &["- aaa bbb", "  ccc ddd"][..],
+                9,
+                &["- aaa bbb", "  ccc ddd"][..],
+            ),
+            (&["- aaa bbb ccc ddd"][..], 9, &["- aaa bbb", "ccc ddd"][..]),
+            (&["aaaaaaaaaa b"][..], 4, &["aaaaaaaaaa", "b"][..]),
+            (&["a", "// b", "// c"][..], 80, &["a", "// b c"][..]),
+            (&["\ta b"][..], 5, &["\ta", "\tb"][..]),
+            (&["あいう えお"][..], 8, &["あいう", "えお"][..]),
+            (&["", "  "][..], 80, &["", "  "][..]),
+        ] {
+            assert_eq!(
+                reflow(&lines(text), width, 4, Some("//")),
+                want,
+                "{:?}",
+                text,
+            );
+        }
+    }
+
+    #[test]
+    fn map_cursor_position() {
+        let before = lines(&["// aa bb", "// cc"]);
+        let after = lines(&["// aa", "// bb cc"]);
+        let comment = Some("//");
+        for (pos, offset, want) in [
+            ((0, 0), 0, (0, 3)),
+            ((0, 4), 1, (0, 4)),
+            ((0, 6), 2, (1, 3)),
+            ((1, 4), 5, (1, 7)),
+            ((1, 5), 6, (1, 8)),
+        ] {
+            assert_eq!(body_offset(&before, pos, comment), offset, "{:?}", pos);
+            assert_eq!(body_position(&after, offset, comment), want, "{:?}", pos);
+        }
+    }
+}
@@ -0,0 +1,537 @@
+use crate::textarea::TextArea;
+use crate::word::find_word_range;
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Specify a range of text around the cursor like Vim's text objects. "Inner" objects don't include surrounding
+/// white spaces or delimiters and "around" objects include them. See [`TextArea::text_object_range`] and
+/// [`TextArea::select_text_object`].
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum TextObject {
+    /// Word at the cursor. Words are separated by white spaces and punctuations (like Vim's `iw`).
+    /// ```
+    /// use tui_textarea::{TextArea, TextObject, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo.bar baz"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    ///
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerWord), Some(((0, 4), (0, 7))));
+    /// ```
+    InnerWord,
+    /// Word at the cursor with trailing white spaces. When there is no trailing white space, leading white spaces are
+    /// included instead (like Vim's `aw`).
+    /// ```
+    /// use tui_textarea::{TextArea, TextObject, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo.bar baz"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    ///
+    /// assert_eq!(textarea.text_object_range(TextObject::AroundWord), Some(((0, 4), (0, 8))));
+    /// ```
+    AroundWord,
+    /// WORD at the cursor. WORDs are separated only by white spaces (like Vim's `iW`).
+    /// ```
+    /// use tui_textarea::{TextArea, TextObject, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo.bar baz"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 5));
+    ///
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerBigWord), Some(((0, 0), (0, 7))));
+    /// ```
+    InnerBigWord,
+    /// WORD at the cursor with trailing (or leading) white spaces (like Vim's `aW`).
+    AroundBigWord,
+    /// Sentence at the cursor. Sentences end with `.`, `!`, or `?` followed by a white space or a line end. Blank lines
+    /// also separate sentences (like Vim's `is`).
+    /// ```
+    /// use tui_textarea::{TextArea, TextObject, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["Hello. How are", "you? Fine."]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 8));
+    ///
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerSentence), Some(((0, 7), (1, 4))));
+    /// ```
+    InnerSentence,
+    /// Sentence at the cursor with trailing (or leading) white spaces (like Vim's `as`).
+    AroundSentence,
+    /// Paragraph at the cursor. Paragraphs are separated by blank lines. When the cursor is on a blank line, the
+    /// consecutive blank lines are selected (like Vim's `ip`).
+    /// ```
+    /// use tui_textarea::{TextArea, TextObject};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "", "c"]);
+    ///
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerParagraph), Some(((0, 0), (2, 0))));
+    /// ```
+    InnerParagraph,
+    /// Paragraph at the cursor with following (or preceding) blank lines (like Vim's `ap`).
+    AroundParagraph,
+    /// Text inside the innermost bracket pair enclosing the cursor. The character is one of the brackets `(`, `)`,
+    /// `[`, `]`, `{`, `}`, `<`, `>`. When the brackets are at the end and the head of lines, the range consists of
+    /// the whole lines between them (like Vim's `i(`).
+    /// ```
+    /// use tui_textarea::{TextArea, TextObject, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["f(a, (b), c)"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 3));
+    ///
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerBracket('(')), Some(((0, 2), (0, 11))));
+    /// ```
+    InnerBracket(char),
+    /// Innermost bracket pair enclosing the cursor including the brackets (like Vim's `a(`).
+    AroundBracket(char),
+    /// Text inside the quote pair on the cursor line. When the cursor is not in quotes, the next quoted text is used.
+    /// Quotes escaped with `\` are ignored (like Vim's `i"`).
+    /// ```
+    /// use tui_textarea::{TextArea, TextObject};
+    ///
+    /// let mut textarea = TextArea::from([r#"say "hi" now"#]);
+    ///
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerQuote('"')), Some(((0, 5), (0, 7))));
+    /// ```
+    InnerQuote(char),
+    /// Quote pair on the cursor line including the quotes and trailing (or leading) white spaces (like Vim's `a"`).
+    AroundQuote(char),
+    /// Contents of the innermost XML/HTML tag pair enclosing the cursor (like Vim's `it`).
+    /// ```
+    /// use tui_textarea::{TextArea, TextObject, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["<p><b>hi</b></p>"]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 6));
+    ///
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerTag), Some(((0, 6), (0, 8))));
+    /// ```
+    InnerTag,
+    /// Innermost XML/HTML tag pair enclosing the cursor including the tags (like Vim's `at`).
+    AroundTag,
+}
+
+type Range = ((usize, usize), (usize, usize));
+
+// Text flattened into characters. Lines are joined with '\n' so that searches can go across lines.
+struct Flat {
+    chars: Vec<char>,
+    starts: Vec<usize>,
+}
+
+impl Flat {
+    fn new(lines: &[String]) -> Self {
+        let mut chars = vec![];
+        let mut starts = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            if i > 0 {
+                chars.push('\n');
+            }
+            starts.push(chars.len());
+            chars.extend(line.chars());
+        }
+        Self { chars, starts }
+    }
+
+    fn index(&self, (row, col): (usize, usize)) -> usize {
+        self.starts[row] + col
+    }
+
+    fn pos(&self, i: usize) -> (usize, usize) {
+        let row = self.starts.partition_point(|&s| s <= i) - 1;
+        (row, i - self.starts[row])
+    }
+
+    fn range(&self, start: usize, end: usize) -> Range {
+        (self.pos(start), self.pos(end))
+    }
+}
+
+fn bracket_pair(c: char) -> Option<(char, char)> {
+    match c {
+        '(' | ')' => Some(('(', ')')),
+        '[' | ']' => Some(('[', ']')),
+        '{' | '}' => Some(('{', '}')),
+        '<' | '>' => Some(('<', '>')),
+        _ => None,
+    }
+}
+
+// Extend the range over the trailing white spaces, or over the leading white spaces when no trailing one exists
+fn around_spaces(chars: &[char], start: usize, end: usize) -> (usize, usize) {
+    let is_space = |c: &char| *c == ' ' || *c == '\t';
+    let trailing = chars[end..].iter().take_while(|c| is_space(c)).count();
+    if trailing > 0 {
+        return (start, end + trailing);
+    }
+    let leading = chars[..start]
+        .iter()
+        .rev()
+        .take_while(|c| is_space(c))
+        .count();
+    (start - leading, end)
+}
+
+fn word_range(line: &str, col: usize, around: bool) -> Option<(usize, usize)> {
+    let (start, end) = find_word_range(line, col)?;
+    if !around {
+        return Some((start, end));
+    }
+    let chars: Vec<char> = line.chars().collect();
+    if chars[start].is_whitespace() {
+        // On white spaces, the following word is included
+        let end = find_word_range(line, end).map_or(end, |(_, e)| e);
+        return Some((start, end));
+    }
+    Some(around_spaces(&chars, start, end))
+}
+
+fn big_word_range(line: &str, col: usize, around: bool) -> Option<(usize, usize)> {
+    let chars: Vec<char> = line.chars().collect();
+    let last = chars.len().checked_sub(1)?;
+    let col = col.min(last);
+    let space = chars[col].is_whitespace();
+    let start = chars[..col]
+        .iter()
+        .rposition(|c| c.is_whitespace() != space)
+        .map_or(0, |i| i + 1);
+    let end = chars[col..]
+        .iter()
+        .position(|c| c.is_whitespace() != space)
+        .map_or(chars.len(), |i| col + i);
+    if !around {
+        return Some((start, end));
+    }
+    if space {
+        let end = chars[end..]
+            .iter()
+            .position(|c| c.is_whitespace())
+            .map_or(chars.len(), |i| end + i);
+        return Some((start, end));
+    }
+    Some(around_spaces(&chars, start, end))
+}
+
+fn quote_range(line: &str, col: usize, quote: char, around: bool) -> Option<(usize, usize)> {
+    let chars: Vec<char> = line.chars().collect();
+    let quotes: Vec<usize> = (0..chars.len())
+        .filter(|&i| chars[i] == quote && (i == 0 || chars[i - 1] != '\\'))
+        .collect();
+    let (open, close) = quotes
+        .chunks_exact(2)
+        .map(|p| (p[0], p[1]))
+        .find(|&(_, close)| col <= close)?;
+    if around {
+        Some(around_spaces(&chars, open, close + 1))
+    } else {
+        Some((open + 1, close))
+    }
+}
+
+fn bracket_range(lines: &[String], cursor: (usize, usize), c: char, around: bool) -> Option<Range> {
+    let (open, close) = bracket_pair(c)?;
+    let flat = Flat::new(lines);
+    let chars = &flat.chars;
+    let cursor = flat.index(cursor);
+
+    // Search the open bracket enclosing the cursor backward
+    let start = if chars.get(cursor) == Some(&open) {
+        cursor
+    } else {
+        let mut depth = 0usize;
+        let mut found = None;
+        for i in (0..cursor.min(chars.len())).rev() {
+            if chars[i] == close {
+                depth += 1;
+            } else if chars[i] == open {
+                if depth == 0 {
+                    found = Some(i);
+                    break;
+                }
+                depth -= 1;
+            }
+        }
+        found?
+    };
+
+    // Search the matching close bracket forward
+    let mut depth = 0usize;
+    let mut end = None;
+    for (i, &ch) in chars.iter().enumerate().skip(start + 1) {
+        if ch == open {
+            depth += 1;
+        } else if ch == close {
+            if depth == 0 {
+                end = Some(i);
+                break;
+            }
+            depth -= 1;
+        }
+    }
+    let end = end?;
+
+    if around {
+        return Some(flat.range(start, end + 1));
+    }
+
+    // When the brackets are at the end and the head of lines, the lines between them are the inner object
+    let (start_row, _) = flat.pos(start);
+    let (end_row, end_col) = flat.pos(end);
+    let open_at_end = chars.get(start + 1) == Some(&'\n');
+    let close_at_head = chars[end - end_col..end].iter().all(|c| c.is_whitespace());
+    if open_at_end && close_at_head && start_row + 1 < end_row {
+        return Some(((start_row + 1, 0), (end_row, 0)));
+    }
+    Some(flat.range(start + 1, end))
+}
+
+fn is_blank(line: &str) -> bool {
+    line.chars().all(char::is_whitespace)
+}
+
+fn paragraph_range(lines: &[String], row: usize, around: bool) -> Range {
+    let blank = is_blank(&lines[row]);
+    let same_block = |r: &usize| is_blank(&lines[*r]) == blank;
+    let top = (0..row).rev().find(|r| !same_block(r)).map_or(0, |r| r + 1);
+    let mut bottom = (row..lines.len())
+        .find(|r| !same_block(r))
+        .map_or(lines.len() - 1, |r| r - 1);
+    let mut top = top;
+    if around {
+        let following = (bottom + 1..lines.len())
+            .take_while(|&r| is_blank(&lines[r]) != blank)
+            .count();
+        if following > 0 {
+            bottom += following;
+        } else {
+            top -= (0..top)
+                .rev()
+                .take_while(|&r| is_blank(&lines[r]) != blank)
+                .count();
+        }
+    }
+    if bottom + 1 < lines.len() {
+        ((top, 0), (bottom + 1, 0))
+    } else if top > 0 {
+        // At the end of text, the line break before the paragraph is included instead
+        let prev = lines[top - 1].chars().count();
+        ((top - 1, prev), (bottom, lines[bottom].chars().count()))
+    } else {
+        ((0, 0), (bottom, lines[bottom].chars().count()))
+    }
+}
+
+fn sentence_range(lines: &[String], cursor: (usize, usize), around: bool) -> Option<Range> {
+    let (row, _) = cursor;
+    if is_blank(&lines[row]) {
+        return None;
+    }
+    let top = (0..row)
+        .rev()
+        .find(|&r| is_blank(&lines[r]))
+        .map_or(0, |r| r + 1);
+    let bottom = (row..lines.len())
+        .find(|&r| is_blank(&lines[r]))
+        .map_or(lines.len(), |r| r)
+        - 1;
+
+    let flat = Flat::new(lines);
+    let (begin, end) = (
+        flat.starts[top],
+        flat.index((bottom, lines[bottom].chars().count())),
+    );
+    let chars = &flat.chars;
+    let cursor = flat.index(cursor).min(end.saturating_sub(1)).max(begin);
+
+    // Find the start and the (exclusive) end of each sentence in the paragraph
+    let mut sentences = vec![];
+    let mut i = begin;
+    while i < end {
+        while i < end && chars[i].is_whitespace() {
+            i += 1;
+        }
+        if i == end {
+            break;
+        }
+        let start = i;
+        let mut stop = end;
+        while i < end {
+            if let '.' | '!' | '?' = chars[i] {
+                let mut j = i + 1;
+                while j < end && matches!(chars[j], ')' | ']' | '"' | '\'') {
+                    j += 1;
+                }
+                if j == end || chars[j].is_whitespace() {
+                    stop = j;
+                    break;
+                }
+            }
+            i += 1;
+        }
+        i = stop;
+        sentences.push((start, stop));
+    }
+
+    let idx = sentences.iter().position(|&(_, e)| cursor < e)?;
+    let (start, stop) = sentences[idx];
+    if !around {
+        return Some(flat.range(start, stop));
+    }
+    match sentences.get(idx + 1) {
+        Some(&(next, _)) => Some(flat.range(start, next)),
+        None => {
+            // The last sentence includes the white spaces before it instead
+            let leading = if idx > 0 { sentences[idx - 1].1 } else { start };
+            Some(flat.range(leading, stop))
+        }
+    }
+}
+
+// Tag found in text. Indices are of the flattened characters and the end is exclusive.
+struct Tag {
+    name: String,
+    start: usize,
+    end: usize,
+    closing: bool,
+}
+
+fn parse_tags(chars: &[char]) -> Vec<Tag> {
+    let mut tags = vec![];
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] != '<' {
+            i += 1;
+            continue;
+        }
+        let end = match chars[i + 1..].iter().position(|&c| c == '>' || c == '<') {
+            Some(n) if chars[i + 1 + n] == '>' => i + 2 + n,
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        let body = &chars[i + 1..end - 1];
+        let closing = body.first() == Some(&'/');
+        let name: String = body
+            .iter()
+            .skip(closing as usize)
+            .take_while(|c| !c.is_whitespace() && **c != '/')
+            .collect();
+        let self_closing = body.last() == Some(&'/');
+        let valid = name
+            .chars()
+            .next()
+            .map_or(false, |c| c.is_alphabetic() || c == '_');
+        if valid && !self_closing {
+            tags.push(Tag {
+                name,
+                start: i,
+                end,
+                closing,
+            });
+        }
+        i = end;
+    }
+    tags
+}
+
+fn tag_range(lines: &[String], cursor: (usize, usize), around: bool) -> Option<Range> {
+    let flat = Flat::new(lines);
+    let cursor = flat.index(cursor);
+
+    // Pair open and close tags with a stack. Unmatched tags are ignored
+    let mut stack: Vec<&Tag> = vec![];
+    let mut pairs = vec![];
+    let tags = parse_tags(&flat.chars);
+    for tag in tags.iter() {
+        if !tag.closing {
+            stack.push(tag);
+            continue;
+        }
+        if let Some(i) = stack.iter().rposition(|t| t.name == tag.name) {
+            pairs.push((stack[i].start, stack[i].end, tag.start, tag.end));
+            stack.truncate(i);
+        }
+    }
+
+    // Innermost pair enclosing the cursor
+    let (open_start, open_end, close_start, close_end) = pairs
+        .into_iter()
+        .filter(|&(s, _, _, e)| s <= cursor && cursor < e)
+        .min_by_key(|&(s, _, _, e)| e - s)?;
+    if around {
+        Some(flat.range(open_start, close_end))
+    } else {
+        Some(flat.range(open_end, close_start))
+    }
+}
+
+impl TextArea<'_> {
+    /// Get the range of the text object at the cursor. The range is a pair of the start position and the end
+    /// position (exclusive) in the same format as [`TextArea::selection_range`]. `None` is returned when the text
+    /// object is not found. See [`TextObject`] for each text object.
+    /// ```
+    /// use tui_textarea::{TextArea, TextObject, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {", "    a(b)", "}"]);
+    /// textarea.move_cursor(CursorMove::Jump(1, 4));
+    ///
+    /// // Lines inside the brackets
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerBracket('{')), Some(((1, 0), (2, 0))));
+    /// assert_eq!(textarea.text_object_range(TextObject::AroundBracket('}')), Some(((0, 7), (2, 1))));
+    /// assert_eq!(textarea.text_object_range(TextObject::InnerBracket('[')), None);
+    /// ```
+    pub fn text_object_range(
+        &self,
+        object: TextObject,
+    ) -> Option<((usize, usize), (usize, usize))> {
+        let lines = self.lines();
+        let (row, col) = self.cursor();
+        let line = &lines[row];
+        let in_line = |r: Option<(usize, usize)>| r.map(|(s, e)| ((row, s), (row, e)));
+        match object {
+            TextObject::InnerWord => in_line(word_range(line, col, false)),
+            TextObject::AroundWord => in_line(word_range(line, col, true)),
+            TextObject::InnerBigWord => in_line(big_word_range(line, col, false)),
+            TextObject::AroundBigWord => in_line(big_word_range(line, col, true)),
+            TextObject::InnerSentence => sentence_range(lines, (row, col), false),
+            TextObject::AroundSentence => sentence_range(lines, (row, col), true),
+            TextObject::InnerParagraph => Some(paragraph_range(lines, row, false)),
+            TextObject::AroundParagraph => Some(paragraph_range(lines, row, true)),
+            TextObject::InnerBracket(c) => bracket_range(lines, (row, col), c, false),
+            TextObject::AroundBracket(c) => bracket_range(lines, (row, col), c, true),
+            TextObject::InnerQuote(q) => in_line(quote_range(line, col, q, false)),
+            TextObject::AroundQuote(q) => in_line(quote_range(line, col, q, true)),
+            TextObject::InnerTag => tag_range(lines, (row, col), false),
+            TextObject::AroundTag => tag_range(lines, (row, col), true),
+        }
+    }
+
+    /// Select the text object at the cursor. The selection starts at the start of the text object and the cursor
+    /// moves to its end. This method returns whether the text object was found. When it was not found, the cursor
+    /// and the selection are not changed.
+    /// ```
+    /// use tui_textarea::{TextArea, TextObject, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from([r#"print("hello world")"#]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 9));
+    ///
+    /// assert!(textarea.select_text_object(TextObject::InnerQuote('"')));
+    /// textarea.cut();
+    /// assert_eq!(textarea.lines(), [r#"print("")"#]);
+    /// ```
+    pub fn select_text_object(&mut self, object: TextObject) -> bool {
+        match self.text_object_range(object) {
+            Some((start, end)) => {
+                self.cancel_selection();
+                self.set_cursor(start);
+                self.start_selection();
+                self.set_cursor(end);
+                true
+            }
+            None => false,
+        }
+    }
+}
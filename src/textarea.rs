@@ -1,63 +1,43 @@
+use crate::bracket::{find_matching_bracket, DEFAULT_BRACKET_QUOTES};
+use crate::chord::{ChordAction, ChordDispatcher, Dispatch};
+use crate::clipboard::ClipboardProvider;
 use crate::cursor::CursorMove;
 use crate::highlight::LineHighlighter;
 use crate::history::{Edit, EditKind, History};
-use crate::input::{Input, Key};
+use crate::indent::{
+    dedent_len, indent_string, indent_width, is_blank, leading_whitespace, IndentRule,
+};
+use crate::input::{EditorEvent, Input, Key};
+use crate::keymap::{EditorCommand, Keymap};
+use crate::macros::{Macro, MacroState};
+use crate::reflow::{body_offset, body_position, paragraph_rows, reflow};
+#[cfg(feature = "mouse")]
+use crate::mouse::MouseState;
 use crate::ratatui::layout::Alignment;
 use crate::ratatui::style::{Color, Modifier, Style};
 use crate::ratatui::widgets::{Block, Widget};
 use crate::scroll::Scrolling;
 #[cfg(feature = "search")]
 use crate::search::Search;
-use crate::util::{spaces, Pos};
+use crate::text_object::TextObject;
+use crate::transform::Case;
+use crate::util::{col_at_display_width, display_width, spaces, Pos, Shared};
+use crate::whitespace::{collapse_blank, final_newline, retab, trim_trailing};
 use crate::widget::Viewport;
 use crate::word::{find_word_exclusive_end_forward, find_word_start_backward};
+use crate::yank::{KillRing, Registers, YankText};
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
-use std::cmp::Ordering;
-use std::fmt;
+use std::cmp::{self, Ordering};
+use std::collections::{BTreeMap, HashSet};
+use std::iter;
+use std::sync::Arc;
 
 #[cfg(feature = "tuirs")]
 use tui::text::Spans as Line;
-use unicode_width::UnicodeWidthChar as _;
 
 // Include module implementations that extend TextArea with additional methods
 
-#[derive(Debug, Clone)]
-enum YankText {
-    Piece(String),
-    Chunk(Vec<String>),
-}
-
-impl Default for YankText {
-    fn default() -> Self {
-        Self::Piece(String::new())
-    }
-}
-
-impl From<String> for YankText {
-    fn from(s: String) -> Self {
-        Self::Piece(s)
-    }
-}
-impl From<Vec<String>> for YankText {
-    fn from(mut c: Vec<String>) -> Self {
-        match c.len() {
-            0 => Self::default(),
-            1 => Self::Piece(c.remove(0)),
-            _ => Self::Chunk(c),
-        }
-    }
-}
-
-impl fmt::Display for YankText {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::Piece(s) => write!(f, "{}", s),
-            Self::Chunk(ss) => write!(f, "{}", ss.join("\n")),
-        }
-    }
-}
-
 /// A type to manage state of textarea. These are some important methods:
 ///
 /// - [`TextArea::default`] creates an empty textarea.
@@ -114,12 +94,30 @@ pub struct TextArea<'a> {
     cursor: (usize, usize), // 0-base
     pub(crate) tab_len: u8,
     hard_tab_indent: bool,
+    smart_backspace: bool,
+    auto_pairs: Vec<(char, char)>,
+    line_comment: Option<String>,
+    block_comment: Option<(String, String)>,
+    fill_column: usize,
+    auto_indent: bool,
+    indenter: Shared<dyn IndentRule>,
     history: History,
     cursor_line_style: Style,
     line_number_style: Option<Style>,
+    matching_bracket_style: Option<Style>,
+    bracket_quotes: Vec<char>,
     pub(crate) viewport: Viewport,
     pub(crate) cursor_style: Style,
-    yank: YankText,
+    overwrite: bool,
+    overwrite_cursor_style: Option<Style>,
+    // Characters replaced in the current overwrite run. `None` means the character was appended at end of line.
+    // The run ends when the cursor moves or other edit happens
+    overwritten: Vec<Option<char>>,
+    overwrite_end: (usize, usize),
+    kill_ring: KillRing,
+    registers: Registers,
+    clipboard: Shared<dyn ClipboardProvider>,
+    macros: MacroState,
     #[cfg(feature = "search")]
     search: Search,
     alignment: Alignment,
@@ -127,11 +125,16 @@ pub struct TextArea<'a> {
     pub(crate) placeholder_style: Style,
     mask: Option<char>,
     pub(crate) selection_start: Option<(usize, usize)>,
+    pub(crate) rect_selection: bool,
     select_style: Style,
     #[cfg(feature = "wrap")]
     pub(crate) wrap_enabled: bool,
     #[cfg(feature = "wrap")]
     pub(crate) wrap_width: Option<usize>,
+    #[cfg(feature = "mouse")]
+    pub(crate) mouse: MouseState,
+    keymap: Keymap,
+    chords: ChordDispatcher,
 }
 
 /// Convert any iterator whose elements can be converted into [`String`] into [`TextArea`]. Each [`String`] element is
@@ -223,12 +226,28 @@ impl<'a> TextArea<'a> {
             cursor: (0, 0),
             tab_len: 4,
             hard_tab_indent: false,
+            smart_backspace: false,
+            auto_pairs: vec![],
+            line_comment: Some("//".to_string()),
+            block_comment: Some(("/*".to_string(), "*/".to_string())),
+            fill_column: 80,
+            auto_indent: false,
+            indenter: Shared::default(),
             history: History::new(50),
             cursor_line_style: Style::default().add_modifier(Modifier::UNDERLINED),
             line_number_style: None,
+            matching_bracket_style: None,
+            bracket_quotes: DEFAULT_BRACKET_QUOTES.to_vec(),
             viewport: Viewport::default(),
             cursor_style: Style::default().add_modifier(Modifier::REVERSED),
-            yank: YankText::default(),
+            overwrite: false,
+            overwrite_cursor_style: None,
+            overwritten: vec![],
+            overwrite_end: (0, 0),
+            kill_ring: KillRing::default(),
+            registers: Registers::default(),
+            clipboard: Shared::default(),
+            macros: MacroState::default(),
             #[cfg(feature = "search")]
             search: Search::default(),
             alignment: Alignment::Left,
@@ -236,11 +255,16 @@ impl<'a> TextArea<'a> {
             placeholder_style: Style::default().fg(Color::DarkGray),
             mask: None,
             selection_start: None,
+            rect_selection: false,
             select_style: Style::default().bg(Color::LightBlue),
             #[cfg(feature = "wrap")]
             wrap_enabled: false,
             #[cfg(feature = "wrap")]
             wrap_width: None,
+            #[cfg(feature = "mouse")]
+            mouse: MouseState::default(),
+            keymap: Keymap::default(),
+            chords: ChordDispatcher::default(),
         }
     }
 
@@ -282,25 +306,290 @@ impl<'a> TextArea<'a> {
     /// ```
     pub fn input(&mut self, input: impl Into<Input>) -> bool {
         let input = input.into();
-        let modified = match input {
-            Input {
-                key: Key::Char('m'),
-                ctrl: true,
-                alt: false,
-                ..
+
+        #[cfg(feature = "mouse")]
+        if let Key::MouseClick(..) | Key::MouseDrag(..) | Key::MouseUp(..) = input.key {
+            // Mouse positions are mapped with the area where the textarea was rendered last time
+            self.handle_mouse_input(input, self.viewport.area());
+            return false;
+        }
+
+        // Inputs of a key sequence are recorded in a macro when the sequence is completed
+        let recording = self.macros.recording().is_some();
+        let mut sequence = if recording {
+            self.chords.pending().to_vec()
+        } else {
+            vec![]
+        };
+        let dispatched = self.chords.dispatch(&input);
+        let completed = matches!(dispatched, Dispatch::Ignored | Dispatch::Action(_));
+
+        let modified = match dispatched {
+            Dispatch::Ignored => match self.keymap.command(&input) {
+                Some(command) => self.execute(command),
+                None => false,
+            },
+            Dispatch::Pending | Dispatch::Canceled => false,
+            Dispatch::Action(ChordAction::Command(command)) => self.execute(command),
+            Dispatch::Action(ChordAction::Callback(callback)) => callback(self),
+        };
+
+        // Inputs which start or stop recording are not recorded
+        if recording && completed && self.macros.recording().is_some() {
+            sequence.push(input.clone());
+            self.macros.record(&sequence);
+        }
+
+        // Check invariants
+        debug_assert!(!self.lines.is_empty(), "no line after {:?}", input);
+        let (r, c) = self.cursor;
+        debug_assert!(
+            self.lines.len() > r,
+            "cursor {:?} exceeds max lines {} after {:?}",
+            self.cursor,
+            self.lines.len(),
+            input,
+        );
+        debug_assert!(
+            self.lines[r].chars().count() >= c,
+            "cursor {:?} exceeds max col {} at line {:?} after {:?}",
+            self.cursor,
+            self.lines[r].chars().count(),
+            self.lines[r],
+            input,
+        );
+
+        modified
+    }
+
+    /// Handle an input event. Key inputs are handled in the same way as [`TextArea::input`]. Pasted text is inserted
+    /// at the cursor with [`TextArea::insert_str`] as one edit so that a single undo reverts the entire paste
+    /// including the replaced selection. Newlines `\r\n` and `\r` in pasted text are normalized to `\n` since
+    /// terminals often send newlines in bracketed paste as `\r`. Pending key sequence is canceled by pasting. `crossterm` and `termwiz` features enable conversion from their own event
+    /// types into [`EditorEvent`]. This method returns if the event modified text contents or not in the textarea.
+    /// ```
+    /// use tui_textarea::{EditorEvent, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(textarea.handle_event(EditorEvent::Paste("hello\nworld".into())));
+    /// assert_eq!(textarea.lines(), ["hello", "world"]);
+    /// assert!(textarea.undo());
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn handle_event(&mut self, event: impl Into<EditorEvent>) -> bool {
+        match event.into() {
+            EditorEvent::Input(input) => self.input(input),
+            EditorEvent::Paste(text) => {
+                self.chords.cancel();
+                let text = text.replace("\r\n", "\n").replace('\r', "\n");
+                self.history.begin_group();
+                let modified = self.insert_str(text);
+                self.history.end_group();
+                modified
             }
-            | Input {
-                key: Key::Char('\n' | '\r'),
-                ctrl: false,
-                alt: false,
-                ..
+        }
+    }
+
+    /// Run the editor command. This method returns if the command modified text contents or not in the textarea.
+    /// See [`EditorCommand`] for the commands and [`Keymap`] for binding them to key inputs.
+    /// ```
+    /// use tui_textarea::{CursorMove, EditorCommand, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    ///
+    /// textarea.execute(EditorCommand::Select(CursorMove::WordForward));
+    /// assert!(textarea.execute(EditorCommand::Cut));
+    /// assert_eq!(textarea.lines(), [""]);
+    /// assert!(textarea.execute(EditorCommand::Undo));
+    /// assert_eq!(textarea.lines(), ["hello"]);
+    /// ```
+    pub fn execute(&mut self, command: EditorCommand) -> bool {
+        match command {
+            EditorCommand::InsertChar(c) if self.overwrite => {
+                self.overwrite_char(c);
+                true
             }
-            | Input {
-                key: Key::Enter, ..
-            } => {
+            EditorCommand::InsertChar(c) => {
+                self.insert_char(c);
+                true
+            }
+            EditorCommand::InsertNewline => {
                 self.insert_newline();
                 true
             }
+            EditorCommand::InsertTab if self.selection_start.is_some() => self.indent_lines(),
+            EditorCommand::InsertTab => self.insert_tab(),
+            EditorCommand::IndentLines => self.indent_lines(),
+            EditorCommand::DedentLines => self.dedent_lines(),
+            EditorCommand::DeleteChar if self.overwrite => self.delete_overwritten_char(),
+            EditorCommand::DeleteChar => self.delete_char(),
+            EditorCommand::DeleteNextChar => self.delete_next_char(),
+            EditorCommand::DeleteLineByEnd => self.delete_line_by_end(),
+            EditorCommand::DeleteLineByHead => self.delete_line_by_head(),
+            EditorCommand::DeleteWord => self.delete_word(),
+            EditorCommand::DeleteNextWord => self.delete_next_word(),
+            EditorCommand::MoveCursor(m) => {
+                self.move_cursor_with_shift(m, false);
+                false
+            }
+            EditorCommand::Select(m) => {
+                self.move_cursor_with_shift(m, true);
+                false
+            }
+            EditorCommand::Scroll(s) => {
+                self.scroll_with_shift(s, false);
+                false
+            }
+            EditorCommand::SelectScroll(s) => {
+                self.scroll_with_shift(s, true);
+                false
+            }
+            EditorCommand::Undo => self.undo(),
+            EditorCommand::Redo => self.redo(),
+            EditorCommand::Copy => {
+                self.copy();
+                false
+            }
+            EditorCommand::Cut => self.cut(),
+            EditorCommand::Paste => self.paste(),
+            EditorCommand::YankPop => self.yank_pop(),
+            EditorCommand::CopyToRegister(name) => {
+                self.copy_to_register(name);
+                false
+            }
+            EditorCommand::CutToRegister(name) => self.cut_to_register(name),
+            EditorCommand::PasteFromRegister(name) => self.paste_from_register(name),
+            EditorCommand::SelectTextObject(obj) => {
+                self.select_text_object(obj);
+                false
+            }
+            EditorCommand::ToggleOverwrite => {
+                self.toggle_overwrite();
+                false
+            }
+            EditorCommand::MoveLinesUp => self.move_lines_up(),
+            EditorCommand::MoveLinesDown => self.move_lines_down(),
+            EditorCommand::DuplicateLines => self.duplicate_lines(),
+            EditorCommand::JoinLines => self.join_lines(),
+            EditorCommand::SortLines => self.sort_lines(),
+            EditorCommand::ReverseLines => self.reverse_lines(),
+            EditorCommand::DedupLines => self.dedup_lines(),
+            EditorCommand::ConvertCase(case) => self.convert_case(case),
+            EditorCommand::ToggleLineComment => self.toggle_line_comment(),
+            EditorCommand::ToggleBlockComment => self.toggle_block_comment(),
+            EditorCommand::ReflowParagraph => self.reflow_paragraph(),
+            EditorCommand::TrimTrailingWhitespace => self.trim_trailing_whitespace(),
+            EditorCommand::NormalizeIndent => self.normalize_indent(),
+            EditorCommand::CollapseBlankLines => self.collapse_blank_lines(),
+            EditorCommand::EnsureFinalNewline => self.ensure_final_newline(),
+        }
+    }
+
+    /// Set the keymap used by [`TextArea::input`]. See [`Keymap`] for more details.
+    /// ```
+    /// use tui_textarea::{EditorCommand, Input, Key, Keymap, TextArea};
+    ///
+    /// let mut keymap = Keymap::new();
+    /// keymap.bind(Input { key: Key::Enter, ..Default::default() }, EditorCommand::InsertNewline);
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.set_keymap(keymap);
+    ///
+    /// // Ctrl+M is no longer bound
+    /// textarea.input(Input { key: Key::Char('m'), ctrl: true, ..Default::default() });
+    /// assert_eq!(textarea.lines(), [""]);
+    /// textarea.input(Input { key: Key::Enter, ..Default::default() });
+    /// assert_eq!(textarea.lines(), ["", ""]);
+    /// ```
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
+
+    /// Get the keymap used by [`TextArea::input`].
+    pub fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Get the mutable reference to the keymap used by [`TextArea::input`] to add, override, or remove bindings.
+    /// ```
+    /// use tui_textarea::{EditorCommand, Input, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// let ctrl_z = Input { key: Key::Char('z'), ctrl: true, ..Default::default() };
+    /// textarea.keymap_mut().bind(ctrl_z.clone(), EditorCommand::Undo);
+    ///
+    /// textarea.input(Input { key: Key::Char('!'), ..Default::default() });
+    /// assert_eq!(textarea.lines(), ["!hello"]);
+    /// textarea.input(ctrl_z);
+    /// assert_eq!(textarea.lines(), ["hello"]);
+    /// ```
+    pub fn keymap_mut(&mut self) -> &mut Keymap {
+        &mut self.keymap
+    }
+
+    /// Set the dispatcher of multi-key sequences used by [`TextArea::input`]. See [`ChordDispatcher`] for more
+    /// details.
+    pub fn set_chords(&mut self, chords: ChordDispatcher) {
+        self.chords = chords;
+    }
+
+    /// Get the dispatcher of multi-key sequences used by [`TextArea::input`].
+    pub fn chords(&self) -> &ChordDispatcher {
+        &self.chords
+    }
+
+    /// Get the mutable reference to the dispatcher of multi-key sequences used by [`TextArea::input`] to add or
+    /// remove key sequences.
+    /// ```
+    /// use tui_textarea::{EditorCommand, Input, Key, TextArea};
+    ///
+    /// let ctrl = |c| Input { key: Key::Char(c), ctrl: true, ..Default::default() };
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// textarea.chords_mut().bind([ctrl('k'), ctrl('k')], EditorCommand::DeleteLineByEnd);
+    ///
+    /// textarea.input(ctrl('k'));
+    /// assert_eq!(textarea.lines(), ["hello"]);
+    /// textarea.input(ctrl('k'));
+    /// assert_eq!(textarea.lines(), [""]);
+    /// ```
+    pub fn chords_mut(&mut self) -> &mut ChordDispatcher {
+        &mut self.chords
+    }
+
+    /// Get the key inputs of the pending multi-key sequence. This is useful for showing a hint such as `C-x-` while
+    /// the rest of the sequence is awaited. An empty slice is returned when no sequence is pending.
+    /// ```
+    /// use tui_textarea::{EditorCommand, Input, Key, TextArea};
+    ///
+    /// let ctrl = |c| Input { key: Key::Char(c), ctrl: true, ..Default::default() };
+    ///
+    /// let mut textarea = TextArea::default();
+    /// textarea.chords_mut().bind([ctrl('x'), ctrl('u')], EditorCommand::Undo);
+    ///
+    /// textarea.input(ctrl('x'));
+    /// let hint: Vec<_> = textarea.pending_chord().iter().map(|i| i.to_string()).collect();
+    /// assert_eq!(hint.join(" "), "ctrl+x");
+    /// ```
+    pub fn pending_chord(&self) -> &[Input] {
+        self.chords.pending()
+    }
+
+    /// Handle a key input without default key mappings. This method handles only
+    ///
+    /// - Single character input without modifier keys
+    /// - Tab
+    /// - Enter
+    /// - Backspace
+    /// - Delete
+    ///
+    /// This method returns if the input modified text contents or not in the textarea.
+    ///
+    /// This method is useful when you want to define your own key mappings and don't want default key mappings.
+    /// See 'Define your own key mappings' section in [the module document](./index.html).
+    pub fn input_without_shortcuts(&mut self, input: impl Into<Input>) -> bool {
+        match input.into() {
             Input {
                 key: Key::Char(c),
                 ctrl: false,
@@ -314,462 +603,89 @@ impl<'a> TextArea<'a> {
                 key: Key::Tab,
                 ctrl: false,
                 alt: false,
-                ..
-            } => self.insert_tab(),
+                shift: true,
+            } => self.dedent_lines(),
             Input {
-                key: Key::Char('h'),
-                ctrl: true,
-                alt: false,
-                ..
-            }
-            | Input {
-                key: Key::Backspace,
+                key: Key::Tab,
                 ctrl: false,
                 alt: false,
                 ..
-            } => self.delete_char(),
+            } if self.selection_start.is_some() => self.indent_lines(),
             Input {
-                key: Key::Char('d'),
-                ctrl: true,
-                alt: false,
-                ..
-            }
-            | Input {
-                key: Key::Delete,
+                key: Key::Tab,
                 ctrl: false,
                 alt: false,
                 ..
-            } => self.delete_next_char(),
-            Input {
-                key: Key::Char('k'),
-                ctrl: true,
-                alt: false,
-                ..
-            } => self.delete_line_by_end(),
-            Input {
-                key: Key::Char('j'),
-                ctrl: true,
-                alt: false,
-                ..
-            } => self.delete_line_by_head(),
+            } => self.insert_tab(),
             Input {
-                key: Key::Char('w'),
-                ctrl: true,
-                alt: false,
-                ..
-            }
-            | Input {
-                key: Key::Char('h'),
-                ctrl: false,
-                alt: true,
-                ..
-            }
-            | Input {
                 key: Key::Backspace,
-                ctrl: false,
-                alt: true,
-                ..
-            } => self.delete_word(),
-            Input {
-                key: Key::Delete,
-                ctrl: false,
-                alt: true,
-                ..
-            }
-            | Input {
-                key: Key::Char('d'),
-                ctrl: false,
-                alt: true,
                 ..
-            } => self.delete_next_word(),
-            Input {
-                key: Key::Char('n'),
-                ctrl: true,
-                alt: false,
-                shift,
-            }
-            | Input {
-                key: Key::Down,
-                ctrl: false,
-                alt: false,
-                shift,
-            } => {
-                self.move_cursor_with_shift(CursorMove::Down, shift);
-                false
-            }
-            Input {
-                key: Key::Char('p'),
-                ctrl: true,
-                alt: false,
-                shift,
-            }
-            | Input {
-                key: Key::Up,
-                ctrl: false,
-                alt: false,
-                shift,
-            } => {
-                self.move_cursor_with_shift(CursorMove::Up, shift);
-                false
-            }
+            } => self.delete_char(),
             Input {
-                key: Key::Char('f'),
-                ctrl: true,
-                alt: false,
-                shift,
-            }
-            | Input {
-                key: Key::Right,
-                ctrl: false,
-                alt: false,
-                shift,
-            } => {
-                self.move_cursor_with_shift(CursorMove::Forward, shift);
-                false
-            }
+                key: Key::Delete, ..
+            } => self.delete_next_char(),
             Input {
-                key: Key::Char('b'),
-                ctrl: true,
-                alt: false,
-                shift,
-            }
-            | Input {
-                key: Key::Left,
-                ctrl: false,
-                alt: false,
-                shift,
+                key: Key::Enter, ..
             } => {
-                self.move_cursor_with_shift(CursorMove::Back, shift);
-                false
+                self.insert_newline();
+                true
             }
             Input {
-                key: Key::Char('a'),
-                ctrl: true,
-                alt: false,
-                shift,
-            }
-            | Input {
-                key: Key::Home,
-                shift,
+                key: Key::MouseScrollDown,
                 ..
-            }
-            | Input {
-                key: Key::Left | Key::Char('b'),
-                ctrl: true,
-                alt: true,
-                shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::Head, shift);
+                self.scroll((1, 0));
                 false
             }
             Input {
-                key: Key::Char('e'),
-                ctrl: true,
-                alt: false,
-                shift,
-            }
-            | Input {
-                key: Key::End,
-                shift,
+                key: Key::MouseScrollUp,
                 ..
-            }
-            | Input {
-                key: Key::Right | Key::Char('f'),
-                ctrl: true,
-                alt: true,
-                shift,
-            } => {
-                self.move_cursor_with_shift(CursorMove::End, shift);
-                false
-            }
-            Input {
-                key: Key::Char('<'),
-                ctrl: false,
-                alt: true,
-                shift,
-            }
-            | Input {
-                key: Key::Up | Key::Char('p'),
-                ctrl: true,
-                alt: true,
-                shift,
             } => {
-                self.move_cursor_with_shift(CursorMove::Top, shift);
+                self.scroll((-1, 0));
                 false
             }
             Input {
-                key: Key::Char('>'),
-                ctrl: false,
-                alt: true,
-                shift,
-            }
-            | Input {
-                key: Key::Down | Key::Char('n'),
-                ctrl: true,
-                alt: true,
-                shift,
+                key: Key::MouseScrollRight,
+                ..
             } => {
-                self.move_cursor_with_shift(CursorMove::Bottom, shift);
+                self.scroll((0, 1));
                 false
             }
             Input {
-                key: Key::Char('f'),
-                ctrl: false,
-                alt: true,
-                shift,
-            }
-            | Input {
-                key: Key::Right,
-                ctrl: true,
-                alt: false,
-                shift,
+                key: Key::MouseScrollLeft,
+                ..
             } => {
-                self.move_cursor_with_shift(CursorMove::WordForward, shift);
+                self.scroll((0, -1));
                 false
             }
-            Input {
-                key: Key::Char('b'),
-                ctrl: false,
-                alt: true,
-                shift,
-            }
-            | Input {
-                key: Key::Left,
-                ctrl: true,
-                alt: false,
-                shift,
+            #[cfg(feature = "mouse")]
+            input @ Input {
+                key: Key::MouseClick(..) | Key::MouseDrag(..) | Key::MouseUp(..),
+                ..
             } => {
-                self.move_cursor_with_shift(CursorMove::WordBack, shift);
-                false
-            }
-            Input {
-                key: Key::Char(']'),
-                ctrl: false,
-                alt: true,
-                shift,
-            }
-            | Input {
-                key: Key::Char('n'),
-                ctrl: false,
-                alt: true,
-                shift,
-            }
-            | Input {
-                key: Key::Down,
-                ctrl: true,
-                alt: false,
-                shift,
-            } => {
-                self.move_cursor_with_shift(CursorMove::ParagraphForward, shift);
-                false
-            }
-            Input {
-                key: Key::Char('['),
-                ctrl: false,
-                alt: true,
-                shift,
-            }
-            | Input {
-                key: Key::Char('p'),
-                ctrl: false,
-                alt: true,
-                shift,
-            }
-            | Input {
-                key: Key::Up,
-                ctrl: true,
-                alt: false,
-                shift,
-            } => {
-                self.move_cursor_with_shift(CursorMove::ParagraphBack, shift);
-                false
-            }
-            Input {
-                key: Key::Char('u'),
-                ctrl: true,
-                alt: false,
-                ..
-            } => self.undo(),
-            Input {
-                key: Key::Char('r'),
-                ctrl: true,
-                alt: false,
-                ..
-            } => self.redo(),
-            Input {
-                key: Key::Char('y'),
-                ctrl: true,
-                alt: false,
-                ..
-            }
-            | Input {
-                key: Key::Paste, ..
-            } => self.paste(),
-            Input {
-                key: Key::Char('x'),
-                ctrl: true,
-                alt: false,
-                ..
-            }
-            | Input { key: Key::Cut, .. } => self.cut(),
-            Input {
-                key: Key::Char('c'),
-                ctrl: true,
-                alt: false,
-                ..
-            }
-            | Input { key: Key::Copy, .. } => {
-                self.copy();
-                false
-            }
-            Input {
-                key: Key::Char('v'),
-                ctrl: true,
-                alt: false,
-                shift,
-            }
-            | Input {
-                key: Key::PageDown,
-                shift,
-                ..
-            } => {
-                self.scroll_with_shift(Scrolling::PageDown, shift);
-                false
-            }
-            Input {
-                key: Key::Char('v'),
-                ctrl: false,
-                alt: true,
-                shift,
-            }
-            | Input {
-                key: Key::PageUp,
-                shift,
-                ..
-            } => {
-                self.scroll_with_shift(Scrolling::PageUp, shift);
-                false
-            }
-            Input {
-                key: Key::MouseScrollDown,
-                shift,
-                ..
-            } => {
-                self.scroll_with_shift((1, 0).into(), shift);
-                false
-            }
-            Input {
-                key: Key::MouseScrollUp,
-                shift,
-                ..
-            } => {
-                self.scroll_with_shift((-1, 0).into(), shift);
-                false
-            }
-            #[cfg(feature = "mouse")]
-            Input {
-                key: Key::MouseClick(_, _),
-                ..
-            } => {
-                // Mouse clicks are handled through handle_mouse_click() method
-                // which requires text area information not available here
+                let area = self.viewport.area();
+                self.handle_mouse_input(input, area);
                 false
             }
             _ => false,
-        };
-
-        // Check invariants
-        debug_assert!(!self.lines.is_empty(), "no line after {:?}", input);
-        let (r, c) = self.cursor;
-        debug_assert!(
-            self.lines.len() > r,
-            "cursor {:?} exceeds max lines {} after {:?}",
-            self.cursor,
-            self.lines.len(),
-            input,
-        );
-        debug_assert!(
-            self.lines[r].chars().count() >= c,
-            "cursor {:?} exceeds max col {} at line {:?} after {:?}",
-            self.cursor,
-            self.lines[r].chars().count(),
-            self.lines[r],
-            input,
-        );
+        }
+    }
 
-        modified
+    // Record edits until `end_history_group` is called as one entry of the history. The Vim emulation layer uses this to
+    // undo one Vim command at once
+    #[cfg(feature = "vim")]
+    pub(crate) fn begin_history_group(&mut self) {
+        self.history.begin_group();
     }
 
-    /// Handle a key input without default key mappings. This method handles only
-    ///
-    /// - Single character input without modifier keys
-    /// - Tab
-    /// - Enter
-    /// - Backspace
-    /// - Delete
-    ///
-    /// This method returns if the input modified text contents or not in the textarea.
-    ///
-    /// This method is useful when you want to define your own key mappings and don't want default key mappings.
-    /// See 'Define your own key mappings' section in [the module document](./index.html).
-    pub fn input_without_shortcuts(&mut self, input: impl Into<Input>) -> bool {
-        match input.into() {
-            Input {
-                key: Key::Char(c),
-                ctrl: false,
-                alt: false,
-                ..
-            } => {
-                self.insert_char(c);
-                true
-            }
-            Input {
-                key: Key::Tab,
-                ctrl: false,
-                alt: false,
-                ..
-            } => self.insert_tab(),
-            Input {
-                key: Key::Backspace,
-                ..
-            } => self.delete_char(),
-            Input {
-                key: Key::Delete, ..
-            } => self.delete_next_char(),
-            Input {
-                key: Key::Enter, ..
-            } => {
-                self.insert_newline();
-                true
-            }
-            Input {
-                key: Key::MouseScrollDown,
-                ..
-            } => {
-                self.scroll((1, 0));
-                false
-            }
-            Input {
-                key: Key::MouseScrollUp,
-                ..
-            } => {
-                self.scroll((-1, 0));
-                false
-            }
-            #[cfg(feature = "mouse")]
-            Input {
-                key: Key::MouseClick(_, _),
-                ..
-            } => {
-                // Mouse clicks are handled through handle_mouse_click() method
-                // which requires text area information not available here
-                false
-            }
-            _ => false,
-        }
+    #[cfg(feature = "vim")]
+    pub(crate) fn end_history_group(&mut self) {
+        self.history.end_group();
     }
 
     fn push_history(&mut self, kind: EditKind, before: Pos, after_offset: usize) {
+        self.kill_ring.reset_last();
+        self.overwritten.clear();
         let (row, col) = self.cursor;
         let after = Pos::new(row, col, after_offset);
         let edit = Edit::new(kind, before, after);
@@ -791,7 +707,124 @@ impl<'a> TextArea<'a> {
             return;
         }
 
+        if !self.auto_pairs.is_empty() && self.insert_pair(c) {
+            return;
+        }
+
         self.delete_selection(false);
+
+        let dedent = self.auto_indent && self.indenter.get().map_or(false, |r| r.dedent_on(c));
+        if dedent && self.dedent_head(c) {
+            return;
+        }
+
+        self.insert_char_at_cursor(c);
+    }
+
+    // Handle the character with the auto pairs. An opening character wraps the selection or is inserted with its
+    // closing character, and a closing character next to the cursor is stepped over. This method returns `false` when
+    // the character should be inserted as usual
+    fn insert_pair(&mut self, c: char) -> bool {
+        let closer = self
+            .auto_pairs
+            .iter()
+            .find(|(open, _)| *open == c)
+            .map(|(_, close)| *close);
+
+        if let Some(((start_row, start_col), (end_row, end_col))) = self.selection_range() {
+            let closer = match closer {
+                Some(closer) if !self.rect_selection => closer,
+                _ => return false,
+            };
+            let cursor_at_end = self.cursor == (end_row, end_col);
+            self.cancel_selection();
+
+            self.history.begin_group();
+            self.cursor = (end_row, end_col);
+            self.insert_piece(closer.to_string());
+            self.cursor = (start_row, start_col);
+            self.insert_piece(c.to_string());
+            self.history.end_group();
+
+            // Keep selecting the wrapped text
+            let start = (start_row, start_col + 1);
+            let end = (end_row, if start_row == end_row { end_col + 1 } else { end_col });
+            let (anchor, cursor) = if cursor_at_end { (start, end) } else { (end, start) };
+            self.selection_start = Some(anchor);
+            self.cursor = cursor;
+            return true;
+        }
+
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let prev = col.checked_sub(1).and_then(|i| line.chars().nth(i));
+        let next = line.chars().nth(col);
+
+        if next == Some(c) && self.auto_pairs.iter().any(|(_, close)| *close == c) {
+            self.move_cursor_with_shift(CursorMove::Forward, false);
+            return true;
+        }
+
+        let closer = match closer {
+            Some(closer) => closer,
+            None => return false,
+        };
+        // Quotes just after a word are not paired since they are likely apostrophes like "don't"
+        if closer == c && prev.map_or(false, char::is_alphanumeric) {
+            return false;
+        }
+
+        let mut pair = c.to_string();
+        pair.push(closer);
+        self.insert_piece(pair);
+        self.cursor.1 -= 1;
+        true
+    }
+
+    // Byte offsets of the empty pair around the cursor like `(|)`
+    fn empty_pair_around_cursor(&self) -> Option<(usize, usize)> {
+        let (row, col) = self.cursor;
+        let mut chars = self.lines[row].char_indices().skip(col.checked_sub(1)?);
+        let (start, open) = chars.next()?;
+        let (offset, close) = chars.next()?;
+        self.auto_pairs
+            .contains(&(open, close))
+            .then(|| (start, offset + close.len_utf8()))
+    }
+
+    // When the text before the cursor is only whitespaces, dedent it by one level and insert the character in one
+    // undoable edit. This method returns `false` when nothing was done
+    fn dedent_head(&mut self, c: char) -> bool {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let offset = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        let before = &line[..offset];
+        if before.is_empty() || leading_whitespace(before).len() != offset {
+            return false;
+        }
+
+        let width = indent_width(before, self.tab_len);
+        let unit = self.tab_len as usize;
+        let width = if unit > 0 && width > 0 {
+            (width - 1) / unit * unit
+        } else {
+            width
+        };
+        let indent = indent_string(width, self.tab_len, self.hard_tab_indent);
+
+        self.history.begin_group();
+        self.delete_range(Pos::new(row, 0, 0), Pos::new(row, col, offset));
+        self.insert_piece(indent);
+        self.insert_char_at_cursor(c);
+        self.history.end_group();
+        true
+    }
+
+    fn insert_char_at_cursor(&mut self, c: char) {
         let (row, col) = self.cursor;
         let line = &mut self.lines[row];
         let i = line
@@ -808,6 +841,117 @@ impl<'a> TextArea<'a> {
         );
     }
 
+    /// Replace the character under the cursor with the character. At the end of line, the character is appended.
+    /// Newlines are inserted without replacing any character. When text is selected, the selection is replaced with
+    /// the character. The replacement is recorded as a single edit in the history. Auto pairs (see
+    /// [`TextArea::set_auto_pairs`]) are not applied to the replacing character.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    ///
+    /// for c in "xyzw".chars() {
+    ///     textarea.overwrite_char(c);
+    /// }
+    /// assert_eq!(textarea.lines(), ["xyzw"]);
+    /// ```
+    pub fn overwrite_char(&mut self, c: char) {
+        if c == '\n' || c == '\r' || self.selection_start.is_some() {
+            self.insert_char(c);
+            return;
+        }
+
+        let mut overwritten = std::mem::take(&mut self.overwritten);
+        if self.cursor != self.overwrite_end {
+            overwritten.clear();
+        }
+
+        let (row, col) = self.cursor;
+        let replaced = self.lines[row].chars().nth(col);
+        // Delete the replaced character after inserting the new one so that undo puts the cursor before them. The
+        // character is inserted as-is since auto pairs and auto dedent don't make sense on replacing one character
+        self.history.begin_group();
+        self.insert_char_at_cursor(c);
+        if replaced.is_some() {
+            self.cursor.1 += 1;
+            self.delete_char_at_cursor();
+        }
+        self.history.end_group();
+
+        overwritten.push(replaced);
+        self.overwritten = overwritten;
+        self.overwrite_end = self.cursor;
+    }
+
+    // Backspace in overwrite mode. Characters replaced in the current overwrite run are restored. Otherwise this is
+    // the same as `delete_char`
+    fn delete_overwritten_char(&mut self) -> bool {
+        if self.cursor != self.overwrite_end || self.selection_start.is_some() {
+            return self.delete_char();
+        }
+        let mut overwritten = std::mem::take(&mut self.overwritten);
+        let replaced = match overwritten.pop() {
+            Some(replaced) => replaced,
+            None => return self.delete_char(),
+        };
+
+        self.history.begin_group();
+        self.delete_char_at_cursor();
+        if let Some(c) = replaced {
+            self.insert_char_at_cursor(c);
+            self.cursor.1 -= 1;
+        }
+        self.history.end_group();
+
+        self.overwritten = overwritten;
+        self.overwrite_end = self.cursor;
+        true
+    }
+
+    /// Set overwrite mode. In overwrite mode, characters typed via [`TextArea::input`] replace the character under
+    /// the cursor (see [`TextArea::overwrite_char`]) and `Backspace` restores the characters replaced by typing since
+    /// the cursor was last moved. The `Insert` key toggles the mode by default. Calling [`TextArea::insert_char`]
+    /// directly always inserts the character.
+    /// ```
+    /// use tui_textarea::{Input, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["abc"]);
+    /// let key = |key| Input { key, ..Default::default() };
+    ///
+    /// textarea.set_overwrite(true);
+    /// textarea.input(key(Key::Char('x')));
+    /// textarea.input(key(Key::Char('y')));
+    /// assert_eq!(textarea.lines(), ["xyc"]);
+    ///
+    /// textarea.input(key(Key::Backspace));
+    /// assert_eq!(textarea.lines(), ["xbc"]);
+    /// assert_eq!(textarea.cursor(), (0, 1));
+    /// ```
+    pub fn set_overwrite(&mut self, enabled: bool) {
+        self.overwrite = enabled;
+        self.overwritten.clear();
+    }
+
+    /// Return `true` when the textarea is in overwrite mode. See [`TextArea::set_overwrite`].
+    pub fn is_overwrite(&self) -> bool {
+        self.overwrite
+    }
+
+    /// Toggle insert mode and overwrite mode. See [`TextArea::set_overwrite`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.toggle_overwrite();
+    /// assert!(textarea.is_overwrite());
+    /// textarea.toggle_overwrite();
+    /// assert!(!textarea.is_overwrite());
+    /// ```
+    pub fn toggle_overwrite(&mut self) {
+        self.set_overwrite(!self.overwrite);
+    }
+
     /// Insert a string at current cursor position. This method returns if some text was inserted or not in the textarea.
     /// Both `\n` and `\r\n` are recognized as newlines but `\r` isn't.
     /// ```
@@ -888,7 +1032,7 @@ impl<'a> TextArea<'a> {
         true
     }
 
-    fn delete_range(&mut self, start: Pos, end: Pos, should_yank: bool) {
+    fn delete_range(&mut self, start: Pos, end: Pos) -> YankText {
         self.cursor = (start.row, start.col);
 
         if start.row == end.row {
@@ -896,11 +1040,8 @@ impl<'a> TextArea<'a> {
                 .drain(start.offset..end.offset)
                 .as_str()
                 .to_string();
-            if should_yank {
-                self.yank = removed.clone().into();
-            }
-            self.push_history(EditKind::DeleteStr(removed), end, start.offset);
-            return;
+            self.push_history(EditKind::DeleteStr(removed.clone()), end, start.offset);
+            return removed.into();
         }
 
         let mut deleted = vec![self.lines[start.row]
@@ -915,16 +1056,14 @@ impl<'a> TextArea<'a> {
             deleted.push(last_line);
         }
 
-        if should_yank {
-            self.yank = YankText::Chunk(deleted.clone());
-        }
-
+        let yank = YankText::from(deleted.clone());
         let edit = if deleted.len() == 1 {
             EditKind::DeleteStr(deleted.remove(0))
         } else {
             EditKind::DeleteChunk(deleted)
         };
         self.push_history(edit, end, start.offset);
+        yank
     }
 
     /// Delete a string from the current cursor position. The `chars` parameter means number of characters, not a byte
@@ -989,12 +1128,12 @@ impl<'a> TextArea<'a> {
                 .drain(start_offset..end_offset)
                 .as_str()
                 .to_string();
-            self.yank = removed.clone().into();
             self.push_history(
-                EditKind::DeleteStr(removed),
+                EditKind::DeleteStr(removed.clone()),
                 Pos::new(start_row, end_col, end_offset),
                 start_offset,
             );
+            self.push_kill(removed.into());
             return true;
         }
 
@@ -1014,7 +1153,8 @@ impl<'a> TextArea<'a> {
 
         let start = Pos::new(start_row, start_col, start_offset);
         let end = Pos::new(r, col, offset);
-        self.delete_range(start, end, true);
+        let removed = self.delete_range(start, end);
+        self.push_kill(removed);
         true
     }
 
@@ -1036,7 +1176,9 @@ impl<'a> TextArea<'a> {
             (s.len(), last_col + 1)
         }
 
-        let (row, _) = self.cursor;
+        // Consecutive kills at the same position are concatenated in the kill ring
+        let consecutive = self.kill_ring.is_consecutive_kill(self.cursor);
+        let (row, cursor_col) = self.cursor;
         let line = &mut self.lines[row];
         if let Some((i, _)) = line.char_indices().nth(col) {
             let (bytes, chars) = bytes_and_chars(chars, &line[i..]);
@@ -1048,7 +1190,10 @@ impl<'a> TextArea<'a> {
                 Pos::new(row, col + chars, i + bytes),
                 i,
             );
-            self.yank = removed.into();
+            let backward = col < cursor_col;
+            self.kill_ring
+                .kill(removed.into(), self.cursor, backward, consecutive);
+            self.sync_clipboard();
             true
         } else {
             false
@@ -1056,7 +1201,8 @@ impl<'a> TextArea<'a> {
     }
 
     /// Insert a tab at current cursor position. Note that this method does nothing when the tab length is 0. This
-    /// method returns if a tab string was inserted or not in the textarea.
+    /// method returns if a tab string was inserted or not in the textarea. Unless hard tab is enabled, spaces are
+    /// inserted up to the next tab stop counting the display width of the text before the cursor.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
     ///
@@ -1081,15 +1227,713 @@ impl<'a> TextArea<'a> {
         }
 
         let (row, col) = self.cursor;
-        let width: usize = self.lines[row]
-            .chars()
-            .take(col)
-            .map(|c| c.width().unwrap_or(0))
-            .sum();
+        let width = display_width(&self.lines[row], col, self.tab_len, None);
         let len = self.tab_len - (width % self.tab_len as usize) as u8;
         self.insert_piece(spaces(len).to_string())
     }
 
+    /// Indent the lines touched by the selection with [`TextArea::indent`]. When no text is selected, the cursor line is
+    /// indented. Empty lines are not indented. The selection keeps spanning the same text after indenting. All lines
+    /// are indented in one edit so a single undo reverts them. This method returns if some line was indented or not.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["a", "", "b", "c"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.move_cursor(CursorMove::Forward);
+    /// assert!(textarea.indent_lines());
+    /// assert_eq!(textarea.lines(), ["    a", "", "    b", "c"]);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 4), (2, 5))));
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["a", "", "b", "c"]);
+    /// ```
+    pub fn indent_lines(&mut self) -> bool {
+        self.shift_lines(true)
+    }
+
+    /// Dedent the lines touched by the selection by one level. When no text is selected, the cursor line is dedented.
+    /// One tab or spaces up to the tab length are removed from the head of each line. The selection keeps spanning the
+    /// same text after dedenting. All lines are dedented in one edit so a single undo reverts them. This method returns
+    /// if some line was dedented or not.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["      a", "\tb", "  c"]);
+    ///
+    /// textarea.select_all();
+    /// assert!(textarea.dedent_lines());
+    /// assert_eq!(textarea.lines(), ["  a", "b", "c"]);
+    /// assert!(textarea.dedent_lines());
+    /// assert_eq!(textarea.lines(), ["a", "b", "c"]);
+    /// assert!(!textarea.dedent_lines());
+    /// ```
+    pub fn dedent_lines(&mut self) -> bool {
+        self.shift_lines(false)
+    }
+
+    // Rows of lines touched by the selection or the cursor line. When the selection ends at the head of a line, the
+    // line is not included
+    pub(crate) fn selected_rows(&self) -> (usize, usize) {
+        match self.selection_range() {
+            Some(((start_row, _), (end_row, 0))) if end_row > start_row => (start_row, end_row - 1),
+            Some(((start_row, _), (end_row, _))) => (start_row, end_row),
+            None => (self.cursor.0, self.cursor.0),
+        }
+    }
+
+    fn shift_lines(&mut self, indent: bool) -> bool {
+        let (first, last) = self.selected_rows();
+        let unit = self.indent();
+        let mut cursor = self.cursor;
+        let mut selection = self.selection_start.take();
+        let mut modified = false;
+
+        self.history.begin_group();
+        for row in first..=last {
+            let line = &self.lines[row];
+            let chars = if indent {
+                if line.is_empty() || unit.is_empty() {
+                    continue;
+                }
+                self.cursor = (row, 0);
+                self.insert_piece(unit.to_string());
+                unit.len()
+            } else {
+                // Leading whitespaces are ASCII so the number of characters is the same as the byte length
+                let chars = dedent_len(line, self.tab_len);
+                if chars == 0 {
+                    continue;
+                }
+                self.delete_range(Pos::new(row, 0, 0), Pos::new(row, chars, chars));
+                chars
+            };
+            modified = true;
+
+            for pos in iter::once(&mut cursor).chain(selection.as_mut()) {
+                if pos.0 == row {
+                    pos.1 = if indent {
+                        pos.1 + chars
+                    } else {
+                        pos.1.saturating_sub(chars)
+                    };
+                }
+            }
+        }
+        self.history.end_group();
+
+        self.cursor = cursor;
+        self.selection_start = selection;
+        modified
+    }
+
+    /// Move the lines touched by the selection or the cursor line up by one line. The cursor and the selection move
+    /// with the lines. This method returns `false` when the lines cannot be moved since they are at the top.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Bottom);
+    /// assert!(textarea.move_lines_up());
+    /// assert_eq!(textarea.lines(), ["a", "c", "b"]);
+    /// assert_eq!(textarea.cursor(), (1, 0));
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["a", "b", "c"]);
+    /// ```
+    pub fn move_lines_up(&mut self) -> bool {
+        self.move_lines(true)
+    }
+
+    /// Move the lines touched by the selection or the cursor line down by one line. The cursor and the selection move
+    /// with the lines. This method returns `false` when the lines cannot be moved since they are at the bottom.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::Down);
+    /// textarea.move_cursor(CursorMove::End);
+    /// assert!(textarea.move_lines_down());
+    /// assert_eq!(textarea.lines(), ["c", "a", "b"]);
+    /// assert_eq!(textarea.selection_range(), Some(((1, 0), (2, 1))));
+    /// assert!(!textarea.move_lines_down());
+    /// ```
+    pub fn move_lines_down(&mut self) -> bool {
+        self.move_lines(false)
+    }
+
+    fn move_lines(&mut self, up: bool) -> bool {
+        let (first, last) = self.selected_rows();
+        if up && first == 0 || !up && last + 1 >= self.lines.len() {
+            return false;
+        }
+
+        let (start, end) = if up { (first - 1, last) } else { (first, last + 1) };
+        let mut lines = self.lines[start..=end].to_vec();
+        if up {
+            lines.rotate_left(1);
+        } else {
+            lines.rotate_right(1);
+        }
+
+        let cursor = self.cursor;
+        let selection = self.selection_start.take();
+        self.replace_rows(start, end, lines);
+
+        // The selection may end at the head of the line next to the moved lines
+        let move_pos = |(row, col): (usize, usize), lines: &[String]| {
+            let row = if up { row - 1 } else { row + 1 };
+            match lines.get(row) {
+                Some(_) => (row, col),
+                None => (row - 1, lines[row - 1].chars().count()),
+            }
+        };
+        self.cursor = move_pos(cursor, &self.lines);
+        self.selection_start = selection.map(|pos| move_pos(pos, &self.lines));
+        true
+    }
+
+    /// Duplicate the lines touched by the selection or the cursor line. The copy is inserted below the lines, and the
+    /// cursor and the selection move to the copy.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b"]);
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.duplicate_lines();
+    /// assert_eq!(textarea.lines(), ["a", "a", "b"]);
+    /// assert_eq!(textarea.cursor(), (1, 1));
+    /// ```
+    pub fn duplicate_lines(&mut self) -> bool {
+        let (first, last) = self.selected_rows();
+        let lines = self.lines[first..=last].to_vec();
+        let n = lines.len();
+
+        let (row, col) = self.cursor;
+        let selection = self.selection_start.take();
+        self.replace_rows(first, last, lines.iter().chain(lines.iter()).cloned().collect());
+
+        self.cursor = (row + n, col);
+        self.selection_start = selection.map(|(row, col)| (row + n, col));
+        true
+    }
+
+    /// Join the lines touched by the selection into one line. When no text is selected or the selection is in one
+    /// line, the line is joined with the next line. Like Vim's `J`, the indentation of the joined lines is removed and
+    /// one space is inserted between the lines unless the line ends with whitespace or the next line starts with `)`.
+    /// The cursor is put at the last joined position. This method returns `false` when there is no line to join.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["if x {", "    f(", "    )", "}"]);
+    ///
+    /// textarea.select_all();
+    /// assert!(textarea.join_lines());
+    /// assert_eq!(textarea.lines(), ["if x { f() }"]);
+    /// assert_eq!(textarea.cursor(), (0, 10));
+    /// assert!(!textarea.join_lines());
+    /// ```
+    pub fn join_lines(&mut self) -> bool {
+        let (first, last) = self.selected_rows();
+        let last = cmp::max(last, first + 1);
+        if last >= self.lines.len() {
+            return false;
+        }
+
+        let mut joined = self.lines[first].clone();
+        let mut col = 0;
+        for line in &self.lines[first + 1..=last] {
+            let line = line.trim_start();
+            col = joined.chars().count();
+            if !joined.is_empty()
+                && !line.is_empty()
+                && !joined.ends_with(char::is_whitespace)
+                && !line.starts_with(')')
+            {
+                joined.push(' ');
+            }
+            joined.push_str(line);
+        }
+
+        self.selection_start = None;
+        self.replace_rows(first, last, vec![joined]);
+        self.cursor = (first, col);
+        true
+    }
+
+    /// Sort the lines touched by the selection in lexicographical order. When no text is selected, all lines are
+    /// sorted. After sorting, the sorted lines are selected. This method returns if some line was moved or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["b", "c", "a"]);
+    ///
+    /// assert!(textarea.sort_lines());
+    /// assert_eq!(textarea.lines(), ["a", "b", "c"]);
+    /// assert!(!textarea.sort_lines());
+    /// ```
+    pub fn sort_lines(&mut self) -> bool {
+        self.transform_lines(|lines| lines.sort())
+    }
+
+    /// Reverse the order of the lines touched by the selection. When no text is selected, all lines are reversed.
+    /// After reversing, the reversed lines are selected. This method returns if some line was moved or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    ///
+    /// assert!(textarea.reverse_lines());
+    /// assert_eq!(textarea.lines(), ["c", "b", "a"]);
+    /// ```
+    pub fn reverse_lines(&mut self) -> bool {
+        self.transform_lines(|lines| lines.reverse())
+    }
+
+    /// Remove duplicate lines in the lines touched by the selection. The first occurrence of each line is kept. When no
+    /// text is selected, duplicates in all lines are removed. After removing, the remaining lines are selected. This
+    /// method returns if some line was removed or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "a", "c", "b"]);
+    ///
+    /// assert!(textarea.dedup_lines());
+    /// assert_eq!(textarea.lines(), ["a", "b", "c"]);
+    /// ```
+    pub fn dedup_lines(&mut self) -> bool {
+        self.transform_lines(|lines| {
+            let mut seen = HashSet::new();
+            lines.retain(|line| seen.insert(line.clone()));
+        })
+    }
+
+    fn transform_lines(&mut self, f: impl FnOnce(&mut Vec<String>)) -> bool {
+        let selected = self.selection_start.is_some();
+        let (first, last) = if selected {
+            self.selected_rows()
+        } else {
+            (0, self.lines.len() - 1)
+        };
+        let mut lines = self.lines[first..=last].to_vec();
+        f(&mut lines);
+        let new_last = first + lines.len() - 1;
+
+        let (row, col) = self.cursor;
+        let selection = self.selection_start.take();
+        if !self.replace_rows(first, last, lines) {
+            self.selection_start = selection;
+            return false;
+        }
+
+        if selected {
+            self.selection_start = Some((first, 0));
+            self.cursor = (new_last, self.lines[new_last].chars().count());
+        } else {
+            let row = cmp::min(row, self.lines.len() - 1);
+            let col = cmp::min(col, self.lines[row].chars().count());
+            self.cursor = (row, col);
+        }
+        true
+    }
+
+    // Replace the lines from `first` to `last` with the new lines as one edit. The new lines are inserted before the
+    // old lines are deleted so that undoing the edit puts the cursor at the head of the first line
+    fn replace_rows(&mut self, first: usize, last: usize, mut lines: Vec<String>) -> bool {
+        debug_assert!(!lines.is_empty(), "Lines to replace must not be empty");
+        if self.lines[first..=last] == lines[..] {
+            return false;
+        }
+
+        let n = lines.len();
+        let tail = &lines[n - 1];
+        let inserted_end = Pos::new(first + n - 1, tail.chars().count(), tail.len());
+        lines.push(String::new());
+
+        self.history.begin_group();
+        self.cursor = (first, 0);
+        self.insert_chunk(lines);
+        let old_last = last + n;
+        let line = &self.lines[old_last];
+        let old_end = Pos::new(old_last, line.chars().count(), line.len());
+        self.delete_range(inserted_end, old_end);
+        self.history.end_group();
+        true
+    }
+
+    /// Toggle the line comment of the lines touched by the selection or the cursor line. When all non-blank lines are
+    /// already commented, they are uncommented. Otherwise the comment token followed by a space is inserted at the
+    /// minimum indentation of the non-blank lines so that the markers are aligned. Blank lines are left as-is. The
+    /// token is set by [`TextArea::set_line_comment`]. All lines are toggled in one edit so a single undo reverts them.
+    /// This method returns if some line was modified or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["fn f() {", "    g();", "", "}"]);
+    ///
+    /// textarea.select_all();
+    /// assert!(textarea.toggle_line_comment());
+    /// assert_eq!(textarea.lines(), ["// fn f() {", "//     g();", "", "// }"]);
+    /// assert!(textarea.toggle_line_comment());
+    /// assert_eq!(textarea.lines(), ["fn f() {", "    g();", "", "}"]);
+    /// ```
+    pub fn toggle_line_comment(&mut self) -> bool {
+        let token = match &self.line_comment {
+            Some(token) if !token.is_empty() => token.clone(),
+            _ => return false,
+        };
+        let (first, last) = self.selected_rows();
+        let rows: Vec<usize> = (first..=last)
+            .filter(|&row| !is_blank(&self.lines[row]))
+            .collect();
+        if rows.is_empty() {
+            return false;
+        }
+        let commented = rows.iter().all(|&row| {
+            let line = &self.lines[row];
+            line[leading_whitespace(line).len()..].starts_with(&token)
+        });
+
+        let mut cursor = self.cursor;
+        let mut selection = self.selection_start.take();
+        self.history.begin_group();
+        if commented {
+            for row in rows {
+                let line = &self.lines[row];
+                // Leading whitespaces are ASCII so the number of characters is the same as the byte length
+                let start = leading_whitespace(line).len();
+                let mut end = start + token.len();
+                if line[end..].starts_with(' ') {
+                    end += 1;
+                }
+                let chars = line[start..end].chars().count();
+                self.delete_range(
+                    Pos::new(row, start, start),
+                    Pos::new(row, start + chars, end),
+                );
+                for pos in iter::once(&mut cursor).chain(selection.as_mut()) {
+                    shift_col_on_delete(pos, row, start, chars);
+                }
+            }
+        } else {
+            // Align the markers by display width since the indentation may mix tabs and spaces
+            let width = rows
+                .iter()
+                .map(|&row| indent_width(leading_whitespace(&self.lines[row]), self.tab_len))
+                .min()
+                .unwrap_or(0);
+            let marker = format!("{} ", token);
+            let chars = marker.chars().count();
+            for row in rows {
+                let col = col_at_display_width(&self.lines[row], width, self.tab_len, None);
+                self.cursor = (row, col);
+                self.insert_piece(marker.clone());
+                for pos in iter::once(&mut cursor).chain(selection.as_mut()) {
+                    shift_col_on_insert(pos, row, col, chars);
+                }
+            }
+        }
+        self.history.end_group();
+
+        self.cursor = cursor;
+        self.selection_start = selection;
+        true
+    }
+
+    /// Toggle the block comment around the lines touched by the selection or the cursor line. When the first non-blank
+    /// line starts with the opening token and the last non-blank line ends with the closing token, the tokens are
+    /// removed. Otherwise the opening token followed by a space is inserted at the indentation of the first line and a
+    /// space followed by the closing token is appended to the last line. The tokens are set by
+    /// [`TextArea::set_block_comment`]. The toggle is recorded as one edit in history. This method returns if some
+    /// line was modified or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["  a = 1;", "  b = 2;"]);
+    ///
+    /// textarea.select_all();
+    /// assert!(textarea.toggle_block_comment());
+    /// assert_eq!(textarea.lines(), ["  /* a = 1;", "  b = 2; */"]);
+    /// assert!(textarea.toggle_block_comment());
+    /// assert_eq!(textarea.lines(), ["  a = 1;", "  b = 2;"]);
+    /// ```
+    pub fn toggle_block_comment(&mut self) -> bool {
+        let (open, close) = match &self.block_comment {
+            Some((open, close)) if !open.is_empty() && !close.is_empty() => {
+                (open.clone(), close.clone())
+            }
+            _ => return false,
+        };
+        let (first, last) = self.selected_rows();
+        let (first, last) = match (
+            (first..=last).find(|&row| !is_blank(&self.lines[row])),
+            (first..=last)
+                .rev()
+                .find(|&row| !is_blank(&self.lines[row])),
+        ) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return false,
+        };
+
+        let head = leading_whitespace(&self.lines[first]).len();
+        let tail = self.lines[last]
+            .trim_end_matches(|c| c == ' ' || c == '\t')
+            .len();
+        let commented = self.lines[first][head..].starts_with(&open)
+            && self.lines[last][..tail].ends_with(&close)
+            && (first != last || head + open.len() + close.len() <= tail);
+
+        let mut cursor = self.cursor;
+        let mut selection = self.selection_start.take();
+        self.history.begin_group();
+        if commented {
+            // Remove the closing token first so that the offset of the opening token is not changed
+            let line = &self.lines[last];
+            let mut start = tail - close.len();
+            if line[..start].ends_with(' ') && (first != last || start > head + open.len()) {
+                start -= 1;
+            }
+            let col = line[..start].chars().count();
+            let chars = line[start..tail].chars().count();
+            self.delete_range(
+                Pos::new(last, col, start),
+                Pos::new(last, col + chars, tail),
+            );
+            for pos in iter::once(&mut cursor).chain(selection.as_mut()) {
+                shift_col_on_delete(pos, last, col, chars);
+            }
+
+            let line = &self.lines[first];
+            let mut end = head + open.len();
+            if line[end..].starts_with(' ') {
+                end += 1;
+            }
+            let chars = line[head..end].chars().count();
+            self.delete_range(
+                Pos::new(first, head, head),
+                Pos::new(first, head + chars, end),
+            );
+            for pos in iter::once(&mut cursor).chain(selection.as_mut()) {
+                shift_col_on_delete(pos, first, head, chars);
+            }
+        } else {
+            let col = self.lines[last][..tail].chars().count();
+            let marker = format!(" {}", close);
+            let chars = marker.chars().count();
+            self.cursor = (last, col);
+            self.insert_piece(marker);
+            for pos in iter::once(&mut cursor).chain(selection.as_mut()) {
+                shift_col_on_insert(pos, last, col, chars);
+            }
+
+            let marker = format!("{} ", open);
+            let chars = marker.chars().count();
+            self.cursor = (first, head);
+            self.insert_piece(marker);
+            for pos in iter::once(&mut cursor).chain(selection.as_mut()) {
+                shift_col_on_insert(pos, first, head, chars);
+            }
+        }
+        self.history.end_group();
+
+        self.cursor = cursor;
+        self.selection_start = selection;
+        true
+    }
+
+    /// Reflow the paragraph at the cursor so that each line is no longer than the fill column set by
+    /// [`TextArea::set_fill_column`], like Vim's `gq` or Emacs' `fill-paragraph`. A paragraph is consecutive non-blank
+    /// lines. Words in the paragraph are joined with one space and broken into lines at the fill column. A word longer
+    /// than the fill column is put on its own line.
+    ///
+    /// The indentation and the line comment token set by [`TextArea::set_line_comment`] are kept as the prefix of each
+    /// line. The first line keeps its own prefix and the following lines use the prefix of the second line so that
+    /// hanging indentation is preserved. Lines with different comment prefixes belong to different paragraphs.
+    ///
+    /// When text is selected, all paragraphs in the selected lines are reflowed and the result is selected. Otherwise
+    /// the cursor stays at the same character. The reflow is recorded as one edit in history. This method returns if
+    /// some line was modified or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from([
+    ///     "  // Lorem ipsum dolor sit amet,",
+    ///     "  // consectetur adipiscing elit",
+    /// ]);
+    ///
+    /// textarea.set_fill_column(24);
+    /// assert!(textarea.reflow_paragraph());
+    /// assert_eq!(textarea.lines(), [
+    ///     "  // Lorem ipsum dolor",
+    ///     "  // sit amet,",
+    ///     "  // consectetur",
+    ///     "  // adipiscing elit",
+    /// ]);
+    /// ```
+    pub fn reflow_paragraph(&mut self) -> bool {
+        let comment = self.line_comment.clone();
+        let comment = comment.as_deref();
+        let (width, tab_len) = (self.fill_column, self.tab_len);
+        if self.selection_start.is_some() {
+            return self.transform_lines(|lines| *lines = reflow(lines, width, tab_len, comment));
+        }
+
+        let (row, col) = self.cursor;
+        let (first, last) = match paragraph_rows(&self.lines, row, comment) {
+            Some(rows) => rows,
+            None => return false,
+        };
+        let lines = reflow(&self.lines[first..=last], width, tab_len, comment);
+        let offset = body_offset(&self.lines[first..=last], (row - first, col), comment);
+        let new_last = first + lines.len() - 1;
+        if !self.replace_rows(first, last, lines) {
+            return false;
+        }
+        let (row, col) = body_position(&self.lines[first..=new_last], offset, comment);
+        self.cursor = (first + row, col);
+        true
+    }
+
+    /// Remove spaces and tabs at the end of the lines touched by the selection. When no text is selected, all lines
+    /// are trimmed. The cursor and the selection stay at the same text. The cleanup is recorded as one edit in history.
+    /// This method returns if some line was modified or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a  ", "\tb\t", "c"]);
+    ///
+    /// assert!(textarea.trim_trailing_whitespace());
+    /// assert_eq!(textarea.lines(), ["a", "\tb", "c"]);
+    /// assert!(!textarea.trim_trailing_whitespace());
+    /// ```
+    pub fn trim_trailing_whitespace(&mut self) -> bool {
+        let (first, last) = self.cleanup_rows();
+        self.clean_rows(first, last, trim_trailing)
+    }
+
+    /// Rewrite the indentation of the lines touched by the selection keeping its display width. When
+    /// [`TextArea::hard_tab_indent`] is enabled, leading spaces are converted to tabs as much as possible. Otherwise
+    /// leading tabs are converted to spaces. The width of a tab is [`TextArea::tab_length`]. When no text is selected,
+    /// all lines are converted. The cursor and the selection stay at the same text. The conversion is recorded as one
+    /// edit in history. This method returns if some line was modified or not. It does nothing when the tab length is 0.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["\tfoo", "\t  bar"]);
+    ///
+    /// assert!(textarea.normalize_indent());
+    /// assert_eq!(textarea.lines(), ["    foo", "      bar"]);
+    ///
+    /// textarea.set_hard_tab_indent(true);
+    /// assert!(textarea.normalize_indent());
+    /// assert_eq!(textarea.lines(), ["\tfoo", "\t  bar"]);
+    /// ```
+    pub fn normalize_indent(&mut self) -> bool {
+        if self.tab_len == 0 {
+            return false;
+        }
+        let (tab_len, hard_tab) = (self.tab_len, self.hard_tab_indent);
+        let (first, last) = self.cleanup_rows();
+        self.clean_rows(first, last, |lines, positions| {
+            retab(lines, tab_len, hard_tab, positions)
+        })
+    }
+
+    /// Replace each run of consecutive blank lines in the lines touched by the selection with one blank line. When no
+    /// text is selected, all lines are processed. The cursor and the selection stay at the same text. The cleanup is
+    /// recorded as one edit in history. This method returns if some line was removed or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "", "", "b", "", "  ", "c"]);
+    ///
+    /// assert!(textarea.collapse_blank_lines());
+    /// assert_eq!(textarea.lines(), ["a", "", "b", "", "c"]);
+    /// ```
+    pub fn collapse_blank_lines(&mut self) -> bool {
+        let (first, last) = self.cleanup_rows();
+        self.clean_rows(first, last, collapse_blank)
+    }
+
+    /// Make the text end with exactly one newline when it is saved with a newline after each line of
+    /// [`TextArea::lines`]. Blank lines at the end of the text are removed so that the last line is not blank. Blank
+    /// text becomes empty. This method always processes the whole text regardless of the selection. The cursor and the
+    /// selection stay at the same text or move to the end of the text. The cleanup is recorded as one edit in history.
+    /// This method returns if some line was modified or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a", "", "  ", ""]);
+    ///
+    /// assert!(textarea.ensure_final_newline());
+    /// assert_eq!(textarea.lines(), ["a"]);
+    /// assert!(!textarea.ensure_final_newline());
+    ///
+    /// // The text is saved as "a\n"
+    /// let saved: String = textarea.lines().iter().map(|l| format!("{}\n", l)).collect();
+    /// assert_eq!(saved, "a\n");
+    /// ```
+    pub fn ensure_final_newline(&mut self) -> bool {
+        let last = self.lines.len() - 1;
+        let first = (0..=last)
+            .rev()
+            .find(|&row| !is_blank(&self.lines[row]))
+            .unwrap_or(0);
+        self.clean_rows(first, last, final_newline)
+    }
+
+    fn cleanup_rows(&self) -> (usize, usize) {
+        if self.selection_start.is_some() {
+            self.selected_rows()
+        } else {
+            (0, self.lines.len() - 1)
+        }
+    }
+
+    // Replace the lines from `first` to `last` with the lines cleaned up by `f` as one edit. `f` moves the positions of
+    // the cursor and the selection in the lines so that they stay at the same text after the cleanup
+    fn clean_rows<F>(&mut self, first: usize, last: usize, f: F) -> bool
+    where
+        F: FnOnce(&[String], &mut [(usize, usize)]) -> Vec<String>,
+    {
+        let mut positions: Vec<(usize, usize)> =
+            iter::once(self.cursor).chain(self.selection_start).collect();
+        let mut inner: Vec<(usize, usize)> = positions
+            .iter()
+            .filter(|(row, _)| first <= *row && *row <= last)
+            .map(|&(row, col)| (row - first, col))
+            .collect();
+        let lines = f(&self.lines[first..=last], &mut inner);
+        let (old_len, new_len) = (last - first + 1, lines.len());
+
+        let selection = self.selection_start.take();
+        if !self.replace_rows(first, last, lines) {
+            self.selection_start = selection;
+            return false;
+        }
+
+        let mut inner = inner.into_iter();
+        for pos in positions.iter_mut() {
+            if pos.0 > last {
+                pos.0 = pos.0 - old_len + new_len;
+            } else if pos.0 >= first {
+                if let Some((row, col)) = inner.next() {
+                    *pos = (first + row, col);
+                }
+            }
+        }
+        self.cursor = positions[0];
+        self.selection_start = positions.get(1).copied();
+        true
+    }
+
     /// Insert a newline at current cursor position.
     /// ```
     /// use tui_textarea::{TextArea, CursorMove};
@@ -1103,6 +1947,42 @@ impl<'a> TextArea<'a> {
     pub fn insert_newline(&mut self) {
         self.delete_selection(false);
 
+        let indent = if self.auto_indent {
+            self.newline_indent()
+        } else {
+            String::new()
+        };
+        if indent.is_empty() {
+            self.split_line();
+            return;
+        }
+
+        self.history.begin_group();
+        self.split_line();
+        self.insert_piece(indent);
+        self.history.end_group();
+    }
+
+    // Indentation of the next line on auto-indent. The leading whitespaces before the cursor are copied and one more
+    // level is added when the indent rule says so
+    fn newline_indent(&self) -> String {
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        let offset = line
+            .char_indices()
+            .nth(col)
+            .map(|(i, _)| i)
+            .unwrap_or(line.len());
+        let before = &line[..offset];
+
+        let mut width = indent_width(leading_whitespace(before), self.tab_len);
+        if self.indenter.get().map_or(false, |r| r.indent_after(before)) {
+            width += self.tab_len as usize;
+        }
+        indent_string(width, self.tab_len, self.hard_tab_indent)
+    }
+
+    fn split_line(&mut self) {
         let (row, col) = self.cursor;
         let line = &mut self.lines[row];
         let offset = line
@@ -1171,8 +2051,27 @@ impl<'a> TextArea<'a> {
             return self.delete_newline();
         }
 
+        if let Some((start, end)) = self.empty_pair_around_cursor() {
+            self.delete_range(Pos::new(row, col - 1, start), Pos::new(row, col + 1, end));
+            return true;
+        }
+
+        let spaces = self.spaces_to_tab_stop();
+        if spaces > 1 {
+            // Leading whitespaces are ASCII so the number of characters is the same as the byte length
+            let start = Pos::new(row, col - spaces, col - spaces);
+            self.delete_range(start, Pos::new(row, col, col));
+            return true;
+        }
+
+        self.delete_char_at_cursor()
+    }
+
+    // Delete the character before the cursor in the line without deleting auto pairs or spaces to the tab stop
+    fn delete_char_at_cursor(&mut self) -> bool {
+        let (row, col) = self.cursor;
         let line = &mut self.lines[row];
-        if let Some((offset, c)) = line.char_indices().nth(col - 1) {
+        if let Some((offset, c)) = col.checked_sub(1).and_then(|i| line.char_indices().nth(i)) {
             line.remove(offset);
             self.cursor.1 -= 1;
             self.push_history(
@@ -1186,6 +2085,29 @@ impl<'a> TextArea<'a> {
         }
     }
 
+    // Number of spaces before the cursor which smart backspace deletes to go back to the previous tab stop. 0 is
+    // returned when smart backspace is disabled or the text before the cursor is not only whitespaces
+    fn spaces_to_tab_stop(&self) -> usize {
+        if !self.smart_backspace || self.tab_len == 0 {
+            return 0;
+        }
+        let (row, col) = self.cursor;
+        let line = &self.lines[row];
+        if leading_whitespace(line).len() < col {
+            return 0;
+        }
+
+        let tab_len = self.tab_len as usize;
+        let width = display_width(line, col, self.tab_len, None);
+        let stop = width.saturating_sub(1) / tab_len * tab_len;
+        line[..col]
+            .chars()
+            .rev()
+            .take_while(|&c| c == ' ')
+            .take(width - stop)
+            .count()
+    }
+
     /// Delete one character next to cursor. When the cursor is at end of line, the newline next to the cursor will be
     /// removed. This method returns if a character was deleted or not in the textarea.
     /// ```
@@ -1342,8 +2264,80 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), [" bbb cccaaa"]);
     /// ```
     pub fn paste(&mut self) -> bool {
+        self.receive_clipboard();
+        self.history.begin_group();
+        self.delete_selection(false);
+        let start = self.cursor;
+        let inserted = self.insert_yank(self.kill_ring.top());
+        self.history.end_group();
+        if inserted {
+            self.kill_ring.yanked(start, self.cursor, 0);
+        }
+        inserted
+    }
+
+    /// Replace the text just pasted by [`TextArea::paste`] with the previous text in the kill ring like Emacs'
+    /// `yank-pop`. Calling this method repeatedly cycles through older texts. The kill ring remembers the last 32
+    /// yanked texts. This method does nothing and returns `false` when the last operation was not pasting.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["foo bar"]);
+    /// textarea.delete_next_word(); // Kill "foo"
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.delete_word(); // Kill "bar"
+    ///
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), [" bar"]);
+    /// assert!(textarea.yank_pop());
+    /// assert_eq!(textarea.lines(), [" foo"]);
+    /// assert!(textarea.yank_pop());
+    /// assert_eq!(textarea.lines(), [" bar"]);
+    ///
+    /// textarea.move_cursor(CursorMove::Head);
+    /// assert!(!textarea.yank_pop());
+    /// ```
+    pub fn yank_pop(&mut self) -> bool {
+        let (start, index, text) = match self.kill_ring.pop(self.cursor) {
+            Some(popped) => popped,
+            None => return false,
+        };
+        self.cancel_selection();
+        self.selection_start = Some(start);
+        // Replacing the pasted text is one edit so that a single undo reverts the yank-pop
+        self.history.begin_group();
         self.delete_selection(false);
-        match self.yank.clone() {
+        self.insert_yank(text);
+        self.history.end_group();
+        self.kill_ring.yanked(start, self.cursor, index);
+        true
+    }
+
+    // Push the yanked text to the kill ring and write it to the clipboard
+    fn push_kill(&mut self, text: YankText) {
+        self.kill_ring.push(text);
+        self.sync_clipboard();
+    }
+
+    fn sync_clipboard(&self) {
+        if let Some(clipboard) = self.clipboard.get() {
+            clipboard.set_text(&self.kill_ring.top().to_string());
+        }
+    }
+
+    // When the clipboard has text which was not yanked in this textarea, push it to the kill ring
+    fn receive_clipboard(&mut self) {
+        let text = match self.clipboard.get().and_then(|c| c.get_text()) {
+            Some(text) => text,
+            None => return,
+        };
+        if text != self.kill_ring.top().to_string() {
+            self.kill_ring.push(YankText::parse(&text));
+        }
+    }
+
+    fn insert_yank(&mut self, text: YankText) -> bool {
+        match text {
             YankText::Piece(s) => self.insert_piece(s),
             YankText::Chunk(c) => self.insert_chunk(c),
         }
@@ -1362,6 +2356,7 @@ impl<'a> TextArea<'a> {
     /// ```
     pub fn start_selection(&mut self) {
         self.selection_start = Some(self.cursor);
+        self.rect_selection = false;
     }
 
     /// Stop the current text selection. This method does nothing if text selection is not ongoing.
@@ -1382,6 +2377,7 @@ impl<'a> TextArea<'a> {
     /// ```
     pub fn cancel_selection(&mut self) {
         self.selection_start = None;
+        self.rect_selection = false;
     }
 
     /// Select the entire text. Cursor moves to the end of the text buffer. When text selection is already ongoing,
@@ -1402,6 +2398,7 @@ impl<'a> TextArea<'a> {
     pub fn select_all(&mut self) {
         self.move_cursor(CursorMove::Jump(u16::MAX, u16::MAX));
         self.selection_start = Some((0, 0));
+        self.rect_selection = false;
     }
 
     /// Return if text selection is ongoing or not.
@@ -1472,6 +2469,40 @@ impl<'a> TextArea<'a> {
         }
     }
 
+    /// Positions of the text selected in each row of the rectangular selection. The rectangle is decided by display
+    /// widths so that tabs and wide characters are aligned as rendered.
+    pub(crate) fn rect_selection_positions(&self) -> Option<Vec<(Pos, Pos)>> {
+        if !self.rect_selection {
+            return None;
+        }
+        let (start_row, start_col) = self.selection_start?;
+        let (end_row, end_col) = self.cursor();
+        let (tab_len, mask) = (self.tab_length(), self.mask_char());
+        let start_x = display_width(&self.lines()[start_row], start_col, tab_len, mask);
+        let end_x = display_width(&self.lines()[end_row], end_col, tab_len, mask);
+        let (left, right) = (start_x.min(end_x), start_x.max(end_x));
+
+        let positions = (start_row.min(end_row)..=start_row.max(end_row))
+            .map(|row| {
+                let line = &self.lines()[row];
+                let pos = |x| {
+                    let col = col_at_display_width(line, x, tab_len, mask);
+                    let offset = line.char_indices().nth(col).map(|(i, _)| i).unwrap_or(line.len());
+                    Pos::new(row, col, offset)
+                };
+                (pos(left), pos(right))
+            })
+            .collect();
+        Some(positions)
+    }
+
+    // Move the cursor to the position without touching the text selection. The position is clamped in the text.
+    pub(crate) fn set_cursor(&mut self, (row, col): (usize, usize)) {
+        let row = row.min(self.lines.len() - 1);
+        let col = col.min(self.lines[row].chars().count());
+        self.cursor = (row, col);
+    }
+
     fn take_selection_positions(&mut self) -> Option<(Pos, Pos)> {
         let range = self.selection_positions();
         self.cancel_selection();
@@ -1497,18 +2528,180 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["Hello World"]); // Text does not change
     /// ```
     pub fn copy(&mut self) {
-        if let Some((start, end)) = self.take_selection_positions() {
-            if start.row == end.row {
-                self.yank = self.lines[start.row][start.offset..end.offset]
-                    .to_string()
-                    .into();
-                return;
+        if let Some(text) = self.take_selection_text() {
+            self.push_kill(text);
+        }
+    }
+
+    /// Replace the selected text with the result of the function applied to it. When no text is selected, the word at
+    /// the cursor ([`TextObject::InnerWord`]) is transformed. The transformed text is selected afterwards even when its
+    /// length changed. With rectangular selection, the function is applied to the text in each row. The replacement is
+    /// recorded as one edit in history. This method returns if the text was modified or not.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["hello world"]);
+    ///
+    /// // Transform the word at the cursor
+    /// assert!(textarea.transform_selection(|s| s.repeat(2)));
+    /// assert_eq!(textarea.lines(), ["hellohello world"]);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 0), (0, 10))));
+    ///
+    /// textarea.move_cursor(CursorMove::End);
+    /// assert!(textarea.transform_selection(|s| s.replace('o', "0")));
+    /// assert_eq!(textarea.lines(), ["hell0hell0 w0rld"]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["hellohello world"]);
+    /// ```
+    pub fn transform_selection<F: Fn(&str) -> String>(&mut self, f: F) -> bool {
+        if let Some(positions) = self.rect_selection_positions() {
+            return self.transform_rect(positions, &f);
+        }
+        match self.selection_positions() {
+            Some((start, end)) => {
+                let forward = self.cursor == (end.row, end.col);
+                self.transform_range(start, end, &f, forward)
+            }
+            None => self.transform_text_object(TextObject::InnerWord, f),
+        }
+    }
+
+    /// Replace the text object at the cursor with the result of the function applied to it. The transformed text is
+    /// selected afterwards. See [`TextObject`] for each text object. This method returns if the text was modified or
+    /// not.
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea, TextObject};
+    ///
+    /// let mut textarea = TextArea::from([r#"print("hello")"#]);
+    /// textarea.move_cursor(CursorMove::Jump(0, 8));
+    ///
+    /// assert!(textarea.transform_text_object(TextObject::InnerQuote('"'), |s| s.chars().rev().collect()));
+    /// assert_eq!(textarea.lines(), [r#"print("olleh")"#]);
+    /// assert_eq!(textarea.selection_range(), Some(((0, 7), (0, 12))));
+    /// ```
+    pub fn transform_text_object<F: Fn(&str) -> String>(
+        &mut self,
+        object: TextObject,
+        f: F,
+    ) -> bool {
+        match self.text_object_range(object) {
+            Some(((sr, sc), (er, ec))) => {
+                let start = Pos::new(sr, sc, self.line_offset(sr, sc));
+                let end = Pos::new(er, ec, self.line_offset(er, ec));
+                self.transform_range(start, end, &f, true)
+            }
+            None => false,
+        }
+    }
+
+    /// Convert the case of the selected text or the word at the cursor. See [`Case`] for each conversion and
+    /// [`TextArea::transform_selection`] for how the text is replaced.
+    /// ```
+    /// use tui_textarea::{Case, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["userName = 1"]);
+    ///
+    /// assert!(textarea.convert_case(Case::Snake));
+    /// assert_eq!(textarea.lines(), ["user_name = 1"]);
+    /// assert!(textarea.convert_case(Case::Upper));
+    /// assert_eq!(textarea.lines(), ["USER_NAME = 1"]);
+    /// assert!(!textarea.convert_case(Case::Upper));
+    /// ```
+    pub fn convert_case(&mut self, case: Case) -> bool {
+        self.transform_selection(|s| case.convert(s))
+    }
+
+    fn transform_range(
+        &mut self,
+        start: Pos,
+        end: Pos,
+        f: &dyn Fn(&str) -> String,
+        forward: bool,
+    ) -> bool {
+        let text = if start.row == end.row {
+            self.lines[start.row][start.offset..end.offset].to_string()
+        } else {
+            let mut text = self.lines[start.row][start.offset..].to_string();
+            for line in &self.lines[start.row + 1..end.row] {
+                text.push('\n');
+                text.push_str(line);
+            }
+            text.push('\n');
+            text.push_str(&self.lines[end.row][..end.offset]);
+            text
+        };
+        let transformed = f(&text);
+        if transformed == text {
+            return false;
+        }
+
+        self.cancel_selection();
+        let head = (start.row, start.col);
+        self.history.begin_group();
+        self.delete_range(start, end);
+        self.insert_str(transformed);
+        self.history.end_group();
+
+        let tail = self.cursor;
+        let (anchor, cursor) = if forward { (head, tail) } else { (tail, head) };
+        self.selection_start = Some(anchor);
+        self.cursor = cursor;
+        true
+    }
+
+    fn transform_rect(&mut self, positions: Vec<(Pos, Pos)>, f: &dyn Fn(&str) -> String) -> bool {
+        let cursor = self.cursor;
+        let mut modified = false;
+        self.history.begin_group();
+        for (s, e) in positions {
+            let text = &self.lines[s.row][s.offset..e.offset];
+            let transformed = f(text);
+            if transformed == text || transformed.contains('\n') {
+                continue;
             }
-            let mut chunk = vec![self.lines[start.row][start.offset..].to_string()];
-            chunk.extend(self.lines[start.row + 1..end.row].iter().cloned());
-            chunk.push(self.lines[end.row][..end.offset].to_string());
-            self.yank = YankText::Chunk(chunk);
+            let removed = self.lines[s.row].drain(s.offset..e.offset).as_str().to_string();
+            self.cursor = (s.row, s.col);
+            self.push_history(EditKind::DeleteStr(removed), e, s.offset);
+            self.insert_piece(transformed);
+            modified = true;
+        }
+        self.history.end_group();
+
+        // Keep the rectangle. Its columns are clamped since the lengths of lines may have changed
+        let clamp = |(row, col): (usize, usize), lines: &[String]| {
+            (row, cmp::min(col, lines[row].chars().count()))
+        };
+        if let Some(pos) = self.selection_start {
+            self.selection_start = Some(clamp(pos, &self.lines));
+        }
+        self.cursor = clamp(cursor, &self.lines);
+        modified
+    }
+
+    // Get the selected text and cancel the selection
+    fn take_selection_text(&mut self) -> Option<YankText> {
+        if let Some(positions) = self.rect_selection_positions() {
+            self.cancel_selection();
+            let chunk: Vec<_> = positions
+                .iter()
+                .map(|(s, e)| self.lines[s.row][s.offset..e.offset].to_string())
+                .collect();
+            return Some(chunk.into());
+        }
+
+        let (start, end) = self.take_selection_positions()?;
+        if start.row == end.row {
+            return Some(
+                self.lines[start.row][start.offset..end.offset]
+                    .to_string()
+                    .into(),
+            );
         }
+        let mut chunk = vec![self.lines[start.row][start.offset..].to_string()];
+        chunk.extend(self.lines[start.row + 1..end.row].iter().cloned());
+        chunk.push(self.lines[end.row][..end.offset].to_string());
+        Some(YankText::Chunk(chunk))
     }
 
     /// Cut the selected text and place it in the yank buffer. This method returns whether the text was modified.
@@ -1535,11 +2728,50 @@ impl<'a> TextArea<'a> {
     }
 
     fn delete_selection(&mut self, should_yank: bool) -> bool {
-        if let Some((s, e)) = self.take_selection_positions() {
-            self.delete_range(s, e, should_yank);
-            return true;
+        match self.take_deleted_selection() {
+            Some(removed) => {
+                if should_yank {
+                    self.push_kill(removed);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Delete the selected text and return it. `None` is returned when no text was deleted
+    fn take_deleted_selection(&mut self) -> Option<YankText> {
+        if let Some(positions) = self.rect_selection_positions() {
+            self.cancel_selection();
+            return self.delete_rect(positions);
         }
-        false
+
+        let (s, e) = self.take_selection_positions()?;
+        Some(self.delete_range(s, e))
+    }
+
+    // Delete the text in each row of the rectangular selection. Edits of all rows are grouped in history so that a
+    // single undo restores them
+    fn delete_rect(&mut self, positions: Vec<(Pos, Pos)>) -> Option<YankText> {
+        let mut removed = Vec::with_capacity(positions.len());
+        self.history.begin_group();
+        for (s, e) in positions.iter().rev() {
+            let text = self.lines[s.row].drain(s.offset..e.offset).as_str().to_string();
+            if !text.is_empty() {
+                self.cursor = (s.row, s.col);
+                self.push_history(EditKind::DeleteStr(text.clone()), e.clone(), s.offset);
+            }
+            removed.push(text);
+        }
+        self.history.end_group();
+        if let Some((s, _)) = positions.first() {
+            self.cursor = (s.row, s.col);
+        }
+        let modified = removed.iter().any(|s| !s.is_empty());
+        modified.then(|| {
+            removed.reverse();
+            removed.into()
+        })
     }
 
     /// Move the cursor to the position specified by the [`CursorMove`] parameter. For each kind of cursor moves, see
@@ -1559,6 +2791,8 @@ impl<'a> TextArea<'a> {
     }
 
     fn move_cursor_with_shift(&mut self, m: CursorMove, shift: bool) {
+        self.kill_ring.reset_last();
+        self.overwritten.clear();
         use crate::cursor::CursorMove;
         
         // Handle visual movement for wrapped text
@@ -1585,6 +2819,13 @@ impl<'a> TextArea<'a> {
                 // Fall back to logical movement
                 self.move_cursor_with_shift(CursorMove::Down, shift);
             }
+            CursorMove::MatchingBracket => {
+                if let Some((_, cursor)) =
+                    find_matching_bracket(&self.lines, self.cursor, &self.bracket_quotes)
+                {
+                    self.handle_cursor_change(cursor, shift);
+                }
+            }
             _ => {
                 // For all other movements, use the existing logic
                 if let Some(cursor) = m.next_cursor(self.cursor, &self.lines, &self.viewport) {
@@ -1666,6 +2907,8 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["abc def"]);
     /// ```
     pub fn undo(&mut self) -> bool {
+        self.kill_ring.reset_last();
+        self.overwritten.clear();
         if let Some(cursor) = self.history.undo(&mut self.lines) {
             self.cancel_selection();
             self.cursor = cursor;
@@ -1689,6 +2932,8 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), [" def"]);
     /// ```
     pub fn redo(&mut self) -> bool {
+        self.kill_ring.reset_last();
+        self.overwritten.clear();
         if let Some(cursor) = self.history.redo(&mut self.lines) {
             self.cancel_selection();
             self.cursor = cursor;
@@ -1698,10 +2943,23 @@ impl<'a> TextArea<'a> {
         }
     }
 
-    pub(crate) fn line_spans<'b>(&'b self, line: &'b str, row: usize, lnum_len: u8) -> Line<'b> {
+    // Positions of the bracket under the cursor and its matching bracket to highlight. This is searched once per
+    // rendering and passed to `line_spans` since the search may scan many lines
+    pub(crate) fn matching_brackets(&self) -> Option<((usize, usize), (usize, usize))> {
+        self.matching_bracket_style?;
+        find_matching_bracket(&self.lines, self.cursor, &self.bracket_quotes)
+    }
+
+    pub(crate) fn line_spans<'b>(
+        &'b self,
+        line: &'b str,
+        row: usize,
+        lnum_len: u8,
+        brackets: Option<((usize, usize), (usize, usize))>,
+    ) -> Line<'b> {
         let mut hl = LineHighlighter::new(
             line,
-            self.cursor_style,
+            self.current_cursor_style(),
             self.tab_len,
             self.mask,
             self.select_style,
@@ -1715,16 +2973,35 @@ impl<'a> TextArea<'a> {
             hl.cursor_line(self.cursor.1, self.cursor_line_style);
         }
 
+        if let (Some(style), Some((from, to))) = (self.matching_bracket_style, brackets) {
+            for (r, c) in [from, to] {
+                if r == row {
+                    hl.matching_bracket(c, style);
+                }
+            }
+        }
+
         #[cfg(feature = "search")]
         if let Some(matches) = self.search.matches(line) {
             hl.search(matches, self.search.style);
         }
 
+        self.highlight_selection(&mut hl, row);
+
+        hl.into_spans()
+    }
+
+    fn highlight_selection(&self, hl: &mut LineHighlighter, row: usize) {
+        if let Some(positions) = self.rect_selection_positions() {
+            if let Some((s, e)) = positions.iter().find(|(s, _)| s.row == row) {
+                hl.selection(row, row, s.offset, row, e.offset);
+            }
+            return;
+        }
+
         if let Some((start, end)) = self.selection_positions() {
             hl.selection(row, start.row, start.offset, end.row, end.offset);
         }
-
-        hl.into_spans()
     }
 
     /// Build a ratatui (or tui-rs) widget to render the current state of the textarea. The widget instance returned
@@ -1785,91 +3062,282 @@ impl<'a> TextArea<'a> {
         self.style
     }
 
-    /// Set the block of textarea. By default, no block is set.
+    /// Set the block of textarea. By default, no block is set.
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::widgets::{Block, Borders};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
+    /// textarea.set_block(block);
+    /// assert!(textarea.block().is_some());
+    /// ```
+    pub fn set_block(&mut self, block: Block<'a>) {
+        self.block = Some(block);
+    }
+
+    /// Remove the block of textarea which was set by [`TextArea::set_block`].
+    /// ```
+    /// use tui_textarea::TextArea;
+    /// use ratatui::widgets::{Block, Borders};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
+    /// textarea.set_block(block);
+    /// textarea.remove_block();
+    /// assert!(textarea.block().is_none());
+    /// ```
+    pub fn remove_block(&mut self) {
+        self.block = None;
+    }
+
+    /// Get the block of textarea if exists.
+    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
+        self.block.as_ref()
+    }
+
+    /// Set the length of tab character. Setting 0 disables tab inputs.
+    /// ```
+    /// use tui_textarea::{TextArea, Input, Key};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
+    ///
+    /// textarea.set_tab_length(8);
+    /// textarea.input(tab_input.clone());
+    /// assert_eq!(textarea.lines(), ["        "]);
+    ///
+    /// textarea.set_tab_length(2);
+    /// textarea.input(tab_input);
+    /// assert_eq!(textarea.lines(), ["          "]);
+    /// ```
+    pub fn set_tab_length(&mut self, len: u8) {
+        self.tab_len = len;
+    }
+
+    /// Get how many spaces are used for representing tab character. The default value is 4.
+    pub fn tab_length(&self) -> u8 {
+        self.tab_len
+    }
+
+    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
+    /// spaces. By default, hard tab is disabled.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_hard_tab_indent(true);
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// ```
+    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
+        self.hard_tab_indent = enabled;
+    }
+
+    /// Get if a hard tab is used for indent or not.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// assert!(!textarea.hard_tab_indent());
+    /// textarea.set_hard_tab_indent(true);
+    /// assert!(textarea.hard_tab_indent());
+    /// ```
+    pub fn hard_tab_indent(&self) -> bool {
+        self.hard_tab_indent
+    }
+
+    /// Set the token of line comments used by [`TextArea::toggle_line_comment`]. The default value is `//`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["echo hi"]);
+    ///
+    /// textarea.set_line_comment("#");
+    /// assert_eq!(textarea.line_comment(), Some("#"));
+    /// textarea.toggle_line_comment();
+    /// assert_eq!(textarea.lines(), ["# echo hi"]);
+    /// ```
+    pub fn set_line_comment<S: Into<String>>(&mut self, token: S) {
+        self.line_comment = Some(token.into());
+    }
+
+    /// Remove the token of line comments. After calling this method, [`TextArea::toggle_line_comment`] does nothing.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["a"]);
+    ///
+    /// textarea.remove_line_comment();
+    /// assert_eq!(textarea.line_comment(), None);
+    /// assert!(!textarea.toggle_line_comment());
+    /// ```
+    pub fn remove_line_comment(&mut self) {
+        self.line_comment = None;
+    }
+
+    /// Get the token of line comments if set.
+    pub fn line_comment(&self) -> Option<&str> {
+        self.line_comment.as_deref()
+    }
+
+    /// Set the opening and closing tokens of block comments used by [`TextArea::toggle_block_comment`]. The default
+    /// value is `/*` and `*/`.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["<p>hi</p>"]);
+    ///
+    /// textarea.set_block_comment("<!--", "-->");
+    /// assert_eq!(textarea.block_comment(), Some(("<!--", "-->")));
+    /// textarea.toggle_block_comment();
+    /// assert_eq!(textarea.lines(), ["<!-- <p>hi</p> -->"]);
+    /// ```
+    pub fn set_block_comment<S: Into<String>, T: Into<String>>(&mut self, open: S, close: T) {
+        self.block_comment = Some((open.into(), close.into()));
+    }
+
+    /// Remove the tokens of block comments. After calling this method, [`TextArea::toggle_block_comment`] does
+    /// nothing.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use ratatui::widgets::{Block, Borders};
     ///
-    /// let mut textarea = TextArea::default();
-    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
-    /// textarea.set_block(block);
-    /// assert!(textarea.block().is_some());
+    /// let mut textarea = TextArea::from(["a"]);
+    ///
+    /// textarea.remove_block_comment();
+    /// assert_eq!(textarea.block_comment(), None);
+    /// assert!(!textarea.toggle_block_comment());
     /// ```
-    pub fn set_block(&mut self, block: Block<'a>) {
-        self.block = Some(block);
+    pub fn remove_block_comment(&mut self) {
+        self.block_comment = None;
     }
 
-    /// Remove the block of textarea which was set by [`TextArea::set_block`].
+    /// Get the opening and closing tokens of block comments if set.
+    pub fn block_comment(&self) -> Option<(&str, &str)> {
+        self.block_comment
+            .as_ref()
+            .map(|(open, close)| (open.as_str(), close.as_str()))
+    }
+
+    /// Set the fill column used by [`TextArea::reflow_paragraph`]. Reflowed lines are no longer than this display
+    /// width unless a word is longer than it. The default value is 80.
     /// ```
     /// use tui_textarea::TextArea;
-    /// use ratatui::widgets::{Block, Borders};
     ///
-    /// let mut textarea = TextArea::default();
-    /// let block = Block::default().borders(Borders::ALL).title("Block Title");
-    /// textarea.set_block(block);
-    /// textarea.remove_block();
-    /// assert!(textarea.block().is_none());
+    /// let mut textarea = TextArea::from(["foo bar baz"]);
+    ///
+    /// textarea.set_fill_column(7);
+    /// assert_eq!(textarea.fill_column(), 7);
+    /// textarea.reflow_paragraph();
+    /// assert_eq!(textarea.lines(), ["foo bar", "baz"]);
     /// ```
-    pub fn remove_block(&mut self) {
-        self.block = None;
+    pub fn set_fill_column(&mut self, width: usize) {
+        self.fill_column = width;
     }
 
-    /// Get the block of textarea if exists.
-    pub fn block<'s>(&'s self) -> Option<&'s Block<'a>> {
-        self.block.as_ref()
+    /// Get the fill column used by [`TextArea::reflow_paragraph`].
+    pub fn fill_column(&self) -> usize {
+        self.fill_column
     }
 
-    /// Set the length of tab character. Setting 0 disables tab inputs.
+    /// Set the pairs of opening and closing characters like brackets and quotes which are automatically paired. When
+    /// typing an opening character, its closing character is inserted after the cursor. When text is selected, the
+    /// selection is wrapped with the pair instead. Typing a closing character next to the cursor moves the cursor over
+    /// it. Backspace between an empty pair deletes both. Each of these operations is undone at once. Quotes whose opening
+    /// and closing characters are the same are not paired just after alphanumeric characters. No pair is set by
+    /// default. Set an empty table to disable auto pairing.
     /// ```
-    /// use tui_textarea::{TextArea, Input, Key};
+    /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
-    /// let tab_input = Input { key: Key::Tab, ctrl: false, alt: false, shift: false };
-    ///
-    /// textarea.set_tab_length(8);
-    /// textarea.input(tab_input.clone());
-    /// assert_eq!(textarea.lines(), ["        "]);
+    /// textarea.set_auto_pairs([('(', ')'), ('[', ']'), ('{', '}'), ('"', '"')]);
     ///
-    /// textarea.set_tab_length(2);
-    /// textarea.input(tab_input);
-    /// assert_eq!(textarea.lines(), ["          "]);
+    /// textarea.insert_char('(');
+    /// assert_eq!(textarea.lines(), ["()"]);
+    /// textarea.insert_char('"');
+    /// assert_eq!(textarea.lines(), ["(\"\")"]);
+    /// textarea.delete_char();
+    /// assert_eq!(textarea.lines(), ["()"]);
+    /// textarea.insert_char(')');
+    /// assert_eq!(textarea.lines(), ["()"]);
+    /// assert_eq!(textarea.cursor(), (0, 2));
     /// ```
-    pub fn set_tab_length(&mut self, len: u8) {
-        self.tab_len = len;
+    pub fn set_auto_pairs<I: IntoIterator<Item = (char, char)>>(&mut self, pairs: I) {
+        self.auto_pairs = pairs.into_iter().collect();
     }
 
-    /// Get how many spaces are used for representing tab character. The default value is 4.
-    pub fn tab_length(&self) -> u8 {
-        self.tab_len
+    /// Get the pairs of characters which are automatically paired. See [`TextArea::set_auto_pairs`].
+    pub fn auto_pairs(&self) -> &[(char, char)] {
+        &self.auto_pairs
     }
 
-    /// Set if a hard tab is used or not for indent. When `true` is set, typing a tab key inserts a hard tab instead of
-    /// spaces. By default, hard tab is disabled.
+    /// Enable or disable smart backspace. When enabled, [`TextArea::delete_char`] in the leading whitespaces of a line
+    /// deletes spaces back to the previous tab stop so that spaces inserted by [`TextArea::insert_tab`] are deleted at
+    /// once. Tab stops are computed with the tab length set by [`TextArea::set_tab_length`]. Smart backspace is disabled
+    /// by default.
     /// ```
     /// use tui_textarea::TextArea;
     ///
     /// let mut textarea = TextArea::default();
+    /// textarea.set_smart_backspace(true);
     ///
-    /// textarea.set_hard_tab_indent(true);
     /// textarea.insert_tab();
-    /// assert_eq!(textarea.lines(), ["\t"]);
+    /// textarea.insert_tab();
+    /// assert_eq!(textarea.lines(), ["        "]);
+    /// textarea.delete_char();
+    /// assert_eq!(textarea.lines(), ["    "]);
     /// ```
-    pub fn set_hard_tab_indent(&mut self, enabled: bool) {
-        self.hard_tab_indent = enabled;
+    pub fn set_smart_backspace(&mut self, enabled: bool) {
+        self.smart_backspace = enabled;
     }
 
-    /// Get if a hard tab is used for indent or not.
+    /// Get if smart backspace is enabled or not. See [`TextArea::set_smart_backspace`].
+    pub fn smart_backspace(&self) -> bool {
+        self.smart_backspace
+    }
+
+    /// Enable or disable auto-indent. When enabled, [`TextArea::insert_newline`] indents the new line with the
+    /// leading whitespaces of the current line. The indentation is normalized with the tab length and
+    /// [`TextArea::hard_tab_indent`]. With an indent rule set by [`TextArea::set_indent_rule`], the new line is indented
+    /// one more level and [`TextArea::insert_char`] dedents the line following the rule. The newline and its
+    /// indentation are undone at once. Auto-indent is disabled by default.
     /// ```
-    /// use tui_textarea::TextArea;
+    /// use tui_textarea::{CursorMove, TextArea};
     ///
-    /// let mut textarea = TextArea::default();
+    /// let mut textarea = TextArea::from(["    foo"]);
+    /// textarea.set_auto_indent(true);
     ///
-    /// assert!(!textarea.hard_tab_indent());
-    /// textarea.set_hard_tab_indent(true);
-    /// assert!(textarea.hard_tab_indent());
+    /// textarea.move_cursor(CursorMove::End);
+    /// textarea.insert_newline();
+    /// assert_eq!(textarea.lines(), ["    foo", "    "]);
+    ///
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["    foo"]);
     /// ```
-    pub fn hard_tab_indent(&self) -> bool {
-        self.hard_tab_indent
+    pub fn set_auto_indent(&mut self, enabled: bool) {
+        self.auto_indent = enabled;
+    }
+
+    /// Get if auto-indent is enabled or not. See [`TextArea::set_auto_indent`].
+    pub fn auto_indent(&self) -> bool {
+        self.auto_indent
+    }
+
+    /// Set the rule to indent and dedent lines on auto-indent. See [`IndentRule`] for more details.
+    pub fn set_indent_rule<R: IndentRule + 'static>(&mut self, rule: R) {
+        self.indenter = Shared::new(Arc::new(rule));
+    }
+
+    /// Remove the indent rule set by [`TextArea::set_indent_rule`]. Auto-indent only copies the indentation after this.
+    pub fn remove_indent_rule(&mut self) {
+        self.indenter = Shared::default();
+    }
+
+    /// Get the indent rule set by [`TextArea::set_indent_rule`].
+    pub fn indent_rule(&self) -> Option<&dyn IndentRule> {
+        self.indenter.get()
     }
 
     /// Get a string for indent. It consists of spaces by default. When hard tab is enabled, it is a tab character.
@@ -1965,6 +3433,71 @@ impl<'a> TextArea<'a> {
         self.line_number_style
     }
 
+    /// Set the style to highlight the bracket under (or before) the cursor and its matching bracket. The highlight is
+    /// disabled by default. Brackets are matched in the same way as [`CursorMove::MatchingBracket`].
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// let style = Style::default().bg(Color::Magenta);
+    /// textarea.set_matching_bracket_style(style);
+    /// assert_eq!(textarea.matching_bracket_style(), Some(style));
+    /// ```
+    pub fn set_matching_bracket_style(&mut self, style: Style) {
+        self.matching_bracket_style = Some(style);
+    }
+
+    /// Remove the style of matching brackets which was set by [`TextArea::set_matching_bracket_style`]. After calling
+    /// this method, matching brackets will no longer be highlighted.
+    /// ```
+    /// use ratatui::style::{Style, Color};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_matching_bracket_style(Style::default().bg(Color::Magenta));
+    /// textarea.remove_matching_bracket_style();
+    /// assert_eq!(textarea.matching_bracket_style(), None);
+    /// ```
+    pub fn remove_matching_bracket_style(&mut self) {
+        self.matching_bracket_style = None;
+    }
+
+    /// Get the style of matching brackets if set.
+    pub fn matching_bracket_style(&self) -> Option<Style> {
+        self.matching_bracket_style
+    }
+
+    /// Set the quote characters whose quoted regions are skipped on matching brackets. A quoted region ends at the
+    /// same quote character not escaped with `\` or at the end of line. Brackets in a quoted region are matched only
+    /// with brackets in quoted regions. The default value is `['"']`. Single quote is not included by default since
+    /// it is not always used for quoting (e.g. lifetimes in Rust).
+    /// ```
+    /// use tui_textarea::{CursorMove, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["f(')', x)"]);
+    /// assert_eq!(textarea.bracket_quotes(), ['"']);
+    ///
+    /// textarea.move_cursor(CursorMove::Jump(0, 1));
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (0, 3));
+    ///
+    /// textarea.set_bracket_quotes(['"', '\'']);
+    /// textarea.move_cursor(CursorMove::Jump(0, 1));
+    /// textarea.move_cursor(CursorMove::MatchingBracket);
+    /// assert_eq!(textarea.cursor(), (0, 8));
+    /// ```
+    pub fn set_bracket_quotes<I: IntoIterator<Item = char>>(&mut self, quotes: I) {
+        self.bracket_quotes = quotes.into_iter().collect();
+    }
+
+    /// Get the quote characters skipped on matching brackets. See [`TextArea::set_bracket_quotes`].
+    pub fn bracket_quotes(&self) -> &[char] {
+        &self.bracket_quotes
+    }
+
     /// Set the placeholder text. The text is set in the textarea when no text is input. Setting a non-empty string `""`
     /// enables the placeholder. The default value is an empty string so the placeholder is disabled by default.
     /// To customize the text style, see [`TextArea::set_placeholder_style`].
@@ -2097,6 +3630,37 @@ impl<'a> TextArea<'a> {
         self.cursor_style
     }
 
+    /// Set the style of cursor in overwrite mode. By default, the same style as [`TextArea::cursor_style`] is used
+    /// in both insert mode and overwrite mode. See [`TextArea::set_overwrite`] for overwrite mode.
+    /// ```
+    /// use ratatui::style::{Modifier, Style};
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    /// assert_eq!(textarea.overwrite_cursor_style(), textarea.cursor_style());
+    ///
+    /// let style = Style::default().add_modifier(Modifier::UNDERLINED);
+    /// textarea.set_overwrite_cursor_style(style);
+    /// assert_eq!(textarea.overwrite_cursor_style(), style);
+    /// ```
+    pub fn set_overwrite_cursor_style(&mut self, style: Style) {
+        self.overwrite_cursor_style = Some(style);
+    }
+
+    /// Get the style of cursor in overwrite mode.
+    pub fn overwrite_cursor_style(&self) -> Style {
+        self.overwrite_cursor_style.unwrap_or(self.cursor_style)
+    }
+
+    // Style of cursor in the current mode
+    pub(crate) fn current_cursor_style(&self) -> Style {
+        if self.overwrite {
+            self.overwrite_cursor_style()
+        } else {
+            self.cursor_style
+        }
+    }
+
     /// Get slice of line texts. This method borrows the content, but not moves. Note that the returned slice will
     /// never be empty because an empty text means a slice containing one empty line. This is correct since any text
     /// file must end with a newline.
@@ -2239,53 +3803,6 @@ impl<'a> TextArea<'a> {
         }
     }
 
-    /// Calculate the visual width of a single character, handling tabs and Unicode
-    // fn calculate_char_visual_width(&self, ch: char, current_visual_pos: u16) -> u16 {
-    //     if ch == '\t' {
-    //         self.tab_len as u16 - (current_visual_pos % self.tab_len as u16)
-    //     } else {
-    //         unicode_width::UnicodeWidthChar::width(ch).unwrap_or(0) as u16
-    //     }
-    // }
-
-
-    /// Convert logical column position to visual column position within a line
-    /// accounting for tab expansion and Unicode width
-    // fn logical_to_visual_column(&self, line: &str, logical_col: usize) -> u16 {
-    //     let mut visual_pos = 0;
-        
-    //     for (char_idx, ch) in line.char_indices() {
-    //         if line[..char_idx].chars().count() >= logical_col {
-    //             break;
-    //         }
-    //         visual_pos += self.calculate_char_visual_width(ch, visual_pos);
-    //     }
-        
-    //     visual_pos
-    // }
-
-    // /// Convert visual column position to logical column position within a line
-    // /// accounting for tab expansion and Unicode width
-    // fn visual_to_logical_column(&self, line: &str, target_visual_col: u16) -> usize {
-    //     let mut logical_pos = 0;
-    //     let mut visual_pos = 0;
-        
-    //     for ch in line.chars() {
-    //         let char_width = self.calculate_char_visual_width(ch, visual_pos);
-            
-    //         if visual_pos + char_width > target_visual_col {
-    //             break;
-    //         }
-            
-    //         visual_pos += char_width;
-    //         logical_pos += 1;
-    //     }
-        
-    //     logical_pos
-    // }
-
-
-
     /// Check if the textarea has a empty content.
     /// ```
     /// use tui_textarea::TextArea;
@@ -2317,7 +3834,7 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.yank_text(), "abc\nd");
     /// ```
     pub fn yank_text(&self) -> String {
-        self.yank.to_string()
+        self.kill_ring.top().to_string()
     }
 
     /// Set a yanked text. The text can be inserted by [`TextArea::paste`]. `\n` and `\r\n` are recognized as newline
@@ -2332,13 +3849,269 @@ impl<'a> TextArea<'a> {
     /// assert_eq!(textarea.lines(), ["hello", "world"]);
     /// ```
     pub fn set_yank_text(&mut self, text: impl Into<String>) {
-        // `str::lines` is not available since it strips a newline at end
-        let lines: Vec<_> = text
-            .into()
-            .split('\n')
-            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
-            .collect();
-        self.yank = lines.into();
+        self.push_kill(YankText::parse(&text.into()));
+    }
+
+    /// Start recording key inputs passed to [`TextArea::input`] as a keyboard macro. The macro is stored with the
+    /// name when [`TextArea::stop_recording`] is called. When recording is already ongoing, it is discarded and
+    /// recording restarts. Inputs which start or stop recording and mouse inputs with positions are not recorded.
+    /// ```
+    /// use tui_textarea::{Input, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.start_recording("a");
+    /// assert_eq!(textarea.recording_macro(), Some("a"));
+    /// textarea.input(Input { key: Key::Char('x'), ..Default::default() });
+    /// textarea.input(Input { key: Key::Enter, ..Default::default() });
+    /// assert!(textarea.stop_recording());
+    /// assert_eq!(textarea.macros()["a"].inputs().len(), 2);
+    /// ```
+    pub fn start_recording(&mut self, name: impl Into<String>) {
+        self.macros.start(name.into());
+    }
+
+    /// Stop recording the keyboard macro started by [`TextArea::start_recording`] and store it. This method returns
+    /// `false` when recording is not ongoing.
+    pub fn stop_recording(&mut self) -> bool {
+        self.macros.stop()
+    }
+
+    /// Get the name of the keyboard macro being recorded. `None` is returned when recording is not ongoing.
+    pub fn recording_macro(&self) -> Option<&str> {
+        self.macros.recording()
+    }
+
+    /// Replay the keyboard macro `count` times by passing the recorded key inputs to [`TextArea::input`]. All edits
+    /// by the replay are grouped so that a single undo reverts them. This method returns if the replay modified text
+    /// contents or not in the textarea. Nothing happens when the macro is not found or is replaying already.
+    /// ```
+    /// use tui_textarea::{CursorMove, Input, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::from(["a", "b", "c"]);
+    /// let key = |key| Input { key, ..Default::default() };
+    ///
+    /// // Append ';' to the line and move to the next line
+    /// textarea.start_recording("semicolon");
+    /// for k in [Key::End, Key::Char(';'), Key::Down] {
+    ///     textarea.input(key(k));
+    /// }
+    /// textarea.stop_recording();
+    ///
+    /// assert!(textarea.replay_macro("semicolon", 2));
+    /// assert_eq!(textarea.lines(), ["a;", "b;", "c;"]);
+    ///
+    /// // Replay is reverted at once
+    /// textarea.undo();
+    /// assert_eq!(textarea.lines(), ["a;", "b", "c"]);
+    /// ```
+    pub fn replay_macro(&mut self, name: &str, count: usize) -> bool {
+        let inputs = match self.macros.begin_replay(name) {
+            Some(inputs) => inputs,
+            None => return false,
+        };
+        self.chords.cancel();
+        self.history.begin_group();
+        let mut modified = false;
+        for _ in 0..count {
+            for input in inputs.iter() {
+                modified |= self.input(input.clone());
+            }
+        }
+        self.history.end_group();
+        self.macros.end_replay();
+        modified
+    }
+
+    /// Get the keyboard macros stored by name. See [`Macro`] for more details.
+    pub fn macros(&self) -> &BTreeMap<String, Macro> {
+        &self.macros.macros
+    }
+
+    /// Get the mutable reference of the keyboard macros to add, modify, or remove them.
+    /// ```
+    /// use tui_textarea::{Input, Key, TextArea};
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.start_recording("a");
+    /// textarea.stop_recording();
+    /// assert!(textarea.macros_mut().remove("a").is_some());
+    /// assert!(textarea.macros().is_empty());
+    /// ```
+    pub fn macros_mut(&mut self) -> &mut BTreeMap<String, Macro> {
+        &mut self.macros.macros
+    }
+
+    /// Set the clipboard to share yanked text with. Text yanked by copy, cut, and deletion is written to the
+    /// clipboard, and [`TextArea::paste`] reads text from it. See [`ClipboardProvider`] for more details.
+    /// ```
+    /// use tui_textarea::{ClipboardProvider, MemoryClipboard, TextArea};
+    ///
+    /// let clipboard = MemoryClipboard::new();
+    /// let mut textarea = TextArea::from(["hello"]);
+    /// textarea.set_clipboard(clipboard.clone());
+    ///
+    /// textarea.delete_line_by_end();
+    /// assert_eq!(clipboard.get_text().as_deref(), Some("hello"));
+    ///
+    /// clipboard.set_text("world");
+    /// textarea.paste();
+    /// assert_eq!(textarea.lines(), ["world"]);
+    /// ```
+    pub fn set_clipboard<C: ClipboardProvider + 'static>(&mut self, clipboard: C) {
+        self.clipboard = Shared::new(Arc::new(clipboard));
+    }
+
+    /// Remove the clipboard set by [`TextArea::set_clipboard`]. After this, only the kill ring is used for pasting.
+    pub fn remove_clipboard(&mut self) {
+        self.clipboard = Shared::default();
+    }
+
+    /// Get the clipboard set by [`TextArea::set_clipboard`].
+    pub fn clipboard(&self) -> Option<&dyn ClipboardProvider> {
+        self.clipboard.get()
+    }
+
+    /// Get the texts in the kill ring from the newest one. The first text is the same as [`TextArea::yank_text`].
+    /// Consecutive kills by [`TextArea::delete_line_by_end`], [`TextArea::delete_line_by_head`],
+    /// [`TextArea::delete_word`], and [`TextArea::delete_next_word`] at the same position are concatenated into one
+    /// text like Emacs.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::from(["foo bar baz"]);
+    ///
+    /// textarea.delete_next_word();
+    /// textarea.delete_next_word(); // Appended to the previous kill
+    /// textarea.set_yank_text("qux");
+    /// assert_eq!(textarea.kill_ring(), ["qux", "foo bar"]);
+    /// ```
+    pub fn kill_ring(&self) -> Vec<String> {
+        self.kill_ring.texts().map(ToString::to_string).collect()
+    }
+
+    /// Get the text in the register. Registers `a` to `z` are available. Uppercase names refer to the same registers
+    /// as lowercase names. `"` is the unnamed register which refers to the newest text in the kill ring. `None` is
+    /// returned when the register is empty or the name is invalid.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_register('a', "hello");
+    /// assert_eq!(textarea.register('a').as_deref(), Some("hello"));
+    /// assert_eq!(textarea.register('b'), None);
+    /// ```
+    pub fn register(&self, name: char) -> Option<String> {
+        if name == '"' {
+            return Some(self.yank_text());
+        }
+        self.registers.get(name).map(ToString::to_string)
+    }
+
+    /// Set the text to the register like Vim. Registers `a` to `z` are available. Uppercase name `A` to `Z` appends
+    /// the text to the register. `_` is the black hole register which discards the text. `"` is the unnamed register
+    /// which is the same as [`TextArea::set_yank_text`]. This method returns `false` when the register name is
+    /// invalid.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_register('a', "hello");
+    /// textarea.set_register('A', ", world");
+    /// assert_eq!(textarea.register('a').as_deref(), Some("hello, world"));
+    ///
+    /// assert!(textarea.set_register('_', "discarded"));
+    /// assert!(!textarea.set_register('1', "invalid"));
+    /// ```
+    pub fn set_register(&mut self, name: char, text: impl Into<String>) -> bool {
+        if name == '"' {
+            self.set_yank_text(text);
+            return true;
+        }
+        if !Registers::is_valid(name) {
+            return false;
+        }
+        self.registers.set(name, YankText::parse(&text.into()));
+        true
+    }
+
+    /// Copy the selected text to the register instead of the kill ring. See [`TextArea::set_register`] for the
+    /// register names. When nothing is selected or the name is invalid, this method does nothing.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello"]);
+    ///
+    /// textarea.select_all();
+    /// textarea.copy_to_register('a');
+    /// assert_eq!(textarea.register('a').as_deref(), Some("hello"));
+    /// assert_eq!(textarea.yank_text(), "");
+    /// ```
+    pub fn copy_to_register(&mut self, name: char) {
+        if name == '"' {
+            self.copy();
+        } else if Registers::is_valid(name) {
+            if let Some(text) = self.take_selection_text() {
+                self.registers.set(name, text);
+            }
+        }
+    }
+
+    /// Cut the selected text to the register instead of the kill ring. Cutting to the black hole register `_` deletes
+    /// the text without changing any register. See [`TextArea::set_register`] for the register names. This method
+    /// returns whether the text was modified.
+    /// ```
+    /// use tui_textarea::{TextArea, CursorMove};
+    ///
+    /// let mut textarea = TextArea::from(["hello world"]);
+    ///
+    /// textarea.start_selection();
+    /// textarea.move_cursor(CursorMove::WordForward);
+    /// textarea.cut_to_register('_');
+    /// assert_eq!(textarea.lines(), ["world"]);
+    /// assert_eq!(textarea.yank_text(), "");
+    /// ```
+    pub fn cut_to_register(&mut self, name: char) -> bool {
+        if name == '"' {
+            return self.cut();
+        }
+        if !Registers::is_valid(name) {
+            return false;
+        }
+        match self.take_deleted_selection() {
+            Some(text) => {
+                self.registers.set(name, text);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Paste the text in the register. The selected text is replaced with the pasted text. See
+    /// [`TextArea::register`] for the register names. This method returns if some text was inserted or not in the
+    /// textarea.
+    /// ```
+    /// use tui_textarea::TextArea;
+    ///
+    /// let mut textarea = TextArea::default();
+    ///
+    /// textarea.set_register('q', "foo\nbar");
+    /// textarea.paste_from_register('q');
+    /// assert_eq!(textarea.lines(), ["foo", "bar"]);
+    /// ```
+    pub fn paste_from_register(&mut self, name: char) -> bool {
+        if name == '"' {
+            return self.paste();
+        }
+        let text = match self.registers.get(name) {
+            Some(text) => text.clone(),
+            None => return false,
+        };
+        self.delete_selection(false);
+        self.insert_yank(text)
     }
 
     /// Set a regular expression pattern for text search. Setting an empty string stops the text search.
@@ -2628,6 +4401,20 @@ impl<'a> TextArea<'a> {
     }
 }
 
+// Adjust the column of the position after inserting `chars` characters at `col` in `row`
+fn shift_col_on_insert(pos: &mut (usize, usize), row: usize, col: usize, chars: usize) {
+    if pos.0 == row && pos.1 >= col {
+        pos.1 += chars;
+    }
+}
+
+// Adjust the column of the position after deleting `chars` characters at `col` in `row`
+fn shift_col_on_delete(pos: &mut (usize, usize), row: usize, col: usize, chars: usize) {
+    if pos.0 == row && pos.1 > col {
+        pos.1 = cmp::max(pos.1.saturating_sub(chars), col);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2691,4 +4478,31 @@ mod tests {
         // Click outside widget area should return false
         assert!(!textarea.handle_mouse_click(10, 10, widget_area));
     }
+
+    #[test]
+    fn transform_rect_selection() {
+        let mut textarea = TextArea::from(["abcd", "efgh", "ijkl"]);
+        textarea.set_cursor((0, 1));
+        textarea.start_selection();
+        textarea.set_cursor((2, 3));
+        textarea.rect_selection = true;
+
+        assert!(textarea.convert_case(Case::Upper));
+        assert_eq!(textarea.lines(), ["aBCd", "eFGh", "iJKl"]);
+        assert_eq!(textarea.selection_start, Some((0, 1)));
+        assert_eq!(textarea.cursor(), (2, 3));
+
+        // Rows are transformed in one edit
+        assert!(textarea.undo());
+        assert_eq!(textarea.lines(), ["abcd", "efgh", "ijkl"]);
+
+        // Rows are clamped when the text gets shorter
+        textarea.set_cursor((0, 0));
+        textarea.start_selection();
+        textarea.set_cursor((1, 4));
+        textarea.rect_selection = true;
+        assert!(textarea.transform_selection(|s| s[..1].to_string()));
+        assert_eq!(textarea.lines(), ["a", "e", "ijkl"]);
+        assert_eq!(textarea.cursor(), (1, 1));
+    }
 }
@@ -0,0 +1,236 @@
+#[cfg(feature = "arbitrary")]
+use arbitrary::Arbitrary;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::mem;
+
+/// Specify how to convert the case of text with [`TextArea::convert_case`](crate::TextArea::convert_case).
+///
+/// [`Case::Snake`], [`Case::Camel`], and [`Case::Kebab`] convert each identifier in the text. An identifier is a run of
+/// alphanumeric characters and `_`. It is split into words at `_` and case changes like `fooBar` or `HTTPServer`, then
+/// the words are joined in the style. Leading and trailing `_` like `_private` or `__init__` are kept. Since `-` is
+/// usually an operator like `a-b` in code, it is a part of an identifier only when converting to [`Case::Kebab`] or
+/// when the whole text is one identifier like `user-id`. Other characters are kept as-is.
+///
+/// This type is marked as `#[non_exhaustive]` since more variations may be supported in the future.
+/// ```
+/// use tui_textarea::Case;
+///
+/// assert_eq!(Case::Upper.convert("hello world"), "HELLO WORLD");
+/// assert_eq!(Case::Title.convert("hello world"), "Hello World");
+/// assert_eq!(Case::Snake.convert("parseHTTPRequest(fooBar)"), "parse_http_request(foo_bar)");
+/// assert_eq!(Case::Camel.convert("user-id"), "userId");
+/// assert_eq!(Case::Camel.convert("_user_id = a-b"), "_userId = a-b");
+/// ```
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Case {
+    /// Convert all characters to uppercase like `HELLO WORLD`.
+    Upper,
+    /// Convert all characters to lowercase like `hello world`.
+    Lower,
+    /// Convert the first character of each word to uppercase and the rest to lowercase like `Hello World`.
+    Title,
+    /// Convert uppercase characters to lowercase and lowercase characters to uppercase like `hELLO wORLD`.
+    Swap,
+    /// Join lowercase words with underscores like `hello_world`.
+    Snake,
+    /// Join capitalized words with the first word in lowercase like `helloWorld`.
+    Camel,
+    /// Join lowercase words with hyphens like `hello-world`.
+    Kebab,
+}
+
+impl Case {
+    /// Convert the case of the text.
+    /// ```
+    /// use tui_textarea::Case;
+    ///
+    /// assert_eq!(Case::Swap.convert("Hello"), "hELLO");
+    /// assert_eq!(Case::Kebab.convert("  FooBar_baz\nqux"), "  foo-bar-baz\nqux");
+    /// ```
+    pub fn convert(self, text: &str) -> String {
+        match self {
+            Self::Upper => text.to_uppercase(),
+            Self::Lower => text.to_lowercase(),
+            Self::Title => title_case(text),
+            Self::Swap => text
+                .chars()
+                .flat_map(|c| {
+                    let swapped: Vec<char> = if c.is_uppercase() {
+                        c.to_lowercase().collect()
+                    } else {
+                        c.to_uppercase().collect()
+                    };
+                    swapped
+                })
+                .collect(),
+            Self::Snake | Self::Camel | Self::Kebab => {
+                let is_kebab_ident = |c: char| c.is_alphanumeric() || c == '_' || c == '-';
+                let hyphen = self == Self::Kebab || text.chars().all(is_kebab_ident);
+                let mut converted = String::with_capacity(text.len());
+                let mut ident = String::new();
+                for c in text.chars() {
+                    if c.is_alphanumeric() || c == '_' || hyphen && c == '-' {
+                        ident.push(c);
+                    } else {
+                        converted.push_str(&self.join_words(&ident));
+                        ident.clear();
+                        converted.push(c);
+                    }
+                }
+                converted.push_str(&self.join_words(&ident));
+                converted
+            }
+        }
+    }
+
+    fn join_words(self, ident: &str) -> String {
+        let is_separator = |c| c == '_' || c == '-';
+        let body = ident.trim_start_matches(is_separator);
+        let prefix = &ident[..ident.len() - body.len()];
+        let body = body.trim_end_matches(is_separator);
+        let suffix = &ident[prefix.len() + body.len()..];
+
+        let words = split_words(body);
+        if words.is_empty() {
+            return ident.to_string();
+        }
+        let joined: String = match self {
+            Self::Snake => lower_words(&words).join("_"),
+            Self::Kebab => lower_words(&words).join("-"),
+            _ => words
+                .iter()
+                .enumerate()
+                .map(|(i, w)| {
+                    if i == 0 {
+                        w.to_lowercase()
+                    } else {
+                        capitalize(w)
+                    }
+                })
+                .collect(),
+        };
+        format!("{}{}{}", prefix, joined, suffix)
+    }
+}
+
+fn lower_words(words: &[String]) -> Vec<String> {
+    words.iter().map(|w| w.to_lowercase()).collect()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(c) => c
+            .to_uppercase()
+            .chain(chars.flat_map(char::to_lowercase))
+            .collect(),
+        None => String::new(),
+    }
+}
+
+// Split the text into words at non-alphanumeric characters and case changes. An uppercase character starts a new word
+// after a lowercase character or a digit, and the last uppercase character of an uppercase run followed by a lowercase
+// character starts a new word (`HTTPServer` is split into `HTTP` and `Server`)
+fn split_words(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = vec![];
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(mem::take(&mut word));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !word.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |c| c.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || prev.is_uppercase() && next_is_lower {
+                words.push(mem::take(&mut word));
+            }
+        }
+        word.push(c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn title_case(text: &str) -> String {
+    let mut converted = String::with_capacity(text.len());
+    let mut in_word = false;
+    for c in text.chars() {
+        if in_word {
+            converted.extend(c.to_lowercase());
+        } else {
+            converted.extend(c.to_uppercase());
+        }
+        // Apostrophes don't split words like "don't"
+        in_word = c.is_alphanumeric() || in_word && c == '\'';
+    }
+    converted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_text_into_words() {
+        for (text, want) in [
+            ("foo bar", &["foo", "bar"][..]),
+            ("fooBar", &["foo", "Bar"][..]),
+            ("FooBar", &["Foo", "Bar"][..]),
+            ("foo_bar-baz.qux", &["foo", "bar", "baz", "qux"][..]),
+            ("HTTPServer", &["HTTP", "Server"][..]),
+            ("parseHTTP", &["parse", "HTTP"][..]),
+            ("v2Api", &["v2", "Api"][..]),
+            ("FOO_BAR", &["FOO", "BAR"][..]),
+            ("__init__", &["init"][..]),
+            ("", &[][..]),
+        ] {
+            assert_eq!(split_words(text), want, "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn convert_case() {
+        for (case, text, want) in [
+            (Case::Upper, "héllo wörld", "HÉLLO WÖRLD"),
+            (Case::Lower, "Hello World", "hello world"),
+            (Case::Title, "hELLO wORLD", "Hello World"),
+            (Case::Title, "don't stop-me", "Don't Stop-Me"),
+            (Case::Swap, "Hello, World", "hELLO, wORLD"),
+            (Case::Swap, "ß", "SS"),
+            (Case::Snake, "helloWorld", "hello_world"),
+            (Case::Snake, "Hello World", "hello world"),
+            (Case::Snake, "fooBar = a-b;", "foo_bar = a-b;"),
+            (Case::Snake, "_privateField", "_private_field"),
+            (Case::Snake, "__init__", "__init__"),
+            (Case::Snake, "__fooBar__", "__foo_bar__"),
+            (Case::Snake, "user-id", "user_id"),
+            (Case::Snake, "a - b", "a - b"),
+            (Case::Snake, "  fooBar  ", "  foo_bar  "),
+            (Case::Snake, "foo\nbarBaz", "foo\nbar_baz"),
+            (Case::Camel, "hello_world", "helloWorld"),
+            (Case::Camel, "HELLO-WORLD", "helloWorld"),
+            (Case::Camel, "XMLHttpRequest", "xmlHttpRequest"),
+            (Case::Camel, "x = a-b", "x = a-b"),
+            (Case::Camel, "_foo_bar", "_fooBar"),
+            (Case::Camel, "foo_bar_", "fooBar_"),
+            (Case::Camel, "_", "_"),
+            (Case::Kebab, "HelloWorld", "hello-world"),
+            (Case::Kebab, "hello_world", "hello-world"),
+            (Case::Kebab, "   ", "   "),
+            (Case::Kebab, "x = fooBar-baz", "x = foo-bar-baz"),
+            (Case::Kebab, "_foo_bar", "_foo-bar"),
+        ] {
+            assert_eq!(case.convert(text), want, "{:?} {:?}", case, text);
+        }
+    }
+}
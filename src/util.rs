@@ -1,3 +1,46 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Instant;
+use unicode_width::UnicodeWidthChar as _;
+
+/// Source of the current time. It is replaceable for testing features which depend on time intervals.
+pub type Clock = Arc<dyn Fn() -> Instant + Send + Sync>;
+
+// Optional trait object set to `TextArea` such as a clipboard. This wrapper is necessary since `TextArea` implements
+// `Debug` but the trait objects don't
+pub(crate) struct Shared<T: ?Sized>(Option<Arc<T>>);
+
+impl<T: ?Sized> Shared<T> {
+    pub(crate) fn new(value: Arc<T>) -> Self {
+        Self(Some(value))
+    }
+
+    pub(crate) fn get(&self) -> Option<&T> {
+        self.0.as_deref()
+    }
+}
+
+impl<T: ?Sized> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized> Default for Shared<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T: ?Sized> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(_) => f.write_str("Some(..)"),
+            None => f.write_str("None"),
+        }
+    }
+}
+
 pub fn spaces(size: u8) -> &'static str {
     const SPACES: &str = "                                                                                                                                                                                                                                                                ";
     &SPACES[..size as usize]
@@ -7,6 +50,59 @@ pub fn num_digits(i: usize) -> u8 {
     f64::log10(i as f64) as u8 + 1
 }
 
+/// Calculate the visual width of a character, handling tabs and Unicode width
+pub fn char_visual_width(c: char, position: usize, tab_len: u8) -> usize {
+    match c {
+        '\t' => {
+            if tab_len == 0 {
+                0
+            } else {
+                tab_len as usize - (position % tab_len as usize)
+            }
+        }
+        _ => c.width().unwrap_or(0),
+    }
+}
+
+/// Iterate over `(col, start_width, width)` of each character in the line as it is rendered. Tabs are expanded to the
+/// next tab stop in the same way as `DisplayTextBuilder`. When `mask` is set, every character is rendered with the mask
+/// character.
+pub fn display_cells(
+    line: &str,
+    tab_len: u8,
+    mask: Option<char>,
+) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+    let mask_width = mask.map(|m| m.width().unwrap_or(0));
+    let mut pos = 0;
+    line.chars().enumerate().map(move |(col, c)| {
+        let width = mask_width.unwrap_or_else(|| char_visual_width(c, pos, tab_len));
+        let start = pos;
+        pos += width;
+        (col, start, width)
+    })
+}
+
+/// Calculate the display width of the first `col` characters of the line.
+pub fn display_width(line: &str, col: usize, tab_len: u8, mask: Option<char>) -> usize {
+    display_cells(line, tab_len, mask)
+        .take(col)
+        .map(|(_, _, w)| w)
+        .sum()
+}
+
+/// Find the character column whose cell covers the display column `x`. When `x` is after the end of the line, the
+/// number of characters in the line is returned.
+pub fn col_at_display_width(line: &str, x: usize, tab_len: u8, mask: Option<char>) -> usize {
+    let mut len = 0;
+    for (col, start, width) in display_cells(line, tab_len, mask) {
+        if x < start + width {
+            return col;
+        }
+        len = col + 1;
+    }
+    len
+}
+
 #[derive(Debug, Clone)]
 pub struct Pos {
     pub row: usize,
@@ -19,3 +115,9 @@ impl Pos {
         Self { row, col, offset }
     }
 }
+
+// Lines of text from string slices for tests of the functions which take `&[String]`
+#[cfg(test)]
+pub(crate) fn lines(s: &[&str]) -> Vec<String> {
+    s.iter().map(|s| s.to_string()).collect()
+}
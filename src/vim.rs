@@ -0,0 +1,1867 @@
+#![cfg(feature = "vim")]
+
+use crate::cursor::CursorMove;
+use crate::input::{Input, Key};
+use crate::scroll::Scrolling;
+use crate::text_object::TextObject;
+use crate::textarea::TextArea;
+use crate::util::display_width;
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
+use std::sync::Arc;
+
+// Max number of repeats of `p` and `.` by a count. Larger counts would exhaust memory or take too long
+const MAX_REPEAT: usize = 10000;
+
+/// Operator of Vim which is applied to the text selected by a motion, a text object, or Visual mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VimOperator {
+    /// `d` operator
+    Delete,
+    /// `c` operator
+    Change,
+    /// `y` operator
+    Yank,
+    /// `>` operator
+    Indent,
+    /// `<` operator
+    Dedent,
+    /// `gq` operator
+    Format,
+}
+
+impl VimOperator {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'd' => Some(Self::Delete),
+            'c' => Some(Self::Change),
+            'y' => Some(Self::Yank),
+            '>' => Some(Self::Indent),
+            '<' => Some(Self::Dedent),
+            _ => None,
+        }
+    }
+}
+
+/// Mode of [`Vim`]. This is useful for changing the cursor style or showing the mode in a status line.
+///
+/// This type is marked as `#[non_exhaustive]` since more modes may be supported in the future.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VimMode {
+    /// Normal mode
+    Normal,
+    /// Insert mode. Key inputs are handled by [`TextArea::input`].
+    Insert,
+    /// Characterwise Visual mode started with `v`
+    Visual,
+    /// Linewise Visual mode started with `V`
+    VisualLine,
+    /// Blockwise Visual mode started with `Ctrl+V`
+    VisualBlock,
+    /// Operator-pending mode waiting for a motion or a text object after the operator
+    Operator(VimOperator),
+    /// Command-line mode started with `:`. See [`Vim::command_line`].
+    Command,
+}
+
+impl VimMode {
+    fn is_visual(self) -> bool {
+        matches!(self, Self::Visual | Self::VisualLine | Self::VisualBlock)
+    }
+}
+
+impl fmt::Display for VimMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Normal => write!(f, "NORMAL"),
+            Self::Insert => write!(f, "INSERT"),
+            Self::Visual => write!(f, "VISUAL"),
+            Self::VisualLine => write!(f, "VISUAL LINE"),
+            Self::VisualBlock => write!(f, "VISUAL BLOCK"),
+            Self::Operator(_) => write!(f, "OPERATOR"),
+            Self::Command => write!(f, "COMMAND"),
+        }
+    }
+}
+
+/// Handler of ex commands input in Command-line mode. It receives the command line without the leading `:` and
+/// returns if the text contents were modified or not. See [`Vim::set_ex_handler`].
+pub type ExCommandHandler = Arc<dyn Fn(&str, &mut TextArea<'_>) -> bool + Send + Sync>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    WordForward,
+    WordEnd,
+    WordBack,
+    Head,
+    FirstNonBlank,
+    End,
+    Top,
+    Bottom,
+    ParagraphForward,
+    ParagraphBack,
+    MatchingBracket,
+    Find { c: char, forward: bool, till: bool },
+}
+
+impl Motion {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            'h' => Some(Self::Left),
+            'l' | ' ' => Some(Self::Right),
+            'k' => Some(Self::Up),
+            'j' => Some(Self::Down),
+            'w' => Some(Self::WordForward),
+            'e' => Some(Self::WordEnd),
+            'b' => Some(Self::WordBack),
+            '0' => Some(Self::Head),
+            '^' => Some(Self::FirstNonBlank),
+            '$' => Some(Self::End),
+            'G' => Some(Self::Bottom),
+            '}' => Some(Self::ParagraphForward),
+            '{' => Some(Self::ParagraphBack),
+            '%' => Some(Self::MatchingBracket),
+            _ => None,
+        }
+    }
+
+    fn is_linewise(self) -> bool {
+        matches!(self, Self::Up | Self::Down | Self::Top | Self::Bottom)
+    }
+
+    fn is_inclusive(self) -> bool {
+        matches!(
+            self,
+            Self::WordEnd | Self::MatchingBracket | Self::Find { forward: true, .. }
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Register {
+    text: String,
+    linewise: bool,
+}
+
+fn line_len(textarea: &TextArea<'_>, row: usize) -> usize {
+    textarea.lines()[row].chars().count()
+}
+
+fn first_non_blank(line: &str) -> usize {
+    line.chars()
+        .position(|c| !c.is_whitespace())
+        .unwrap_or_else(|| line.chars().count())
+}
+
+fn select(textarea: &mut TextArea<'_>, start: (usize, usize), end: (usize, usize)) {
+    textarea.cancel_selection();
+    textarea.set_cursor(start);
+    textarea.start_selection();
+    textarea.set_cursor(end);
+}
+
+// Reflow the paragraphs in the lines. Like Vim, the cursor is moved to the first non-blank character of the last
+// formatted line
+fn format_lines(textarea: &mut TextArea<'_>, top: usize, bottom: usize) -> bool {
+    select(textarea, (top, 0), (bottom, usize::MAX));
+    let modified = textarea.reflow_paragraph();
+    textarea.cancel_selection();
+    let row = textarea.cursor().0;
+    let col = first_non_blank(&textarea.lines()[row]);
+    textarea.set_cursor((row, col));
+    modified
+}
+
+// Move the cursor by the motion. Returns false when the motion failed (e.g. the character to find was not found).
+fn move_by(textarea: &mut TextArea<'_>, motion: Motion, count: Option<usize>) -> bool {
+    let n = count.unwrap_or(1);
+    let (row, col) = textarea.cursor();
+    // Stop at the edge of the text so that a huge count doesn't loop forever
+    let repeat = |textarea: &mut TextArea<'_>, m| {
+        for _ in 0..n {
+            let prev = textarea.cursor();
+            textarea.move_cursor(m);
+            if textarea.cursor() == prev {
+                break;
+            }
+        }
+    };
+    match motion {
+        Motion::Left => textarea.set_cursor((row, col.saturating_sub(n))),
+        Motion::Right => textarea.set_cursor((row, col.saturating_add(n))),
+        Motion::Up => repeat(textarea, CursorMove::Up),
+        Motion::Down => repeat(textarea, CursorMove::Down),
+        Motion::WordForward => repeat(textarea, CursorMove::WordForward),
+        Motion::WordEnd => repeat(textarea, CursorMove::WordEnd),
+        Motion::WordBack => repeat(textarea, CursorMove::WordBack),
+        Motion::ParagraphForward => repeat(textarea, CursorMove::ParagraphForward),
+        Motion::ParagraphBack => repeat(textarea, CursorMove::ParagraphBack),
+        Motion::MatchingBracket => {
+            textarea.move_cursor(CursorMove::MatchingBracket);
+            if textarea.cursor() == (row, col) {
+                return false;
+            }
+        }
+        Motion::Head => textarea.set_cursor((row, 0)),
+        Motion::FirstNonBlank => {
+            let col = first_non_blank(&textarea.lines()[row]);
+            textarea.set_cursor((row, col));
+        }
+        Motion::End => {
+            let row = row.saturating_add(n - 1);
+            textarea.set_cursor((row, usize::MAX));
+        }
+        Motion::Top | Motion::Bottom => {
+            let row = match (motion, count) {
+                (_, Some(n)) => n - 1,
+                (Motion::Top, None) => 0,
+                _ => usize::MAX,
+            };
+            let row = row.min(textarea.lines().len() - 1);
+            let col = first_non_blank(&textarea.lines()[row]);
+            textarea.set_cursor((row, col));
+        }
+        Motion::Find { c, forward, till } => {
+            let chars: Vec<char> = textarea.lines()[row].chars().collect();
+            let mut pos = col;
+            for _ in 0..n {
+                let found = if forward {
+                    chars
+                        .iter()
+                        .skip(pos + 1)
+                        .position(|&x| x == c)
+                        .map(|i| pos + 1 + i)
+                } else {
+                    chars[..pos.min(chars.len())].iter().rposition(|&x| x == c)
+                };
+                pos = match found {
+                    Some(i) if till && forward => i - 1,
+                    Some(i) if till => i + 1,
+                    Some(i) => i,
+                    None => return false,
+                };
+            }
+            textarea.set_cursor((row, pos));
+        }
+    }
+    true
+}
+
+type Range = ((usize, usize), (usize, usize));
+
+// Find the range of the text object at the cursor. The end position is exclusive. The flag is true when the range
+// consists of whole lines.
+fn text_object(textarea: &TextArea<'_>, kind: char, around: bool) -> Option<(Range, bool)> {
+    let object = match (kind, around) {
+        ('w', false) => TextObject::InnerWord,
+        ('w', true) => TextObject::AroundWord,
+        ('W', false) => TextObject::InnerBigWord,
+        ('W', true) => TextObject::AroundBigWord,
+        ('s', false) => TextObject::InnerSentence,
+        ('s', true) => TextObject::AroundSentence,
+        ('p', false) => TextObject::InnerParagraph,
+        ('p', true) => TextObject::AroundParagraph,
+        ('t', false) => TextObject::InnerTag,
+        ('t', true) => TextObject::AroundTag,
+        ('"' | '\'' | '`', false) => TextObject::InnerQuote(kind),
+        ('"' | '\'' | '`', true) => TextObject::AroundQuote(kind),
+        ('b', _) => return text_object(textarea, '(', around),
+        ('B', _) => return text_object(textarea, '{', around),
+        ('(' | ')' | '[' | ']' | '{' | '}' | '<' | '>', false) => TextObject::InnerBracket(kind),
+        ('(' | ')' | '[' | ']' | '{' | '}' | '<' | '>', true) => TextObject::AroundBracket(kind),
+        _ => return None,
+    };
+    let (start, end) = textarea.text_object_range(object)?;
+    let linewise = start.1 == 0 && end.1 == 0 && start.0 < end.0;
+    if linewise {
+        let bottom = end.0 - 1;
+        Some((((start.0, 0), (bottom, line_len(textarea, bottom))), true))
+    } else {
+        Some(((start, end), false))
+    }
+}
+
+/// Modal Vim emulation layer over [`TextArea`]. This type is available when `vim` feature is enabled.
+///
+/// [`Vim::input`] handles key inputs like Vim and edits the textarea. It supports:
+///
+/// - Normal, Insert, Visual (`v`, `V`, `Ctrl+V`), Operator-pending, and Command-line (`:`) modes
+/// - Counts like `3w`, `2dd`, `d3w`
+/// - Operators `d`, `c`, `y`, `>`, `<` combined with motions (`h`, `j`, `k`, `l`, `w`, `e`, `b`, `0`, `^`, `$`, `gg`,
+///   `G`, `{`, `}`, `f`, `F`, `t`, `T`, `;`, `,`) and text objects (`iw`, `aw`, `i"`, `a'`, `i(`, `a{`, ...)
+/// - `x`, `X`, `s`, `S`, `D`, `C`, `Y`, `r`, `p`, `P`, `i`, `a`, `I`, `A`, `o`, `O`, `u`, `Ctrl+R`, and scrolling
+///   with `Ctrl+E`, `Ctrl+Y`, `Ctrl+D`, `Ctrl+U`, `Ctrl+F`, `Ctrl+B`
+/// - Repeating the last change with `.`
+/// - Registers `"a` to `"z` (appending with `"A` to `"Z`), the unnamed register, and the black hole register `"_`
+/// - Ex commands through the handler set with [`Vim::set_ex_handler`]. `:{number}` jumps to the line.
+///
+/// Key inputs in Insert mode are passed to [`TextArea::input`] so the keymap of the textarea is used.
+///
+/// ```
+/// use tui_textarea::{Input, Key, TextArea, Vim, VimMode};
+///
+/// let mut textarea = TextArea::from(["hello world", "goodbye"]);
+/// let mut vim = Vim::new();
+///
+/// let mut keys = |vim: &mut Vim, textarea: &mut TextArea, s: &str| {
+///     for c in s.chars() {
+///         let key = if c == '\x1b' { Key::Esc } else { Key::Char(c) };
+///         vim.input(textarea, Input { key, ..Default::default() });
+///     }
+/// };
+///
+/// keys(&mut vim, &mut textarea, "dw");
+/// assert_eq!(textarea.lines(), ["world", "goodbye"]);
+///
+/// keys(&mut vim, &mut textarea, "ciwhi\x1b");
+/// assert_eq!(textarea.lines(), ["hi", "goodbye"]);
+/// assert_eq!(vim.mode(), VimMode::Normal);
+///
+/// keys(&mut vim, &mut textarea, "j.");
+/// assert_eq!(textarea.lines(), ["hi", "hi"]);
+/// ```
+#[derive(Clone)]
+pub struct Vim {
+    mode: VimMode,
+    count: usize,
+    op_count: usize,
+    prefix: Option<char>,
+    register: Option<char>,
+    // Texts yanked linewise to the registers. Text in the register of the textarea is put linewise only while it is
+    // still the same as the text here
+    linewise: HashMap<char, String>,
+    visual_anchor: (usize, usize),
+    visual_cursor: (usize, usize),
+    last_find: Option<(char, bool, bool)>,
+    command_line: String,
+    ex_handler: Option<ExCommandHandler>,
+    change: Vec<Input>,
+    last_change: Vec<Input>,
+    changed: bool,
+    repeatable: bool,
+    replaying: bool,
+    grouping: bool,
+}
+
+impl Default for Vim {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Vim {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Vim")
+            .field("mode", &self.mode)
+            .field("count", &self.count)
+            .field("prefix", &self.prefix)
+            .field("register", &self.register)
+            .field("linewise", &self.linewise)
+            .field("command_line", &self.command_line)
+            .field("last_change", &self.last_change)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Vim {
+    /// Create a new Vim emulation layer in Normal mode.
+    pub fn new() -> Self {
+        Self {
+            mode: VimMode::Normal,
+            count: 0,
+            op_count: 0,
+            prefix: None,
+            register: None,
+            linewise: HashMap::new(),
+            visual_anchor: (0, 0),
+            visual_cursor: (0, 0),
+            last_find: None,
+            command_line: String::new(),
+            ex_handler: None,
+            change: vec![],
+            last_change: vec![],
+            changed: false,
+            repeatable: true,
+            replaying: false,
+            grouping: false,
+        }
+    }
+
+    /// Get the current mode.
+    /// ```
+    /// use tui_textarea::{Input, Key, TextArea, Vim, VimMode};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let mut vim = Vim::new();
+    /// assert_eq!(vim.mode(), VimMode::Normal);
+    ///
+    /// vim.input(&mut textarea, Input { key: Key::Char('i'), ..Default::default() });
+    /// assert_eq!(vim.mode(), VimMode::Insert);
+    /// assert_eq!(vim.mode().to_string(), "INSERT");
+    /// ```
+    pub fn mode(&self) -> VimMode {
+        self.mode
+    }
+
+    /// Get the command line being input in Command-line mode, without the leading `:`. `None` is returned in other
+    /// modes.
+    pub fn command_line(&self) -> Option<&str> {
+        (self.mode == VimMode::Command).then(|| self.command_line.as_str())
+    }
+
+    /// Set the handler of ex commands input in Command-line mode. The handler receives the command line without the
+    /// leading `:` when `Enter` key is input.
+    /// ```
+    /// use std::sync::atomic::{AtomicBool, Ordering};
+    /// use std::sync::Arc;
+    /// use tui_textarea::{Input, Key, TextArea, Vim};
+    ///
+    /// let mut textarea = TextArea::default();
+    /// let mut vim = Vim::new();
+    ///
+    /// let quit = Arc::new(AtomicBool::new(false));
+    /// let flag = quit.clone();
+    /// vim.set_ex_handler(move |cmd, _textarea| {
+    ///     if cmd == "q" {
+    ///         flag.store(true, Ordering::Relaxed);
+    ///     }
+    ///     false
+    /// });
+    ///
+    /// for key in [Key::Char(':'), Key::Char('q'), Key::Enter] {
+    ///     vim.input(&mut textarea, Input { key, ..Default::default() });
+    /// }
+    /// assert!(quit.load(Ordering::Relaxed));
+    /// ```
+    pub fn set_ex_handler<F>(&mut self, handler: F)
+    where
+        F: Fn(&str, &mut TextArea<'_>) -> bool + Send + Sync + 'static,
+    {
+        self.ex_handler = Some(Arc::new(handler));
+    }
+
+    /// Handle the key input like Vim and edit the textarea. This method returns if the input modified text contents
+    /// or not.
+    pub fn input(&mut self, textarea: &mut TextArea<'_>, input: impl Into<Input>) -> bool {
+        let input = input.into();
+        if input.key == Key::Null {
+            return false;
+        }
+        if !self.replaying {
+            self.change.push(input.clone());
+        }
+        // All edits by one command, including the text typed in Insert mode, are undone at once like Vim
+        if !self.grouping {
+            textarea.begin_history_group();
+            self.grouping = true;
+        }
+
+        let modified = match self.mode {
+            VimMode::Insert => self.insert_input(textarea, input),
+            VimMode::Command => self.command_input(textarea, input),
+            _ => self.normal_input(textarea, input),
+        };
+        self.changed |= modified;
+
+        // Remember the inputs of the last change for `.` when one command is completed
+        let at_rest = self.mode == VimMode::Normal
+            && self.prefix.is_none()
+            && self.count == 0
+            && self.op_count == 0
+            && self.register.is_none();
+        if at_rest && !self.replaying {
+            textarea.end_history_group();
+            self.grouping = false;
+            let change = mem::take(&mut self.change);
+            if self.changed && self.repeatable {
+                self.last_change = change;
+            }
+            self.changed = false;
+            self.repeatable = true;
+        }
+
+        modified
+    }
+
+    fn take_count(&mut self) -> Option<usize> {
+        let count = match (self.op_count, self.count) {
+            (0, 0) => None,
+            (a, b) => Some(a.max(1).saturating_mul(b.max(1))),
+        };
+        self.count = 0;
+        self.op_count = 0;
+        count
+    }
+
+    fn reset_pending(&mut self) {
+        self.count = 0;
+        self.op_count = 0;
+        self.prefix = None;
+        self.register = None;
+    }
+
+    fn insert_input(&mut self, textarea: &mut TextArea<'_>, input: Input) -> bool {
+        if input.key == Key::Esc {
+            self.mode = VimMode::Normal;
+            textarea.cancel_selection();
+            let (row, col) = textarea.cursor();
+            textarea.set_cursor((row, col.saturating_sub(1)));
+            return false;
+        }
+        textarea.input(input)
+    }
+
+    fn command_input(&mut self, textarea: &mut TextArea<'_>, input: Input) -> bool {
+        self.repeatable = false;
+        match input.key {
+            Key::Esc => {
+                self.mode = VimMode::Normal;
+                self.command_line.clear();
+                false
+            }
+            Key::Backspace => {
+                if self.command_line.pop().is_none() {
+                    self.mode = VimMode::Normal;
+                }
+                false
+            }
+            Key::Enter => {
+                self.mode = VimMode::Normal;
+                let cmd = mem::take(&mut self.command_line);
+                let cmd = cmd.trim();
+                if let Ok(line) = cmd.parse::<usize>() {
+                    let row = line.saturating_sub(1).min(textarea.lines().len() - 1);
+                    let col = first_non_blank(&textarea.lines()[row]);
+                    textarea.set_cursor((row, col));
+                    return false;
+                }
+                match self.ex_handler.clone() {
+                    Some(handler) => handler(cmd, textarea),
+                    None => false,
+                }
+            }
+            Key::Char(c) if !input.ctrl && !input.alt => {
+                self.command_line.push(c);
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn normal_input(&mut self, textarea: &mut TextArea<'_>, input: Input) -> bool {
+        if let Some(prefix) = self.prefix.take() {
+            return self.prefixed_input(textarea, prefix, input);
+        }
+
+        if input.key == Key::Esc {
+            self.reset_pending();
+            if self.mode.is_visual() {
+                self.exit_visual(textarea);
+            }
+            self.mode = VimMode::Normal;
+            return false;
+        }
+
+        if input.ctrl {
+            return self.ctrl_input(textarea, input.key);
+        }
+
+        let c = match input.key {
+            Key::Char(c) => c,
+            Key::Left | Key::Backspace => 'h',
+            Key::Right => 'l',
+            Key::Up => 'k',
+            Key::Down => 'j',
+            Key::Home => '0',
+            Key::End => '$',
+            Key::Delete => 'x',
+            Key::MouseScrollDown | Key::MouseScrollUp => {
+                if self.mode != VimMode::VisualLine {
+                    textarea.input(input);
+                }
+                return false;
+            }
+            _ => {
+                self.reset_pending();
+                return false;
+            }
+        };
+
+        // Counts
+        if let Some(d) = c.to_digit(10) {
+            if d != 0 || self.count > 0 {
+                self.count = self.count.saturating_mul(10).saturating_add(d as usize);
+                return false;
+            }
+        }
+
+        if let Some(m) = Motion::from_char(c) {
+            let count = self.take_count();
+            return self.motion(textarea, m, count);
+        }
+
+        match c {
+            ';' | ',' => {
+                let count = self.take_count();
+                return match self.last_find {
+                    Some((target, forward, till)) => {
+                        let forward = forward == (c == ';');
+                        let motion = Motion::Find {
+                            c: target,
+                            forward,
+                            till,
+                        };
+                        self.motion(textarea, motion, count)
+                    }
+                    None => false,
+                };
+            }
+            'g' | 'f' | 'F' | 't' | 'T' | '"' => {
+                self.prefix = Some(c);
+                return false;
+            }
+            // `gqq` formats lines as well as `gqgq`
+            'q' if self.mode == VimMode::Operator(VimOperator::Format) => {
+                return self.operator(textarea, VimOperator::Format);
+            }
+            'i' | 'a' if self.mode != VimMode::Normal => {
+                self.prefix = Some(c);
+                return false;
+            }
+            _ => {}
+        }
+
+        if let Some(op) = VimOperator::from_char(c) {
+            return self.operator(textarea, op);
+        }
+
+        match self.mode {
+            VimMode::Normal => self.normal_command(textarea, c),
+            VimMode::Visual | VimMode::VisualLine | VimMode::VisualBlock => {
+                self.visual_command(textarea, c)
+            }
+            _ => {
+                // Unknown motion cancels the pending operator
+                self.reset_pending();
+                self.mode = VimMode::Normal;
+                false
+            }
+        }
+    }
+
+    fn ctrl_input(&mut self, textarea: &mut TextArea<'_>, key: Key) -> bool {
+        let count = self.take_count();
+        let n = count.unwrap_or(1);
+        match key {
+            Key::Char('r') if self.mode == VimMode::Normal => {
+                self.repeatable = false;
+                let mut modified = false;
+                for _ in 0..n {
+                    if !textarea.redo() {
+                        break;
+                    }
+                    modified = true;
+                }
+                return modified;
+            }
+            Key::Char('v') if self.mode == VimMode::Normal || self.mode.is_visual() => {
+                self.enter_visual(textarea, VimMode::VisualBlock);
+                return false;
+            }
+            _ => {}
+        }
+
+        let scroll = match key {
+            Key::Char('e') => Scrolling::Delta {
+                rows: n.min(i16::MAX as usize) as i16,
+                cols: 0,
+            },
+            Key::Char('y') => Scrolling::Delta {
+                rows: -(n.min(i16::MAX as usize) as i16),
+                cols: 0,
+            },
+            Key::Char('d') => Scrolling::HalfPageDown,
+            Key::Char('u') => Scrolling::HalfPageUp,
+            Key::Char('f') => Scrolling::PageDown,
+            Key::Char('b') => Scrolling::PageUp,
+            _ => {
+                self.reset_pending();
+                return false;
+            }
+        };
+        if self.mode == VimMode::VisualLine {
+            textarea.cancel_selection();
+            textarea.set_cursor(self.visual_cursor);
+            textarea.scroll(scroll);
+            self.visual_cursor = textarea.cursor();
+            self.highlight_lines(textarea);
+        } else {
+            textarea.scroll(scroll);
+        }
+        false
+    }
+
+    fn prefixed_input(&mut self, textarea: &mut TextArea<'_>, prefix: char, input: Input) -> bool {
+        let c = match input.key {
+            Key::Char(c) if !input.ctrl && !input.alt => c,
+            _ => {
+                self.reset_pending();
+                if let VimMode::Operator(_) = self.mode {
+                    self.mode = VimMode::Normal;
+                }
+                return false;
+            }
+        };
+        match prefix {
+            '"' => {
+                if c.is_ascii_alphabetic() || c == '"' || c == '_' {
+                    self.register = Some(c);
+                }
+                false
+            }
+            'g' if c == 'g' => {
+                let count = self.take_count();
+                self.motion(textarea, Motion::Top, count)
+            }
+            'g' if c == 'q' => self.operator(textarea, VimOperator::Format),
+            'f' | 'F' | 't' | 'T' => {
+                let forward = prefix == 'f' || prefix == 't';
+                let till = prefix == 't' || prefix == 'T';
+                self.last_find = Some((c, forward, till));
+                let count = self.take_count();
+                self.motion(textarea, Motion::Find { c, forward, till }, count)
+            }
+            'r' => {
+                let n = self.take_count().unwrap_or(1);
+                let (row, col) = textarea.cursor();
+                if col.saturating_add(n) > line_len(textarea, row) {
+                    return false;
+                }
+                // Replace the selected characters so that the replaced text is not yanked
+                select(textarea, (row, col), (row, col + n));
+                textarea.insert_str(c.to_string().repeat(n));
+                textarea.set_cursor((row, col + n - 1));
+                true
+            }
+            'i' | 'a' => self.text_object(textarea, c, prefix == 'a'),
+            _ => {
+                self.reset_pending();
+                if let VimMode::Operator(_) = self.mode {
+                    self.mode = VimMode::Normal;
+                }
+                false
+            }
+        }
+    }
+
+    fn motion(&mut self, textarea: &mut TextArea<'_>, motion: Motion, count: Option<usize>) -> bool {
+        match self.mode {
+            VimMode::Operator(op) => {
+                self.mode = VimMode::Normal;
+                self.operate_motion(textarea, op, motion, count)
+            }
+            VimMode::VisualLine => {
+                textarea.cancel_selection();
+                textarea.set_cursor(self.visual_cursor);
+                move_by(textarea, motion, count);
+                self.visual_cursor = textarea.cursor();
+                self.highlight_lines(textarea);
+                false
+            }
+            _ => {
+                if self.mode == VimMode::Normal {
+                    self.register = None;
+                }
+                move_by(textarea, motion, count);
+                false
+            }
+        }
+    }
+
+    fn text_object(&mut self, textarea: &mut TextArea<'_>, kind: char, around: bool) -> bool {
+        let range = text_object(textarea, kind, around);
+        match self.mode {
+            VimMode::Operator(op) => {
+                self.mode = VimMode::Normal;
+                self.count = 0;
+                self.op_count = 0;
+                match range {
+                    Some(((start, end), true)) => self.operate_lines(textarea, op, start.0, end.0, start.1),
+                    Some(((start, end), false)) => self.operate_range(textarea, op, start, end),
+                    None => {
+                        self.register = None;
+                        false
+                    }
+                }
+            }
+            _ => {
+                if let Some(((start, end), _)) = range {
+                    if start < end {
+                        let end = if end.1 > 0 { (end.0, end.1 - 1) } else { end };
+                        self.mode = VimMode::Visual;
+                        self.visual_anchor = start;
+                        select(textarea, start, end);
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    fn operator(&mut self, textarea: &mut TextArea<'_>, op: VimOperator) -> bool {
+        match self.mode {
+            VimMode::Normal => {
+                self.op_count = mem::take(&mut self.count);
+                self.mode = VimMode::Operator(op);
+                false
+            }
+            VimMode::Operator(pending) if pending == op => {
+                // `dd`, `cc`, `yy`, `>>`, `<<`, `gqq`
+                self.mode = VimMode::Normal;
+                let n = self.take_count().unwrap_or(1);
+                let (row, col) = textarea.cursor();
+                self.operate_lines(textarea, op, row, row.saturating_add(n - 1), col)
+            }
+            VimMode::Operator(_) => {
+                self.reset_pending();
+                self.mode = VimMode::Normal;
+                false
+            }
+            _ => self.operate_visual(textarea, op),
+        }
+    }
+
+    fn normal_command(&mut self, textarea: &mut TextArea<'_>, c: char) -> bool {
+        let (row, col) = textarea.cursor();
+        let len = line_len(textarea, row);
+        match c {
+            'i' => self.start_insert(textarea, (row, col)),
+            'a' => self.start_insert(textarea, (row, col + 1)),
+            'I' => {
+                let col = first_non_blank(&textarea.lines()[row]);
+                self.start_insert(textarea, (row, col))
+            }
+            'A' => self.start_insert(textarea, (row, len)),
+            'o' | 'O' => {
+                self.reset_pending();
+                textarea.cancel_selection();
+                if c == 'o' {
+                    textarea.set_cursor((row, len));
+                    textarea.insert_newline();
+                } else {
+                    textarea.set_cursor((row, 0));
+                    textarea.insert_newline();
+                    textarea.move_cursor(CursorMove::Up);
+                }
+                self.mode = VimMode::Insert;
+                true
+            }
+            'x' | 's' => {
+                let n = self.take_count().unwrap_or(1);
+                if len == 0 {
+                    if c == 's' {
+                        self.mode = VimMode::Insert;
+                    }
+                    self.register = None;
+                    return false;
+                }
+                let op = if c == 'x' {
+                    VimOperator::Delete
+                } else {
+                    VimOperator::Change
+                };
+                self.operate_range(textarea, op, (row, col), (row, col.saturating_add(n)))
+            }
+            'X' => {
+                let n = self.take_count().unwrap_or(1);
+                if col == 0 {
+                    self.register = None;
+                    return false;
+                }
+                self.operate_range(textarea, VimOperator::Delete, (row, col - n.min(col)), (row, col))
+            }
+            'D' | 'C' => {
+                self.take_count();
+                let op = if c == 'D' {
+                    VimOperator::Delete
+                } else {
+                    VimOperator::Change
+                };
+                self.operate_range(textarea, op, (row, col), (row, len))
+            }
+            'S' | 'Y' => {
+                let n = self.take_count().unwrap_or(1);
+                let bottom = row.saturating_add(n - 1).min(textarea.lines().len() - 1);
+                let op = if c == 'S' {
+                    VimOperator::Change
+                } else {
+                    VimOperator::Yank
+                };
+                self.operate_lines(textarea, op, row, bottom, col)
+            }
+            'p' | 'P' => {
+                let n = self.take_count().unwrap_or(1);
+                match self.take_register(textarea) {
+                    Some(reg) => self.put(textarea, &reg, c == 'P', n),
+                    None => false,
+                }
+            }
+            'u' => {
+                self.repeatable = false;
+                let n = self.take_count().unwrap_or(1);
+                let mut modified = false;
+                for _ in 0..n {
+                    if !textarea.undo() {
+                        break;
+                    }
+                    modified = true;
+                }
+                modified
+            }
+            'r' => {
+                self.prefix = Some('r');
+                false
+            }
+            'v' => {
+                self.reset_pending();
+                self.enter_visual(textarea, VimMode::Visual);
+                false
+            }
+            'V' => {
+                self.reset_pending();
+                self.enter_visual(textarea, VimMode::VisualLine);
+                false
+            }
+            ':' => {
+                self.reset_pending();
+                self.mode = VimMode::Command;
+                self.command_line.clear();
+                false
+            }
+            '.' => {
+                let n = self.take_count().unwrap_or(1).min(MAX_REPEAT);
+                self.register = None;
+                self.repeatable = false;
+                let change = self.last_change.clone();
+                self.replaying = true;
+                let mut modified = false;
+                for _ in 0..n {
+                    let mut repeated = false;
+                    for input in change.iter() {
+                        repeated |= self.input(textarea, input.clone());
+                    }
+                    if !repeated {
+                        break;
+                    }
+                    modified = true;
+                }
+                self.replaying = false;
+                modified
+            }
+            _ => {
+                self.reset_pending();
+                false
+            }
+        }
+    }
+
+    fn visual_command(&mut self, textarea: &mut TextArea<'_>, c: char) -> bool {
+        self.count = 0;
+        match c {
+            'x' => self.operate_visual(textarea, VimOperator::Delete),
+            's' => self.operate_visual(textarea, VimOperator::Change),
+            'v' => {
+                self.enter_visual(textarea, VimMode::Visual);
+                false
+            }
+            'V' => {
+                self.enter_visual(textarea, VimMode::VisualLine);
+                false
+            }
+            'o' => {
+                if self.mode == VimMode::VisualLine {
+                    mem::swap(&mut self.visual_anchor, &mut self.visual_cursor);
+                    self.highlight_lines(textarea);
+                } else {
+                    let cursor = textarea.cursor();
+                    let anchor = mem::replace(&mut self.visual_anchor, cursor);
+                    let rect = textarea.rect_selection;
+                    select(textarea, cursor, anchor);
+                    textarea.rect_selection = rect;
+                }
+                false
+            }
+            'p' | 'P' => match self.take_register(textarea) {
+                Some(mut reg) => {
+                    // Replace the selection with the register. The replaced text is stored in the unnamed register
+                    let mut before = !reg.linewise;
+                    if self.mode == VimMode::VisualLine {
+                        reg.linewise = true;
+                        let bottom = self.visual_anchor.0.max(self.visual_cursor.0);
+                        let top = self.visual_anchor.0.min(self.visual_cursor.0);
+                        before = top == 0 || bottom + 1 < textarea.lines().len();
+                    }
+                    self.operate_visual(textarea, VimOperator::Delete);
+                    self.put(textarea, &reg, before, 1)
+                }
+                None => false,
+            },
+            ':' => {
+                self.exit_visual(textarea);
+                self.mode = VimMode::Command;
+                self.command_line.clear();
+                false
+            }
+            _ => false,
+        }
+    }
+
+    fn start_insert(&mut self, textarea: &mut TextArea<'_>, pos: (usize, usize)) -> bool {
+        self.reset_pending();
+        textarea.cancel_selection();
+        textarea.set_cursor(pos);
+        self.mode = VimMode::Insert;
+        false
+    }
+
+    fn enter_visual(&mut self, textarea: &mut TextArea<'_>, mode: VimMode) {
+        if self.mode == mode {
+            self.exit_visual(textarea);
+            return;
+        }
+        let cursor = if self.mode == VimMode::VisualLine {
+            self.visual_cursor
+        } else {
+            textarea.cursor()
+        };
+        if !self.mode.is_visual() {
+            self.visual_anchor = cursor;
+        }
+        self.mode = mode;
+        match mode {
+            VimMode::VisualLine => {
+                self.visual_cursor = cursor;
+                self.highlight_lines(textarea);
+            }
+            _ => {
+                select(textarea, self.visual_anchor, cursor);
+                textarea.rect_selection = mode == VimMode::VisualBlock;
+            }
+        }
+    }
+
+    fn exit_visual(&mut self, textarea: &mut TextArea<'_>) {
+        let cursor = if self.mode == VimMode::VisualLine {
+            self.visual_cursor
+        } else {
+            textarea.cursor()
+        };
+        textarea.cancel_selection();
+        textarea.set_cursor(cursor);
+        self.mode = VimMode::Normal;
+    }
+
+    // Select whole lines between the anchor and the cursor in linewise Visual mode
+    fn highlight_lines(&self, textarea: &mut TextArea<'_>) {
+        let (anchor, cursor) = (self.visual_anchor.0, self.visual_cursor.0);
+        if anchor <= cursor {
+            select(textarea, (anchor, 0), (cursor, usize::MAX));
+        } else {
+            select(textarea, (anchor, usize::MAX), (cursor, 0));
+        }
+    }
+
+    fn operate_motion(
+        &mut self,
+        textarea: &mut TextArea<'_>,
+        op: VimOperator,
+        motion: Motion,
+        count: Option<usize>,
+    ) -> bool {
+        let start = textarea.cursor();
+        let mut motion = motion;
+        if op == VimOperator::Change && motion == Motion::WordForward {
+            // `cw` works like `ce` when the cursor is on a word
+            let on_space = textarea.lines()[start.0]
+                .chars()
+                .nth(start.1)
+                .map_or(true, char::is_whitespace);
+            if !on_space {
+                motion = Motion::WordEnd;
+            }
+        }
+
+        if !move_by(textarea, motion, count) {
+            self.register = None;
+            return false;
+        }
+        let end = textarea.cursor();
+
+        if motion.is_linewise() {
+            return self.operate_lines(textarea, op, start.0.min(end.0), start.0.max(end.0), start.1);
+        }
+
+        let (s, mut e) = if start <= end {
+            (start, end)
+        } else {
+            (end, start)
+        };
+        if motion == Motion::WordForward && e.0 > s.0 {
+            // Like Vim, `dw` at the last word of a line does not join the next line
+            let before: String = textarea.lines()[e.0].chars().take(e.1).collect();
+            if before.chars().all(char::is_whitespace) {
+                e = (e.0 - 1, line_len(textarea, e.0 - 1));
+            }
+        }
+        if motion.is_inclusive() {
+            e.1 += 1;
+        }
+        self.operate_range(textarea, op, s, e)
+    }
+
+    // Apply the operator to the characterwise range. The end position is exclusive.
+    fn operate_range(
+        &mut self,
+        textarea: &mut TextArea<'_>,
+        op: VimOperator,
+        start: (usize, usize),
+        end: (usize, usize),
+    ) -> bool {
+        match op {
+            VimOperator::Indent | VimOperator::Dedent => {
+                self.register = None;
+                return self.shift_lines(textarea, op, start.0, end.0);
+            }
+            VimOperator::Format => {
+                self.register = None;
+                return format_lines(textarea, start.0, end.0);
+            }
+            VimOperator::Yank => {
+                select(textarea, start, end);
+                textarea.copy();
+                textarea.set_cursor(start);
+                self.store_register(textarea, None, false);
+                return false;
+            }
+            VimOperator::Delete | VimOperator::Change => {}
+        }
+
+        let prev = textarea.yank_text();
+        select(textarea, start, end);
+        let modified = textarea.cut();
+        textarea.cancel_selection();
+        if modified {
+            self.store_register(textarea, Some(prev), false);
+        } else {
+            self.register = None;
+        }
+        if op == VimOperator::Change {
+            self.mode = VimMode::Insert;
+        }
+        modified
+    }
+
+    fn operate_lines(
+        &mut self,
+        textarea: &mut TextArea<'_>,
+        op: VimOperator,
+        top: usize,
+        bottom: usize,
+        col: usize,
+    ) -> bool {
+        let last = textarea.lines().len() - 1;
+        let bottom = bottom.min(last);
+        let text = textarea.lines()[top..=bottom].join("\n");
+
+        match op {
+            VimOperator::Indent | VimOperator::Dedent => {
+                self.register = None;
+                self.shift_lines(textarea, op, top, bottom)
+            }
+            VimOperator::Format => {
+                self.register = None;
+                format_lines(textarea, top, bottom)
+            }
+            VimOperator::Yank => {
+                textarea.cancel_selection();
+                textarea.set_cursor((top, col));
+                self.put_register(textarea, text, true);
+                false
+            }
+            VimOperator::Delete => {
+                let prev = textarea.yank_text();
+                let (start, end) = if bottom < last {
+                    ((top, 0), (bottom + 1, 0))
+                } else if top > 0 {
+                    ((top - 1, usize::MAX), (bottom, usize::MAX))
+                } else {
+                    ((0, 0), (bottom, usize::MAX))
+                };
+                select(textarea, start, end);
+                let modified = textarea.cut();
+                textarea.cancel_selection();
+                let row = top.min(textarea.lines().len() - 1);
+                let col = first_non_blank(&textarea.lines()[row]);
+                textarea.set_cursor((row, col));
+                self.restore_or_put(textarea, prev, text, true);
+                modified
+            }
+            VimOperator::Change => {
+                let prev = textarea.yank_text();
+                let indent = first_non_blank(&textarea.lines()[top]);
+                select(textarea, (top, indent), (bottom, usize::MAX));
+                let modified = textarea.cut();
+                textarea.cancel_selection();
+                self.restore_or_put(textarea, prev, text, true);
+                self.mode = VimMode::Insert;
+                modified
+            }
+        }
+    }
+
+    fn operate_visual(&mut self, textarea: &mut TextArea<'_>, op: VimOperator) -> bool {
+        let anchor = self.visual_anchor;
+        match self.mode {
+            VimMode::VisualLine => {
+                let cursor = self.visual_cursor;
+                textarea.cancel_selection();
+                self.mode = VimMode::Normal;
+                let (top, bottom) = (anchor.0.min(cursor.0), anchor.0.max(cursor.0));
+                let col = if anchor.0 <= cursor.0 { anchor.1 } else { cursor.1 };
+                self.operate_lines(textarea, op, top, bottom, col)
+            }
+            VimMode::Visual => {
+                let cursor = textarea.cursor();
+                textarea.cancel_selection();
+                self.mode = VimMode::Normal;
+                let (s, mut e) = if anchor <= cursor {
+                    (anchor, cursor)
+                } else {
+                    (cursor, anchor)
+                };
+                // Vim's selection is inclusive
+                e.1 += 1;
+                self.operate_range(textarea, op, s, e)
+            }
+            _ => {
+                // Blockwise Visual mode
+                let cursor = textarea.cursor();
+                self.mode = VimMode::Normal;
+                let (top, bottom) = (anchor.0.min(cursor.0), anchor.0.max(cursor.0));
+                match op {
+                    VimOperator::Indent | VimOperator::Dedent => {
+                        textarea.cancel_selection();
+                        self.register = None;
+                        return self.shift_lines(textarea, op, top, bottom);
+                    }
+                    VimOperator::Format => {
+                        self.register = None;
+                        return format_lines(textarea, top, bottom);
+                    }
+                    _ => {}
+                }
+
+                // Include the character at the right edge of the rectangle
+                let tab = textarea.tab_length();
+                let anchor_x = display_width(&textarea.lines()[anchor.0], anchor.1, tab, None);
+                let cursor_x = display_width(&textarea.lines()[cursor.0], cursor.1, tab, None);
+                if cursor_x >= anchor_x {
+                    textarea.set_cursor((cursor.0, cursor.1 + 1));
+                } else {
+                    let col = (anchor.1 + 1).min(line_len(textarea, anchor.0));
+                    textarea.selection_start = Some((anchor.0, col));
+                }
+                let top_left = match textarea.rect_selection_positions() {
+                    Some(positions) => (positions[0].0.row, positions[0].0.col),
+                    None => cursor,
+                };
+
+                let prev = textarea.yank_text();
+                let modified = if op == VimOperator::Yank {
+                    textarea.copy();
+                    false
+                } else {
+                    textarea.cut()
+                };
+                textarea.cancel_selection();
+                textarea.set_cursor(top_left);
+                self.store_register(textarea, Some(prev), false);
+                if op == VimOperator::Change {
+                    self.mode = VimMode::Insert;
+                }
+                modified
+            }
+        }
+    }
+
+    fn shift_lines(&mut self, textarea: &mut TextArea<'_>, op: VimOperator, top: usize, bottom: usize) -> bool {
+        let bottom = bottom.min(textarea.lines().len() - 1);
+        select(textarea, (top, 0), (bottom, usize::MAX));
+        let modified = if op == VimOperator::Indent {
+            textarea.indent_lines()
+        } else {
+            textarea.dedent_lines()
+        };
+        textarea.cancel_selection();
+        let col = first_non_blank(&textarea.lines()[top]);
+        textarea.set_cursor((top, col));
+        modified
+    }
+
+    // Store the text yanked by the textarea to the selected register. When `prev` is given and the register is the
+    // black hole register, the yank buffer of the textarea is restored to `prev`.
+    fn store_register(&mut self, textarea: &mut TextArea<'_>, prev: Option<String>, linewise: bool) {
+        let text = textarea.yank_text();
+        match prev {
+            Some(prev) => self.restore_or_put(textarea, prev, text, linewise),
+            None => self.put_register(textarea, text, linewise),
+        }
+    }
+
+    fn restore_or_put(&mut self, textarea: &mut TextArea<'_>, prev: String, text: String, linewise: bool) {
+        if self.register == Some('_') {
+            self.register = None;
+            textarea.set_yank_text(prev);
+        } else {
+            self.put_register(textarea, text, linewise);
+        }
+    }
+
+    fn put_register(&mut self, textarea: &mut TextArea<'_>, text: String, linewise: bool) {
+        let name = self.register.take().unwrap_or('"');
+        if name == '_' {
+            return;
+        }
+        let lower = name.to_ascii_lowercase();
+        let (text, linewise) = match textarea.register(lower) {
+            Some(prev) if name.is_ascii_uppercase() => {
+                if self.is_linewise(lower, &prev) || linewise {
+                    (format!("{}\n{}", prev, text), true)
+                } else {
+                    (prev + &text, false)
+                }
+            }
+            _ => (text, linewise),
+        };
+        if lower.is_ascii_lowercase() {
+            textarea.set_register(lower, text.clone());
+            self.set_linewise(lower, &text, linewise);
+        }
+        textarea.set_yank_text(text.clone());
+        self.set_linewise('"', &text, linewise);
+    }
+
+    fn take_register(&mut self, textarea: &TextArea<'_>) -> Option<Register> {
+        let name = self.register.take().unwrap_or('"').to_ascii_lowercase();
+        if name == '_' {
+            return None;
+        }
+        // The registers may be updated by the textarea directly
+        let text = textarea.register(name).filter(|t| !t.is_empty())?;
+        let linewise = self.is_linewise(name, &text);
+        Some(Register { text, linewise })
+    }
+
+    fn is_linewise(&self, name: char, text: &str) -> bool {
+        self.linewise.get(&name).map_or(false, |t| t == text)
+    }
+
+    fn set_linewise(&mut self, name: char, text: &str, linewise: bool) {
+        if linewise {
+            self.linewise.insert(name, text.to_string());
+        } else {
+            self.linewise.remove(&name);
+        }
+    }
+
+    fn put(&mut self, textarea: &mut TextArea<'_>, reg: &Register, before: bool, count: usize) -> bool {
+        if reg.text.is_empty() {
+            return false;
+        }
+        textarea.cancel_selection();
+        let count = count.min(MAX_REPEAT);
+        let (row, col) = textarea.cursor();
+        if reg.linewise {
+            let text = vec![reg.text.as_str(); count].join("\n");
+            let row = if before {
+                textarea.set_cursor((row, 0));
+                textarea.insert_str(format!("{}\n", text));
+                row
+            } else {
+                textarea.set_cursor((row, usize::MAX));
+                textarea.insert_str(format!("\n{}", text));
+                row + 1
+            };
+            let col = first_non_blank(&textarea.lines()[row]);
+            textarea.set_cursor((row, col));
+        } else {
+            if !before && col < line_len(textarea, row) {
+                textarea.set_cursor((row, col + 1));
+            }
+            textarea.insert_str(reg.text.repeat(count));
+            let (row, col) = textarea.cursor();
+            textarea.set_cursor((row, col.saturating_sub(1)));
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keys(vim: &mut Vim, textarea: &mut TextArea<'_>, s: &str) -> bool {
+        let mut modified = false;
+        for c in s.chars() {
+            let key = match c {
+                '\x1b' => Key::Esc,
+                '\n' => Key::Enter,
+                c => Key::Char(c),
+            };
+            modified |= vim.input(textarea, Input { key, ..Default::default() });
+        }
+        modified
+    }
+
+    fn ctrl(vim: &mut Vim, textarea: &mut TextArea<'_>, c: char) -> bool {
+        let input = Input {
+            key: Key::Char(c),
+            ctrl: true,
+            ..Default::default()
+        };
+        vim.input(textarea, input)
+    }
+
+    fn run_textarea(lines: &[&str], cursor: (usize, usize), s: &str) -> (TextArea<'static>, Vim) {
+        let mut t = TextArea::from(lines.iter().map(|l| l.to_string()));
+        t.set_cursor(cursor);
+        let mut vim = Vim::new();
+        keys(&mut vim, &mut t, s);
+        (t, vim)
+    }
+
+    fn run(lines: &[&str], cursor: (usize, usize), s: &str) -> (Vec<String>, (usize, usize), Vim) {
+        let (t, vim) = run_textarea(lines, cursor, s);
+        (t.lines().to_vec(), t.cursor(), vim)
+    }
+
+    #[test]
+    fn motions_with_counts() {
+        for (keys, cursor) in [
+            ("l", (0, 1)),
+            ("3l", (0, 3)),
+            ("w", (0, 4)),
+            ("2w", (0, 8)),
+            ("e", (0, 2)),
+            ("$", (0, 11)),
+            ("j", (1, 0)),
+            ("G", (2, 2)),
+            ("2G", (1, 0)),
+            ("jgg", (0, 0)),
+            ("fc", (0, 8)),
+            ("tc", (0, 7)),
+            ("fa;", (0, 9)),
+            ("$Fa", (0, 9)),
+            ("$Fa,", (0, 9)),
+            ("12j", (2, 0)),
+        ] {
+            let (_, c, _) = run(&["foo bar cab", "x", "  z"], (0, 0), keys);
+            assert_eq!(c, cursor, "{:?}", keys);
+        }
+    }
+
+    #[test]
+    fn matching_bracket_motion() {
+        let text = ["f(a, [b]) {", "  x", "}"];
+        for (cursor, keys, want) in [
+            ((0, 1), "%", (0, 8)),
+            ((0, 1), "%%", (0, 1)),
+            ((0, 10), "%", (2, 0)),
+            ((0, 3), "%", (0, 3)),
+        ] {
+            let (_, c, _) = run(&text, cursor, keys);
+            assert_eq!(c, want, "{:?} at {:?}", keys, cursor);
+        }
+
+        let (l, c, _) = run(&text, (0, 1), "d%");
+        assert_eq!(l, ["f {", "  x", "}"]);
+        assert_eq!(c, (0, 1));
+        let (l, _, _) = run(&text, (2, 0), "d%");
+        assert_eq!(l, ["f(a, [b]) "]);
+    }
+
+    #[test]
+    fn operators_with_motions() {
+        for (keys, lines, cursor) in [
+            ("dw", &["bar baz", "qux"][..], (0, 0)),
+            ("d2w", &["baz", "qux"][..], (0, 0)),
+            ("2dw", &["baz", "qux"][..], (0, 0)),
+            ("de", &[" bar baz", "qux"][..], (0, 0)),
+            ("d$", &["", "qux"][..], (0, 0)),
+            ("wD", &["foo ", "qux"][..], (0, 4)),
+            ("dj", &[""][..], (0, 0)),
+            ("wwdw", &["foo bar ", "qux"][..], (0, 8)),
+            ("dfa", &["r baz", "qux"][..], (0, 0)),
+            ("dta", &["ar baz", "qux"][..], (0, 0)),
+            ("x", &["oo bar baz", "qux"][..], (0, 0)),
+            ("3x", &[" bar baz", "qux"][..], (0, 0)),
+            ("$X", &["foo bar ba", "qux"][..], (0, 10)),
+            ("dd", &["qux"][..], (0, 0)),
+            ("jdd", &["foo bar baz"][..], (0, 0)),
+            ("2dd", &[""][..], (0, 0)),
+            ("3dd", &[""][..], (0, 0)),
+            ("rx", &["xoo bar baz", "qux"][..], (0, 0)),
+            ("3rx", &["xxx bar baz", "qux"][..], (0, 2)),
+        ] {
+            let (l, c, _) = run(&["foo bar baz", "qux"], (0, 0), keys);
+            assert_eq!(l, lines, "{:?}", keys);
+            assert_eq!(c, cursor, "{:?}", keys);
+        }
+    }
+
+    #[test]
+    fn change_and_insert() {
+        for (keys, lines) in [
+            ("cwxy\x1b", &["xy bar"][..]),
+            ("ccxy\x1b", &["xy"][..]),
+            ("Cxy\x1b", &["xy"][..]),
+            ("wc$xy\x1b", &["foo xy"][..]),
+            ("sxy\x1b", &["xyoo bar"][..]),
+            ("ixy\x1b", &["xyfoo bar"][..]),
+            ("axy\x1b", &["fxyoo bar"][..]),
+            ("Axy\x1b", &["foo barxy"][..]),
+            ("wIxy\x1b", &["xyfoo bar"][..]),
+            ("oxy\x1b", &["foo bar", "xy"][..]),
+            ("Oxy\x1b", &["xy", "foo bar"][..]),
+        ] {
+            let (l, _, vim) = run(&["foo bar"], (0, 0), keys);
+            assert_eq!(l, lines, "{:?}", keys);
+            assert_eq!(vim.mode(), VimMode::Normal, "{:?}", keys);
+        }
+
+        let (_, c, vim) = run(&["foo"], (0, 0), "Ax");
+        assert_eq!(vim.mode(), VimMode::Insert);
+        assert_eq!(c, (0, 4));
+        let (_, c, _) = run(&["foo"], (0, 0), "Ax\x1b");
+        assert_eq!(c, (0, 3));
+    }
+
+    #[test]
+    fn text_objects() {
+        let line = r#"call(foo, "bar baz", [1, (2)])"#;
+        for (col, keys, want) in [
+            (6, "diw", r#"call(, "bar baz", [1, (2)])"#),
+            (6, "daw", r#"call(, "bar baz", [1, (2)])"#),
+            (11, "daw", r#"call(foo, "baz", [1, (2)])"#),
+            (12, "di\"", r#"call(foo, "", [1, (2)])"#),
+            (0, "di\"", r#"call(foo, "", [1, (2)])"#),
+            (12, "da\"", r#"call(foo,, [1, (2)])"#),
+            (6, "di(", "call()"),
+            (6, "dab", "call"),
+            (27, "di(", r#"call(foo, "bar baz", [1, ()])"#),
+            (23, "di[", r#"call(foo, "bar baz", [])"#),
+            (23, "ci]x\x1b", r#"call(foo, "bar baz", [x])"#),
+            (4, "di)", "call()"),
+            (29, "di(", "call()"),
+            (0, "di(", line),
+        ] {
+            let (l, _, _) = run(&[line], (0, col), keys);
+            assert_eq!(l, [want], "{:?} at {}", keys, col);
+        }
+
+        let (l, _, _) = run(&["fn f() {", "    a;", "    b;", "}"], (1, 4), "di{");
+        assert_eq!(l, ["fn f() {", "}"]);
+        let (l, _, _) = run(&["fn f() {", "    a;", "}"], (1, 4), "da{");
+        assert_eq!(l, ["fn f() "]);
+        let (l, _, _) = run(&["{a{b}", "c}"], (1, 0), "di{");
+        assert_eq!(l, ["{}"]);
+    }
+
+    #[test]
+    fn yank_and_put_with_registers() {
+        let (t, _) = run_textarea(&["foo", "bar"], (0, 0), "yyjp");
+        assert_eq!(t.lines(), ["foo", "bar", "foo"]);
+        assert_eq!(t.cursor(), (2, 0));
+        assert_eq!(t.register('"').as_deref(), Some("foo"));
+
+        let (l, _, _) = run(&["foo", "bar"], (0, 0), "yyjP");
+        assert_eq!(l, ["foo", "foo", "bar"]);
+
+        let (l, _, _) = run(&["foo bar"], (0, 0), "ywP");
+        assert_eq!(l, ["foo foo bar"]);
+
+        let (l, c, _) = run(&["foo bar"], (0, 0), "yw$p");
+        assert_eq!(l, ["foo barfoo "]);
+        assert_eq!(c, (0, 10));
+
+        let (l, _, _) = run(&["foo bar"], (0, 0), "x3p");
+        assert_eq!(l, ["offfo bar"]);
+
+        // Named registers and appending to them
+        let (t, _) = run_textarea(&["foo", "bar"], (0, 0), "\"ayyj\"Ayy\"bdd\"ap");
+        assert_eq!(t.lines(), ["foo", "foo", "bar"]);
+        assert_eq!(t.register('a').as_deref(), Some("foo\nbar"));
+        assert_eq!(t.register('b').as_deref(), Some("bar"));
+        assert_eq!(t.register('"').as_deref(), Some("bar"));
+
+        // Black hole register does not change the unnamed register
+        let (t, _) = run_textarea(&["foo bar"], (0, 0), "yw\"_dwP");
+        assert_eq!(t.lines(), ["foo bar"]);
+        assert_eq!(t.register('"').as_deref(), Some("foo "));
+
+        // Registers are shared with the textarea
+        let mut t = TextArea::from(["foo", "bar"]);
+        let mut vim = Vim::new();
+        keys(&mut vim, &mut t, "\"ayiw");
+        assert_eq!(t.register('a').as_deref(), Some("foo"));
+        t.set_register('b', "x");
+        keys(&mut vim, &mut t, "j\"bp");
+        assert_eq!(t.lines(), ["foo", "bxar"]);
+        t.move_cursor(CursorMove::Head);
+        t.start_selection();
+        t.move_cursor(CursorMove::End);
+        t.copy_to_register('c');
+        keys(&mut vim, &mut t, "gg\"cP");
+        assert_eq!(t.lines(), ["bxarfoo", "bxar"]);
+        // Text of the textarea register is put characterwise even if the register was yanked linewise before
+        keys(&mut vim, &mut t, "\"ayy");
+        t.set_register('a', "y");
+        keys(&mut vim, &mut t, "\"ap");
+        assert_eq!(t.lines(), ["bxaryfoo", "bxar"]);
+    }
+
+    #[test]
+    fn visual_modes() {
+        let (t, vim) = run_textarea(&["foo bar baz"], (0, 0), "vey");
+        assert_eq!(t.lines(), ["foo bar baz"]);
+        assert_eq!(t.register('"').as_deref(), Some("foo"));
+        assert_eq!(vim.mode(), VimMode::Normal);
+
+        let (l, _, _) = run(&["foo bar baz"], (0, 4), "vwd");
+        assert_eq!(l, ["foo az"]);
+        let (l, _, _) = run(&["foo bar baz"], (0, 5), "vbd");
+        assert_eq!(l, ["foo r baz"]);
+        let (l, _, _) = run(&["foo bar baz"], (0, 5), "viwcx\x1b");
+        assert_eq!(l, ["foo x baz"]);
+        let (l, _, _) = run(&["foo bar baz"], (0, 4), "vex");
+        assert_eq!(l, ["foo  baz"]);
+        let (l, _, _) = run(&["foo bar baz"], (0, 4), "veo0d");
+        assert_eq!(l, [" baz"]);
+
+        let (l, c, _) = run(&["a", "b", "c", "d"], (1, 0), "Vjd");
+        assert_eq!(l, ["a", "d"]);
+        assert_eq!(c, (1, 0));
+        let (l, _, _) = run(&["a", "b", "c", "d"], (2, 0), "Vky");
+        assert_eq!(l, ["a", "b", "c", "d"]);
+        let (l, _, _) = run(&["a", "b", "c", "d"], (2, 0), "Vkyjjp");
+        assert_eq!(l, ["a", "b", "c", "d", "b", "c"]);
+        let (l, _, _) = run(&["a", "b", "c"], (1, 0), "Vcx\x1b");
+        assert_eq!(l, ["a", "x", "c"]);
+
+        let mut t = TextArea::from(["abcd", "efgh", "ijkl"]);
+        t.set_cursor((0, 1));
+        let mut vim = Vim::new();
+        ctrl(&mut vim, &mut t, 'v');
+        assert_eq!(vim.mode(), VimMode::VisualBlock);
+        keys(&mut vim, &mut t, "jld");
+        assert_eq!(t.lines(), ["ad", "eh", "ijkl"]);
+        assert_eq!(t.cursor(), (0, 1));
+        assert_eq!(t.register('"').as_deref(), Some("bc\nfg"));
+
+        let (_, _, vim) = run(&["foo"], (0, 0), "v\x1b");
+        assert_eq!(vim.mode(), VimMode::Normal);
+        let (_, _, vim) = run(&["foo"], (0, 0), "vV");
+        assert_eq!(vim.mode(), VimMode::VisualLine);
+        let (_, _, vim) = run(&["foo"], (0, 0), "vv");
+        assert_eq!(vim.mode(), VimMode::Normal);
+    }
+
+    #[test]
+    fn indent_and_dedent() {
+        let (l, _, _) = run(&["a", "b", "c"], (0, 0), ">j");
+        assert_eq!(l, ["    a", "    b", "c"]);
+        let (l, _, _) = run(&["a", "", "c"], (0, 0), "3>>");
+        assert_eq!(l, ["    a", "", "    c"]);
+        let (l, c, _) = run(&["      a", "\tb", "c"], (0, 0), "<2j");
+        assert_eq!(l, ["  a", "b", "c"]);
+        assert_eq!(c, (0, 2));
+        let (l, _, _) = run(&["a", "b", "c"], (0, 0), "Vj>");
+        assert_eq!(l, ["    a", "    b", "c"]);
+        // Dedent is the same as `TextArea::dedent_lines`
+        let (l, _, _) = run(&["  \tfoo"], (0, 0), "<<");
+        assert_eq!(l, ["foo"]);
+
+        // Shifting lines and replacing characters don't change the unnamed register
+        let (l, _, _) = run(&["foo", "    bar"], (0, 0), "yyj<<kp");
+        assert_eq!(l, ["foo", "foo", "bar"]);
+        let (l, _, _) = run(&["foo"], (0, 0), "yyrxp");
+        assert_eq!(l, ["xoo", "foo"]);
+    }
+
+    #[test]
+    fn format_lines() {
+        let (l, c, _) = run(&["aaa", "  bbb", "", "ccc"], (0, 1), "gqj");
+        assert_eq!(l, ["aaa bbb", "", "ccc"]);
+        assert_eq!(c, (0, 0));
+        let (l, _, _) = run(&["aaa", "bbb", "", "ccc", "ddd"], (1, 0), "gqip");
+        assert_eq!(l, ["aaa bbb", "", "ccc", "ddd"]);
+        let (l, _, _) = run(&["a", "b", "", "c", "d"], (0, 0), "Vjjjgq");
+        assert_eq!(l, ["a b", "", "c", "d"]);
+
+        let long = vec!["word"; 30].join(" ");
+        for keys in ["gqq", "gqgq"] {
+            let (l, c, _) = run(&[&long], (0, 0), keys);
+            assert_eq!(l, [vec!["word"; 16].join(" "), vec!["word"; 14].join(" ")], "{:?}", keys);
+            assert_eq!(c, (1, 0), "{:?}", keys);
+        }
+    }
+
+    #[test]
+    fn repeat_last_change() {
+        let (l, _, _) = run(&["foo bar baz qux"], (0, 0), "dw..");
+        assert_eq!(l, ["qux"]);
+        let (l, _, _) = run(&["a", "b", "c", "d"], (0, 0), "Ax\x1bj.j.");
+        assert_eq!(l, ["ax", "bx", "cx", "d"]);
+        let (l, _, _) = run(&["foo", "bar"], (0, 0), "cwx\x1bj.");
+        assert_eq!(l, ["x", "x"]);
+        let (l, _, _) = run(&["abcdef"], (0, 0), "x2.");
+        assert_eq!(l, ["def"]);
+        // Undo is not repeated
+        let (l, _, _) = run(&["aaa"], (0, 0), "xu.");
+        assert_eq!(l, ["aa"]);
+        // Motions don't overwrite the last change
+        let (l, _, _) = run(&["a", "b"], (0, 0), "ddj.");
+        assert_eq!(l, [""]);
+    }
+
+    #[test]
+    fn undo_redo() {
+        let mut t = TextArea::from(["abc"]);
+        let mut vim = Vim::new();
+        keys(&mut vim, &mut t, "xx");
+        assert_eq!(t.lines(), ["c"]);
+        keys(&mut vim, &mut t, "2u");
+        assert_eq!(t.lines(), ["abc"]);
+        assert!(ctrl(&mut vim, &mut t, 'r'));
+        assert_eq!(t.lines(), ["bc"]);
+    }
+
+    #[test]
+    fn undo_whole_change() {
+        for (text, keys) in [
+            (&["abc"][..], "rxu"),
+            (&["abc"][..], "3rxu"),
+            (&["foo bar"][..], "cwxyz\x1bu"),
+            (&["foo bar"][..], "sxy\x1bu"),
+            (&["foo", "bar"][..], "Sxy\x1bu"),
+            (&["foo", "bar"][..], "ccxy\x1bu"),
+            (&["foo", "bar"][..], "ix\ny\x1bu"),
+            (&["foo bar"][..], "yiwwviwpu"),
+            (&["foo bar"][..], "cwx\x1bw.uu"),
+        ] {
+            let (l, _, _) = run(text, (0, 0), keys);
+            assert_eq!(l, text, "{:?}", keys);
+        }
+
+        // Each command is undone separately
+        let (l, _, _) = run(&["foo bar baz"], (0, 0), "cwx\x1bw.u");
+        assert_eq!(l, ["x bar baz"]);
+        let (l, _, _) = run(&["abc"], (0, 0), "rxlryu");
+        assert_eq!(l, ["xbc"]);
+    }
+
+    #[test]
+    fn large_count() {
+        let many = "99999999999999999999";
+        let (l, c, _) = run(&["ab", "cd"], (0, 0), &format!("{}$", many));
+        assert_eq!(l, ["ab", "cd"]);
+        assert_eq!(c, (1, 2));
+        let (l, _, _) = run(&["ab", "cd"], (0, 0), &format!("{}rx", many));
+        assert_eq!(l, ["ab", "cd"]);
+        let (l, _, _) = run(&["ab", "cd"], (0, 0), &format!("{}x", many));
+        assert_eq!(l, ["", "cd"]);
+        let (l, _, _) = run(&["ab", "cd"], (1, 0), &format!("{}dd", many));
+        assert_eq!(l, ["ab"]);
+        let (l, _, _) = run(&["ab", "cd"], (0, 0), &format!("{}S", many));
+        assert_eq!(l, [""]);
+
+        let mut t = TextArea::from(["a", "b"]);
+        let mut vim = Vim::new();
+        keys(&mut vim, &mut t, many);
+        ctrl(&mut vim, &mut t, 'e');
+        keys(&mut vim, &mut t, many);
+        ctrl(&mut vim, &mut t, 'y');
+
+        // Commands with a huge count don't hang or exhaust memory
+        let huge = "99999999999";
+        let (l, c, _) = run(&["a", "b", "c"], (0, 0), &format!("{}j", huge));
+        assert_eq!(l, ["a", "b", "c"]);
+        assert_eq!(c, (2, 0));
+        let (_, c, _) = run(&["a b", "c d"], (0, 0), &format!("{}w", huge));
+        assert_eq!(c, (1, 3));
+        let (l, _, _) = run(&["abc"], (0, 0), &format!("xx{}u", huge));
+        assert_eq!(l, ["abc"]);
+        let (mut t, mut vim) = run_textarea(&["abc"], (0, 0), &format!("xxuu{}", huge));
+        ctrl(&mut vim, &mut t, 'r');
+        assert_eq!(t.lines(), ["c"]);
+        let (l, _, _) = run(&["ab"], (0, 0), &format!("yl{}p", huge));
+        assert_eq!(l[0].len(), MAX_REPEAT + 2);
+        let (l, _, _) = run(&["ab", "cd"], (0, 0), &format!("yy{}p", huge));
+        assert_eq!(l.len(), MAX_REPEAT + 2);
+        let (l, _, _) = run(&["abc"], (0, 0), &format!("x{}.", huge));
+        assert_eq!(l, [""]);
+        let (l, _, _) = run(&["abc"], (0, 0), &format!("ix\x1b{}.", huge));
+        assert_eq!(l[0].len(), MAX_REPEAT + 4);
+    }
+
+    #[test]
+    fn command_line() {
+        let mut t = TextArea::from(["a", "b", "c"]);
+        let mut vim = Vim::new();
+        keys(&mut vim, &mut t, ":3");
+        assert_eq!(vim.mode(), VimMode::Command);
+        assert_eq!(vim.command_line(), Some("3"));
+        keys(&mut vim, &mut t, "\n");
+        assert_eq!(vim.command_line(), None);
+        assert_eq!(t.cursor(), (2, 0));
+
+        vim.set_ex_handler(|cmd, t| {
+            if cmd == "d" {
+                t.insert_str("!")
+            } else {
+                false
+            }
+        });
+        assert!(keys(&mut vim, &mut t, ":d\n"));
+        assert_eq!(t.lines(), ["a", "b", "!c"]);
+        assert!(!keys(&mut vim, &mut t, ":x\n"));
+
+        let bs = Input {
+            key: Key::Backspace,
+            ..Default::default()
+        };
+        keys(&mut vim, &mut t, ":");
+        vim.input(&mut t, bs.clone());
+        assert_eq!(vim.mode(), VimMode::Normal);
+        keys(&mut vim, &mut t, ":ab");
+        vim.input(&mut t, bs);
+        assert_eq!(vim.command_line(), Some("a"));
+        keys(&mut vim, &mut t, "\x1b");
+        assert_eq!(vim.mode(), VimMode::Normal);
+    }
+
+    #[test]
+    fn escape_cancels_pending() {
+        let (l, _, vim) = run(&["foo bar"], (0, 0), "d\x1bw");
+        assert_eq!(l, ["foo bar"]);
+        assert_eq!(vim.mode(), VimMode::Normal);
+        let (_, c, _) = run(&["foo bar baz"], (0, 0), "2\x1bw");
+        assert_eq!(c, (0, 4));
+    }
+}
@@ -0,0 +1,139 @@
+use crate::indent::{indent_string, indent_width, is_blank, leading_whitespace};
+use crate::util::{col_at_display_width, display_width};
+
+// Each function below returns the cleaned lines and moves the positions in the lines so that they point to the same
+// text after the cleanup. Rows of the positions are relative to the head of the lines
+
+// Remove spaces and tabs at the end of each line
+pub(crate) fn trim_trailing(lines: &[String], positions: &mut [(usize, usize)]) -> Vec<String> {
+    let trimmed: Vec<String> = lines
+        .iter()
+        .map(|line| line.trim_end_matches(|c| c == ' ' || c == '\t').to_string())
+        .collect();
+    for (row, col) in positions.iter_mut() {
+        *col = (*col).min(trimmed[*row].chars().count());
+    }
+    trimmed
+}
+
+// Rewrite the indentation of each line with tabs or spaces keeping its display width
+pub(crate) fn retab(
+    lines: &[String],
+    tab_len: u8,
+    hard_tab: bool,
+    positions: &mut [(usize, usize)],
+) -> Vec<String> {
+    let retabbed: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            let indent = leading_whitespace(line);
+            let width = indent_width(indent, tab_len);
+            let mut s = indent_string(width, tab_len, hard_tab);
+            s.push_str(&line[indent.len()..]);
+            s
+        })
+        .collect();
+    for (row, col) in positions.iter_mut() {
+        // Leading whitespaces are ASCII so the number of characters is the same as the byte length
+        let (old, new) = (&lines[*row], &retabbed[*row]);
+        let (old_len, new_len) = (leading_whitespace(old).len(), leading_whitespace(new).len());
+        *col = if *col < old_len {
+            let x = display_width(old, *col, tab_len, None);
+            col_at_display_width(&new[..new_len], x, tab_len, None)
+        } else {
+            *col + new_len - old_len
+        };
+    }
+    retabbed
+}
+
+// Replace each run of blank lines with its first line
+pub(crate) fn collapse_blank(lines: &[String], positions: &mut [(usize, usize)]) -> Vec<String> {
+    let mut collapsed: Vec<String> = Vec::with_capacity(lines.len());
+    // New row of each old row
+    let mut rows = Vec::with_capacity(lines.len());
+    for line in lines {
+        let prev_blank = collapsed.last().map_or(false, |l| is_blank(l));
+        if !(prev_blank && is_blank(line)) {
+            collapsed.push(line.clone());
+        }
+        rows.push(collapsed.len() - 1);
+    }
+    for (row, col) in positions.iter_mut() {
+        *row = rows[*row];
+        *col = (*col).min(collapsed[*row].chars().count());
+    }
+    collapsed
+}
+
+// Make the text end with exactly one newline when a newline is put after each line. The lines start with the last
+// non-blank line of the text, or are all lines when the text is blank. Blank lines after it are removed. Blank text
+// becomes empty
+pub(crate) fn final_newline(lines: &[String], positions: &mut [(usize, usize)]) -> Vec<String> {
+    let last = if is_blank(&lines[0]) {
+        String::new()
+    } else {
+        lines[0].clone()
+    };
+    let len = last.chars().count();
+    for (row, col) in positions.iter_mut() {
+        if *row > 0 || *col > len {
+            *row = 0;
+            *col = len;
+        }
+    }
+    vec![last]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::lines;
+
+    #[test]
+    fn trim_trailing_whitespaces() {
+        let mut pos = [(0, 3), (1, 5), (2, 0)];
+        let trimmed = trim_trailing(&lines(&["a  ", "  b \t", "\t"]), &mut pos);
+        assert_eq!(trimmed, ["a", "  b", ""]);
+        assert_eq!(pos, [(0, 1), (1, 3), (2, 0)]);
+    }
+
+    #[test]
+    fn retab_indentation() {
+        let text = lines(&["        a", "  \tb", "   c", "d\t"]);
+        let mut pos = [(0, 4), (0, 9), (1, 1), (1, 3), (2, 1)];
+        let retabbed = retab(&text, 4, true, &mut pos);
+        assert_eq!(retabbed, ["\t\ta", "\tb", "   c", "d\t"]);
+        assert_eq!(pos, [(0, 1), (0, 3), (1, 0), (1, 1), (2, 1)]);
+
+        let mut pos = [(0, 1), (0, 3), (1, 1)];
+        let retabbed = retab(&retabbed, 4, false, &mut pos);
+        assert_eq!(retabbed, ["        a", "    b", "   c", "d\t"]);
+        assert_eq!(pos, [(0, 4), (0, 9), (1, 4)]);
+    }
+
+    #[test]
+    fn collapse_blank_lines() {
+        let text = lines(&["a", "", "  ", "", "b", "", "c", " ", ""]);
+        let mut pos = [(2, 1), (3, 0), (4, 1), (8, 0)];
+        let collapsed = collapse_blank(&text, &mut pos);
+        assert_eq!(collapsed, ["a", "", "b", "", "c", " "]);
+        assert_eq!(pos, [(1, 0), (1, 0), (2, 1), (5, 0)]);
+    }
+
+    #[test]
+    fn ensure_final_newline() {
+        for (text, want, want_pos) in [
+            (&["a"][..], &["a"][..], [(0, 1), (0, 1)]),
+            (&["a", ""][..], &["a"][..], [(0, 1), (0, 1)]),
+            (&["ab", "", " ", ""][..], &["ab"][..], [(0, 1), (0, 2)]),
+            (&[""][..], &[""][..], [(0, 0), (0, 0)]),
+            (&["", " "][..], &[""][..], [(0, 0), (0, 0)]),
+        ] {
+            let mut pos = [(0, 1), (text.len() - 1, 1)];
+            let fixed = final_newline(&lines(text), &mut pos);
+            assert_eq!(fixed, want, "{:?}", text);
+            assert_eq!(pos, want_pos, "{:?}", text);
+        }
+    }
+}
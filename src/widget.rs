@@ -3,7 +3,7 @@ use crate::ratatui::layout::Rect;
 use crate::ratatui::text::Span;
 use crate::ratatui::widgets::Widget;
 use crate::textarea::TextArea;
-use crate::util::num_digits;
+use crate::util::{char_visual_width, display_width, num_digits};
 #[cfg(feature = "ratatui")]
 use ratatui::text::Line;
 use std::cmp;
@@ -19,13 +19,17 @@ use tui::text::Spans as Line;
 // point we stick with using `ratatui::Frame::render_widget` because it is simpler API. Users don't need to
 // manage states of textarea instances separately.
 // https://docs.rs/ratatui/latest/ratatui/terminal/struct.Frame.html#method.render_stateful_widget
+//
+// The second value is the area where the widget (including its block) was rendered last time. It is used for mapping
+// mouse positions in `TextArea::input`.
 #[derive(Default, Debug)]
-pub struct Viewport(AtomicU64);
+pub struct Viewport(AtomicU64, AtomicU64);
 
 impl Clone for Viewport {
     fn clone(&self) -> Self {
         let u = self.0.load(Ordering::Relaxed);
-        Viewport(AtomicU64::new(u))
+        let a = self.1.load(Ordering::Relaxed);
+        Viewport(AtomicU64::new(u), AtomicU64::new(a))
     }
 }
 
@@ -64,6 +68,20 @@ impl Viewport {
         self.0.store(u, Ordering::Relaxed);
     }
 
+    #[cfg(feature = "mouse")]
+    pub fn area(&self) -> Rect {
+        let u = self.1.load(Ordering::Relaxed);
+        Rect::new((u >> 48) as u16, (u >> 32) as u16, (u >> 16) as u16, u as u16)
+    }
+
+    fn store_area(&self, area: Rect) {
+        let u = ((area.x as u64) << 48)
+            | ((area.y as u64) << 32)
+            | ((area.width as u64) << 16)
+            | area.height as u64;
+        self.1.store(u, Ordering::Relaxed);
+    }
+
     pub fn scroll(&mut self, rows: i16, cols: i16) {
         fn apply_scroll(pos: u16, delta: i16) -> u16 {
             if delta >= 0 {
@@ -91,26 +109,9 @@ fn next_scroll_top(prev_top: u16, cursor: u16, len: u16) -> u16 {
     }
 }
 
-/// Calculate the visual width of a character, handling tabs and Unicode width
-fn char_visual_width(c: char, position: usize, tab_len: u8) -> usize {
-    match c {
-        '\t' => {
-            if tab_len == 0 {
-                0
-            } else {
-                tab_len as usize - (position % tab_len as usize)
-            }
-        }
-        _ => {
-            use unicode_width::UnicodeWidthChar;
-            c.width().unwrap_or(0)
-        }
-    }
-}
-
 /// Calculate visual position range for horizontal clipping
 /// Returns (start_char_idx, end_char_idx, start_visual_offset)
-fn calculate_horizontal_range(
+pub(crate) fn calculate_horizontal_range(
     line: &str,
     col_left: u16,
     viewport_width: u16,
@@ -190,6 +191,7 @@ impl<'a> TextArea<'a> {
             viewport_width = viewport_width.saturating_sub((lnum_len + 2) as u16);
         }
 
+        let brackets = self.matching_brackets();
         for (i, line) in self.lines()[top_row..bottom_row].iter().enumerate() {
             let rendered_line = if col_left > 0 || line.chars().count() > viewport_width as usize {
                 let (start, end, _) = calculate_horizontal_range(
@@ -199,12 +201,12 @@ impl<'a> TextArea<'a> {
                     self.tab_length(),
                 );
                 if start < line.len() {
-                    self.line_spans(&line[start..end], top_row + i, lnum_len)
+                    self.line_spans(&line[start..end], top_row + i, lnum_len, brackets)
                 } else {
-                    self.line_spans("", top_row + i, lnum_len)
+                    self.line_spans("", top_row + i, lnum_len, brackets)
                 }
             } else {
-                self.line_spans(line.as_str(), top_row + i, lnum_len)
+                self.line_spans(line.as_str(), top_row + i, lnum_len, brackets)
             };
 
             lines.push(rendered_line);
@@ -214,7 +216,7 @@ impl<'a> TextArea<'a> {
     }
 
     fn placeholder_lines(&'a self) -> Vec<Line<'a>> {
-        let cursor = Span::styled(" ", self.cursor_style);
+        let cursor = Span::styled(" ", self.current_cursor_style());
         let text = Span::raw(self.placeholder.as_str());
         vec![Line::from(vec![cursor, text])]
     }
@@ -248,14 +250,7 @@ impl<'a> TextArea<'a> {
         }
         
         let line = &self.lines()[cursor_row];
-        let mut visual_pos = 0;
-        
-        for (char_idx, c) in line.char_indices() {
-            if line[..char_idx].chars().count() >= cursor_col {
-                break;
-            }
-            visual_pos += char_visual_width(c, visual_pos, self.tab_length());
-        }
+        let visual_pos = display_width(line, cursor_col, self.tab_length(), None);
         
         next_scroll_top(prev_left, visual_pos as u16, text_width)
     }
@@ -324,6 +319,7 @@ impl Widget for &TextArea<'_> {
 
         // Store scroll position for rendering on the next tick
         self.viewport.store(top_row, left_col, width, height);
+        self.viewport.store_area(area);
 
         self.render_lines(lines, text_area, buf);
     }
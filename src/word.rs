@@ -79,3 +79,21 @@ pub fn find_word_start_backward(line: &str, start_col: usize) -> Option<usize> {
     }
     (cur != CharKind::Space).then(|| 0)
 }
+
+pub fn find_word_range(line: &str, col: usize) -> Option<(usize, usize)> {
+    let chars: Vec<char> = line.chars().collect();
+    let last = chars.len().checked_sub(1)?;
+    let col = col.min(last);
+    let kind = CharKind::new(chars[col]);
+    let start = chars[..col]
+        .iter()
+        .rposition(|&c| CharKind::new(c) != kind)
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let end = chars[col..]
+        .iter()
+        .position(|&c| CharKind::new(c) != kind)
+        .map(|i| col + i)
+        .unwrap_or(chars.len());
+    Some((start, end))
+}
@@ -36,6 +36,7 @@ impl<'a> TextArea<'a> {
 
             let mut lines = Vec::new();
             let mut display_row = 0;
+            let brackets = self.matching_brackets();
 
             for (logical_row, line_text) in self.lines().iter().enumerate() {
                 let wrapped_lines = textwrap::wrap(line_text, &options);
@@ -66,7 +67,7 @@ impl<'a> TextArea<'a> {
                         }
 
                         // Get fully highlighted line (handles selection, cursor, search, etc.)
-                        let full_highlighted_line = self.line_spans(line_text, logical_row, 0);
+                        let full_highlighted_line = self.line_spans(line_text, logical_row, 0, brackets);
                         
                         // Calculate character range for this wrapped segment
                         let (segment_start_char, segment_end_char) = 
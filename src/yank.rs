@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+use std::fmt;
+
+// Max number of texts remembered in the kill ring. Older texts are dropped
+const KILL_RING_SIZE: usize = 32;
+
+#[derive(Debug, Clone)]
+pub(crate) enum YankText {
+    Piece(String),
+    Chunk(Vec<String>),
+}
+
+impl YankText {
+    // `str::lines` is not available since it strips a newline at end
+    pub(crate) fn parse(text: &str) -> Self {
+        text.split('\n')
+            .map(|s| s.strip_suffix('\r').unwrap_or(s).to_string())
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(self, Self::Piece(s) if s.is_empty())
+    }
+
+    // Concatenate the other text after this text. The last line of this text and the first line of the other text
+    // are joined
+    fn append(&mut self, other: YankText) {
+        let mut lines = match std::mem::take(self) {
+            Self::Piece(s) => vec![s],
+            Self::Chunk(c) => c,
+        };
+        let mut other = match other {
+            Self::Piece(s) => vec![s],
+            Self::Chunk(c) => c,
+        }
+        .into_iter();
+        if let Some(first) = other.next() {
+            lines.last_mut().unwrap().push_str(&first);
+        }
+        lines.extend(other);
+        *self = lines.into();
+    }
+}
+
+impl Default for YankText {
+    fn default() -> Self {
+        Self::Piece(String::new())
+    }
+}
+
+impl From<String> for YankText {
+    fn from(s: String) -> Self {
+        Self::Piece(s)
+    }
+}
+impl From<Vec<String>> for YankText {
+    fn from(mut c: Vec<String>) -> Self {
+        match c.len() {
+            0 => Self::default(),
+            1 => Self::Piece(c.remove(0)),
+            _ => Self::Chunk(c),
+        }
+    }
+}
+
+impl fmt::Display for YankText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Piece(s) => write!(f, "{}", s),
+            Self::Chunk(ss) => write!(f, "{}", ss.join("\n")),
+        }
+    }
+}
+
+// The last operation on the kill ring. This is used for checking consecutive kills and yank-pop
+#[derive(Debug, Clone)]
+enum LastAction {
+    Kill((usize, usize)),
+    Yank {
+        start: (usize, usize),
+        end: (usize, usize),
+        index: usize,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct KillRing {
+    // The newest text is at the end
+    ring: Vec<YankText>,
+    last: Option<LastAction>,
+}
+
+impl KillRing {
+    pub(crate) fn top(&self) -> YankText {
+        self.ring.last().cloned().unwrap_or_default()
+    }
+
+    pub(crate) fn texts(&self) -> impl Iterator<Item = &YankText> {
+        self.ring.iter().rev()
+    }
+
+    pub(crate) fn push(&mut self, text: YankText) {
+        if self.ring.len() >= KILL_RING_SIZE {
+            self.ring.remove(0);
+        }
+        self.ring.push(text);
+    }
+
+    // Record the killed text. When `consecutive` is true, the text is appended to the previous kill.
+    // `backward` means the text was deleted before the cursor so it is prepended instead
+    pub(crate) fn kill(
+        &mut self,
+        text: YankText,
+        cursor: (usize, usize),
+        backward: bool,
+        consecutive: bool,
+    ) {
+        match self.ring.last_mut() {
+            Some(last) if consecutive && !text.is_empty() => {
+                if backward {
+                    let mut text = text;
+                    text.append(std::mem::take(last));
+                    *last = text;
+                } else {
+                    last.append(text);
+                }
+            }
+            _ => self.push(text),
+        }
+        self.last = Some(LastAction::Kill(cursor));
+    }
+
+    pub(crate) fn is_consecutive_kill(&self, cursor: (usize, usize)) -> bool {
+        matches!(self.last, Some(LastAction::Kill(c)) if c == cursor)
+    }
+
+    pub(crate) fn yanked(&mut self, start: (usize, usize), end: (usize, usize), index: usize) {
+        self.last = Some(LastAction::Yank { start, end, index });
+    }
+
+    // Get the range of the last yanked text and the next older text in the ring when the last operation was yank
+    pub(crate) fn pop(&self, cursor: (usize, usize)) -> Option<((usize, usize), usize, YankText)> {
+        match self.last {
+            Some(LastAction::Yank { start, end, index })
+                if end == cursor && self.ring.len() > 1 =>
+            {
+                let index = (index + 1) % self.ring.len();
+                let text = self.ring[self.ring.len() - 1 - index].clone();
+                Some((start, index, text))
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn reset_last(&mut self) {
+        self.last = None;
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Registers {
+    named: HashMap<char, YankText>,
+}
+
+impl Registers {
+    pub(crate) fn is_valid(name: char) -> bool {
+        name.is_ascii_alphabetic() || name == '_'
+    }
+
+    pub(crate) fn get(&self, name: char) -> Option<&YankText> {
+        self.named.get(&name.to_ascii_lowercase())
+    }
+
+    // Set the text to the register. Uppercase name appends the text to the register and '_' discards the text.
+    pub(crate) fn set(&mut self, name: char, text: YankText) {
+        if !name.is_ascii_alphabetic() {
+            return;
+        }
+        let lower = name.to_ascii_lowercase();
+        match self.named.get_mut(&lower) {
+            Some(prev) if name.is_ascii_uppercase() => prev.append(text),
+            _ => {
+                self.named.insert(lower, text);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(y: &YankText) -> String {
+        y.to_string()
+    }
+
+    #[test]
+    fn append_yank_text() {
+        for (a, b, want) in [
+            ("ab", "cd", "abcd"),
+            ("a\nb", "c", "a\nbc"),
+            ("a", "b\nc", "ab\nc"),
+            ("a\nb", "c\nd", "a\nbc\nd"),
+            ("", "a\n", "a\n"),
+        ] {
+            let mut y = YankText::parse(a);
+            y.append(YankText::parse(b));
+            assert_eq!(lines(&y), want, "{:?} + {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn consecutive_kills() {
+        let mut r = KillRing::default();
+        r.kill("foo".to_string().into(), (0, 0), false, false);
+        assert!(r.is_consecutive_kill((0, 0)));
+        assert!(!r.is_consecutive_kill((0, 1)));
+        r.kill(" bar".to_string().into(), (0, 0), false, true);
+        assert_eq!(lines(&r.top()), "foo bar");
+        r.kill("x ".to_string().into(), (0, 0), true, true);
+        assert_eq!(lines(&r.top()), "x foo bar");
+        r.reset_last();
+        assert!(!r.is_consecutive_kill((0, 0)));
+        r.kill("baz".to_string().into(), (0, 0), false, false);
+        let texts: Vec<_> = r.texts().map(lines).collect();
+        assert_eq!(texts, ["baz", "x foo bar"]);
+    }
+
+    #[test]
+    fn kill_ring_size() {
+        let mut r = KillRing::default();
+        for i in 0..KILL_RING_SIZE + 3 {
+            r.push(i.to_string().into());
+        }
+        assert_eq!(r.texts().count(), KILL_RING_SIZE);
+        assert_eq!(lines(r.texts().last().unwrap()), "3");
+    }
+
+    #[test]
+    fn named_registers() {
+        let mut r = Registers::default();
+        r.set('a', YankText::parse("foo"));
+        r.set('A', YankText::parse("bar"));
+        r.set('B', YankText::parse("baz"));
+        r.set('_', YankText::parse("discarded"));
+        r.set('1', YankText::parse("invalid"));
+        assert_eq!(r.get('a').map(lines).as_deref(), Some("foobar"));
+        assert_eq!(r.get('A').map(lines).as_deref(), Some("foobar"));
+        assert_eq!(r.get('b').map(lines).as_deref(), Some("baz"));
+        assert!(r.get('_').is_none());
+        assert!(r.get('1').is_none());
+    }
+}
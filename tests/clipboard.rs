@@ -0,0 +1,158 @@
+use std::sync::{Arc, Mutex};
+use tui_textarea::{ClipboardProvider, CursorMove, MemoryClipboard, TextArea};
+
+#[derive(Clone, Default)]
+struct FakeClipboard {
+    copied: Arc<Mutex<Vec<String>>>,
+    external: Arc<Mutex<Option<String>>>,
+}
+
+impl FakeClipboard {
+    fn copied(&self) -> Vec<String> {
+        self.copied.lock().unwrap().clone()
+    }
+
+    // Emulate copying text in another application
+    fn copy_externally(&self, text: &str) {
+        *self.external.lock().unwrap() = Some(text.to_string());
+    }
+}
+
+impl ClipboardProvider for FakeClipboard {
+    fn set_text(&self, text: &str) {
+        self.copied.lock().unwrap().push(text.to_string());
+        *self.external.lock().unwrap() = Some(text.to_string());
+    }
+
+    fn get_text(&self) -> Option<String> {
+        self.external.lock().unwrap().clone()
+    }
+}
+
+#[test]
+fn test_yank_writes_clipboard() {
+    let clipboard = FakeClipboard::default();
+    let mut t = TextArea::from(["foo bar baz", "qux"]);
+    t.set_clipboard(clipboard.clone());
+
+    t.start_selection();
+    t.move_cursor(CursorMove::WordForward);
+    t.copy();
+    t.start_selection();
+    t.move_cursor(CursorMove::Head);
+    t.cut();
+    t.delete_next_word();
+    t.move_cursor(CursorMove::Down);
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    t.copy();
+    t.set_yank_text("a\r\nb");
+    assert_eq!(clipboard.copied(), ["foo ", "foo ", "bar", "qux", "a\nb"]);
+
+    // Consecutive kills write the concatenated text
+    let clipboard = FakeClipboard::default();
+    let mut t = TextArea::from(["foo bar"]);
+    t.set_clipboard(clipboard.clone());
+    t.delete_next_word();
+    t.delete_next_word();
+    assert_eq!(clipboard.copied(), ["foo", "foo bar"]);
+
+    // Copying to named registers does not write the clipboard
+    t.set_yank_text("x");
+    t.select_all();
+    t.copy_to_register('a');
+    assert_eq!(clipboard.copied(), ["foo", "foo bar", "x"]);
+}
+
+#[test]
+fn test_paste_reads_clipboard() {
+    let clipboard = FakeClipboard::default();
+    let mut t = TextArea::default();
+    t.set_clipboard(clipboard.clone());
+
+    // Empty clipboard falls back to the kill ring
+    t.set_yank_text("foo");
+    clipboard.external.lock().unwrap().take();
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["foo"]);
+
+    // Text copied in another application is pasted and pushed to the kill ring
+    clipboard.copy_externally("bar\nbaz");
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["foobar", "baz"]);
+    assert_eq!(t.kill_ring(), ["bar\nbaz", "foo"]);
+    assert!(t.yank_pop());
+    assert_eq!(t.lines(), ["foofoo"]);
+
+    // The same text is not pushed twice
+    assert!(t.paste());
+    assert_eq!(t.kill_ring(), ["bar\nbaz", "foo"]);
+
+    t.remove_clipboard();
+    assert!(t.clipboard().is_none());
+    clipboard.copy_externally("qux");
+    t.select_all();
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["bar", "baz"]);
+}
+
+#[test]
+fn test_share_memory_clipboard() {
+    let clipboard = MemoryClipboard::new();
+    let mut a = TextArea::from(["hello"]);
+    let mut b = TextArea::from(["world"]);
+    a.set_clipboard(clipboard.clone());
+    b.set_clipboard(clipboard.clone());
+
+    a.select_all();
+    assert!(a.cut());
+    b.move_cursor(CursorMove::End);
+    assert!(b.paste());
+    assert_eq!(b.lines(), ["worldhello"]);
+
+    b.delete_word();
+    assert_eq!(clipboard.get_text().as_deref(), Some("worldhello"));
+    assert!(a.paste());
+    assert_eq!(a.lines(), ["worldhello"]);
+}
+
+#[cfg(feature = "crossterm")]
+#[test]
+fn test_osc52_through_backend() {
+    use ratatui::backend::CrosstermBackend;
+    use ratatui::Terminal;
+    use std::io::{self, Write};
+    use tui_textarea::Osc52Clipboard;
+
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let output = Output::default();
+    let mut term = Terminal::new(CrosstermBackend::new(output.clone())).unwrap();
+    let clipboard = Osc52Clipboard::new();
+    let mut t = TextArea::from(["hello"]);
+    t.set_clipboard(clipboard.clone());
+
+    term.draw(|f| f.render_widget(&t, f.area())).unwrap();
+    t.select_all();
+    t.copy();
+    // Copying does not write anything until the sequence is written through the backend
+    let drawn = output.0.lock().unwrap().len();
+    assert!(clipboard.has_pending());
+
+    clipboard.write_pending(term.backend_mut()).unwrap();
+    term.draw(|f| f.render_widget(&t, f.area())).unwrap();
+    let written = output.0.lock().unwrap().clone();
+    let seq = b"\x1b]52;c;aGVsbG8=\x07";
+    assert_eq!(&written[drawn..drawn + seq.len()], seq);
+    assert!(!clipboard.has_pending());
+}
@@ -1,4 +1,4 @@
-use tui_textarea::{CursorMove, TextArea};
+use tui_textarea::{CursorMove, TextArea, TextObject};
 
 const BOTTOM_RIGHT: CursorMove = CursorMove::Jump(u16::MAX, u16::MAX);
 
@@ -21,6 +21,7 @@ fn empty_textarea() {
         WordBack,
         ParagraphForward,
         ParagraphBack,
+        MatchingBracket,
         Jump(0, 0),
         Jump(u16::MAX, u16::MAX),
     ] {
@@ -326,3 +327,232 @@ fn word_end() {
         }
     }
 }
+
+#[test]
+fn matching_bracket() {
+    let lines = [
+        "fn f(x: &[u8]) {",
+        "    g(\")\", '{');",
+        "    // \"\\\"(\"",
+        "}",
+    ];
+    let mut t = TextArea::from(lines);
+    for (from, to) in [
+        ((0, 4), (0, 13)), // Bracket under the cursor
+        ((0, 13), (0, 4)),
+        ((0, 14), (0, 4)), // Bracket before the cursor
+        ((0, 9), (0, 12)), // Other kind of bracket
+        ((1, 5), (1, 14)), // Skip the bracket in string literal
+        ((1, 14), (1, 5)),
+        ((1, 7), (1, 7)), // Bracket in string literal is not matched with brackets outside it
+        ((2, 10), (2, 10)), // Unmatched bracket
+        ((0, 15), (0, 15)), // Single quotes are not skipped by default
+        ((0, 2), (0, 2)), // No bracket
+    ] {
+        t.move_cursor(CursorMove::Jump(from.0 as u16, from.1 as u16));
+        t.move_cursor(CursorMove::MatchingBracket);
+        assert_eq!(t.cursor(), to, "from {:?}", from);
+    }
+
+    t.set_bracket_quotes(['"', '\'']);
+    t.move_cursor(CursorMove::Jump(0, 15));
+    t.move_cursor(CursorMove::MatchingBracket);
+    assert_eq!(t.cursor(), (3, 0));
+
+    // Selection is extended by the motion
+    t.set_bracket_quotes([]);
+    t.move_cursor(CursorMove::Jump(1, 5));
+    t.start_selection();
+    t.move_cursor(CursorMove::MatchingBracket);
+    assert_eq!(t.selection_range(), Some(((1, 5), (1, 7))));
+}
+
+fn text_object_at(
+    lines: &[&str],
+    (row, col): (u16, u16),
+    object: TextObject,
+) -> Option<((usize, usize), (usize, usize))> {
+    let mut t: TextArea = lines.iter().cloned().collect();
+    t.move_cursor(CursorMove::Jump(row, col));
+    t.text_object_range(object)
+}
+
+#[test]
+fn text_object_word() {
+    use TextObject::*;
+
+    for (line, col, object, expected) in [
+        ("foo.bar  baz", 5, InnerWord, Some((4, 7))),
+        ("foo.bar  baz", 5, AroundWord, Some((4, 9))),
+        ("foo.bar  baz", 3, InnerWord, Some((3, 4))),
+        ("foo.bar  baz", 7, InnerWord, Some((7, 9))),
+        ("foo.bar  baz", 7, AroundWord, Some((7, 12))),
+        ("foo.bar  baz", 11, AroundWord, Some((7, 12))), // Leading spaces when no trailing space
+        ("foo.bar  baz", 5, InnerBigWord, Some((0, 7))),
+        ("foo.bar  baz", 5, AroundBigWord, Some((0, 9))),
+        ("foo.bar  baz", 10, AroundBigWord, Some((7, 12))),
+        ("", 0, InnerWord, None),
+        ("", 0, InnerBigWord, None),
+    ] {
+        let r = text_object_at(&[line], (0, col), object);
+        let expected = expected.map(|(s, e)| ((0, s), (0, e)));
+        assert_eq!(r, expected, "{:?} at {} in {:?}", object, col, line);
+    }
+}
+
+#[test]
+fn text_object_sentence() {
+    use TextObject::*;
+
+    let lines = [
+        "Hello world. This is",
+        "a test!  Bye?",
+        "",
+        "Next paragraph.",
+    ];
+    for (pos, object, expected) in [
+        ((0, 3), InnerSentence, Some(((0, 0), (0, 12)))),
+        ((0, 3), AroundSentence, Some(((0, 0), (0, 13)))),
+        ((1, 2), InnerSentence, Some(((0, 13), (1, 7)))), // Sentence across lines
+        ((1, 2), AroundSentence, Some(((0, 13), (1, 9)))),
+        ((1, 10), InnerSentence, Some(((1, 9), (1, 13)))),
+        ((1, 10), AroundSentence, Some(((1, 7), (1, 13)))), // Last sentence takes leading spaces
+        ((3, 0), InnerSentence, Some(((3, 0), (3, 15)))),
+        ((2, 0), InnerSentence, None),
+    ] {
+        let r = text_object_at(&lines, pos, object);
+        assert_eq!(r, expected, "{:?} at {:?}", object, pos);
+    }
+}
+
+#[test]
+fn text_object_paragraph() {
+    use TextObject::*;
+
+    let lines = ["a", "b", "", "", "c", "d"];
+    for (pos, object, expected) in [
+        ((0, 0), InnerParagraph, ((0, 0), (2, 0))),
+        ((1, 0), AroundParagraph, ((0, 0), (4, 0))),
+        ((2, 0), InnerParagraph, ((2, 0), (4, 0))),
+        ((3, 0), AroundParagraph, ((1, 1), (5, 1))), // Blank lines with the following paragraph at the end
+        ((5, 0), InnerParagraph, ((3, 0), (5, 1))), // Last paragraph takes the preceding line break
+        ((4, 0), AroundParagraph, ((1, 1), (5, 1))), // Preceding blank lines when no following blank line
+    ] {
+        let r = text_object_at(&lines, pos, object);
+        assert_eq!(r, Some(expected), "{:?} at {:?}", object, pos);
+    }
+
+    let r = text_object_at(&["only"], (0, 2), AroundParagraph);
+    assert_eq!(r, Some(((0, 0), (0, 4))));
+}
+
+#[test]
+fn text_object_bracket() {
+    use TextObject::*;
+
+    let line = "f(a, [b, (c)], d)";
+    for (col, object, expected) in [
+        (2, InnerBracket('('), Some((2, 16))),
+        (2, AroundBracket(')'), Some((1, 17))),
+        (1, InnerBracket('('), Some((2, 16))), // On the open bracket
+        (16, InnerBracket('('), Some((2, 16))), // On the close bracket
+        (7, InnerBracket('['), Some((6, 12))),
+        (10, InnerBracket('('), Some((10, 11))), // Innermost pair
+        (10, InnerBracket('['), Some((6, 12))),  // Nested in other brackets
+        (10, AroundBracket('['), Some((5, 13))),
+        (0, InnerBracket('('), None),
+        (2, InnerBracket('{'), None),
+        (2, InnerBracket('x'), None),
+    ] {
+        let r = text_object_at(&[line], (0, col), object);
+        let expected = expected.map(|(s, e)| ((0, s), (0, e)));
+        assert_eq!(r, expected, "{:?} at {}", object, col);
+    }
+
+    let lines = ["fn f() {", "    if x {", "        y", "    }", "}"];
+    for (pos, object, expected) in [
+        ((2, 8), InnerBracket('{'), ((2, 0), (3, 0))), // Lines between brackets
+        ((2, 8), AroundBracket('{'), ((1, 9), (3, 5))),
+        ((1, 4), InnerBracket('{'), ((1, 0), (4, 0))),
+        ((3, 4), InnerBracket('}'), ((2, 0), (3, 0))),
+    ] {
+        let r = text_object_at(&lines, pos, object);
+        assert_eq!(r, Some(expected), "{:?} at {:?}", object, pos);
+    }
+
+    // Brackets not at the end and the head of lines
+    let r = text_object_at(&["f(a,", "  b)"], (1, 2), InnerBracket('('));
+    assert_eq!(r, Some(((0, 2), (1, 3))));
+}
+
+#[test]
+fn text_object_quote() {
+    use TextObject::*;
+
+    let line = r#"a "b \" c" 'd' e"#;
+    for (col, object, expected) in [
+        (4, InnerQuote('"'), Some((3, 9))), // Escaped quote is skipped
+        (4, AroundQuote('"'), Some((2, 11))),
+        (0, InnerQuote('"'), Some((3, 9))), // Next quoted text
+        (12, InnerQuote('\''), Some((12, 13))),
+        (12, AroundQuote('\''), Some((11, 15))),
+        (15, InnerQuote('\''), None),
+        (4, InnerQuote('`'), None),
+    ] {
+        let r = text_object_at(&[line], (0, col), object);
+        let expected = expected.map(|(s, e)| ((0, s), (0, e)));
+        assert_eq!(r, expected, "{:?} at {}", object, col);
+    }
+}
+
+#[test]
+fn text_object_tag() {
+    use TextObject::*;
+
+    let line = r#"<div id="a"><b>x</b><br/> y</div>"#;
+    for (col, object, expected) in [
+        (15, InnerTag, Some((15, 16))),
+        (15, AroundTag, Some((12, 20))),
+        (13, InnerTag, Some((15, 16))), // On the open tag
+        (22, InnerTag, Some((12, 27))), // Self-closing tag is ignored
+        (22, AroundTag, Some((0, 33))),
+        (0, InnerTag, Some((12, 27))),
+    ] {
+        let r = text_object_at(&[line], (0, col), object);
+        let expected = expected.map(|(s, e)| ((0, s), (0, e)));
+        assert_eq!(r, expected, "{:?} at {}", object, col);
+    }
+
+    let lines = ["<ul>", "  <li>a</li>", "  <li>b", "  </li>", "</ul>"];
+    for (pos, object, expected) in [
+        ((1, 6), InnerTag, ((1, 6), (1, 7))),
+        ((2, 6), InnerTag, ((2, 6), (3, 2))), // Tag pair across lines
+        ((2, 6), AroundTag, ((2, 2), (3, 7))),
+        ((1, 0), InnerTag, ((0, 4), (4, 0))),
+    ] {
+        let r = text_object_at(&lines, pos, object);
+        assert_eq!(r, Some(expected), "{:?} at {:?}", object, pos);
+    }
+
+    assert_eq!(text_object_at(&["<a>x</b>"], (0, 3), InnerTag), None);
+}
+
+#[test]
+fn select_text_object() {
+    let mut t = TextArea::from(["foo(bar, baz)"]);
+    t.move_cursor(CursorMove::Jump(0, 5));
+    assert!(t.select_text_object(TextObject::InnerBracket('(')));
+    assert_eq!(t.selection_range(), Some(((0, 4), (0, 12))));
+    assert_eq!(t.cursor(), (0, 12));
+
+    // Cursor and selection are not changed when the text object is not found
+    assert!(!t.select_text_object(TextObject::InnerQuote('"')));
+    assert_eq!(t.selection_range(), Some(((0, 4), (0, 12))));
+    assert_eq!(t.cursor(), (0, 12));
+
+    t.cancel_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.select_text_object(TextObject::AroundWord));
+    assert!(t.cut());
+    assert_eq!(t.lines(), ["(bar, baz)"]);
+}
@@ -1,4 +1,4 @@
-use tui_textarea::{Input, Key, TextArea};
+use tui_textarea::{EditorEvent, Input, Key, TextArea};
 
 // Sanity test for checking textarea does not crash against all combination of inputs
 #[test]
@@ -47,6 +47,8 @@ fn test_input_all_combinations_sanity() {
         Esc,
         MouseScrollDown,
         MouseScrollUp,
+        MouseScrollLeft,
+        MouseScrollRight,
         Copy,
         Cut,
         Paste,
@@ -80,3 +82,59 @@ fn test_insert_multi_code_unit_emoji() {
     }
     assert_eq!(t.lines(), ["👨‍👩‍👧‍👦"]);
 }
+
+#[test]
+fn test_paste_event() {
+    let mut t = TextArea::from(["ab"]);
+    t.move_cursor(tui_textarea::CursorMove::Forward);
+    assert!(t.handle_event(EditorEvent::Paste("x\r\n    y\nz".into())));
+    assert_eq!(t.lines(), ["ax", "    y", "zb"]);
+    assert_eq!(t.cursor(), (2, 1));
+
+    // Entire paste is reverted by one undo
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["ax", "    y", "zb"]);
+
+    assert!(!t.handle_event(EditorEvent::Paste(String::new())));
+
+    // Key inputs are handled as `TextArea::input`
+    let input = Input {
+        key: Key::Char('c'),
+        ..Default::default()
+    };
+    assert!(t.handle_event(input));
+    assert_eq!(t.lines(), ["ax", "    y", "zcb"]);
+
+    // Bare `\r` sent by terminals on bracketed paste is a newline
+    let mut t = TextArea::default();
+    assert!(t.handle_event(EditorEvent::Paste("a\rb\r\nc\r".into())));
+    assert_eq!(t.lines(), ["a", "b", "c", ""]);
+
+    // Pasting over the selection is reverted by one undo
+    let mut t = TextArea::from(["foo", "bar"]);
+    t.select_all();
+    assert!(t.handle_event(EditorEvent::Paste("xyz".into())));
+    assert_eq!(t.lines(), ["xyz"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["foo", "bar"]);
+}
+
+#[test]
+fn test_paste_event_cancels_pending_chord() {
+    let ctrl = |c| Input {
+        key: Key::Char(c),
+        ctrl: true,
+        ..Default::default()
+    };
+    let mut t = TextArea::default();
+    t.chords_mut()
+        .bind([ctrl('x'), ctrl('u')], tui_textarea::EditorCommand::Undo);
+
+    t.input(ctrl('x'));
+    assert_eq!(t.pending_chord(), [ctrl('x')]);
+    assert!(t.handle_event(EditorEvent::Paste("foo".into())));
+    assert!(t.pending_chord().is_empty());
+    assert_eq!(t.lines(), ["foo"]);
+}
@@ -1,6 +1,6 @@
 #![cfg(feature = "serde")]
 
-use tui_textarea::{CursorMove, Input, Key, Scrolling};
+use tui_textarea::{CursorMove, EditorCommand, Input, Key, Keymap, Macro, Scrolling};
 
 #[test]
 fn test_serde_key() {
@@ -45,3 +45,107 @@ fn test_serde_cursor_move() {
     let d: CursorMove = serde_json::from_str(&s).unwrap();
     assert_eq!(d, c);
 }
+
+#[test]
+fn test_serde_editor_command() {
+    for (c, want) in [
+        (EditorCommand::Undo, r#""Undo""#),
+        (EditorCommand::InsertChar('a'), r#"{"InsertChar":"a"}"#),
+        (EditorCommand::Select(CursorMove::Down), r#"{"Select":"Down"}"#),
+        (
+            EditorCommand::Scroll(Scrolling::HalfPageDown),
+            r#"{"Scroll":"HalfPageDown"}"#,
+        ),
+        (
+            EditorCommand::PasteFromRegister('a'),
+            r#"{"PasteFromRegister":"a"}"#,
+        ),
+    ] {
+        let s = serde_json::to_string(&c).unwrap();
+        assert_eq!(s, want);
+        let d: EditorCommand = serde_json::from_str(&s).unwrap();
+        assert_eq!(d, c);
+    }
+}
+
+#[test]
+fn test_serde_keymap() {
+    let mut k = Keymap::new();
+    k.bind("ctrl+shift+z".parse().unwrap(), EditorCommand::Redo);
+    k.bind("alt+f".parse().unwrap(), EditorCommand::MoveCursor(CursorMove::WordForward));
+    let s = serde_json::to_string(&k).unwrap();
+    assert_eq!(
+        s,
+        r#"{"alt+f":{"MoveCursor":"WordForward"},"ctrl+shift+z":"Redo"}"#,
+    );
+    let d: Keymap = serde_json::from_str(&s).unwrap();
+    assert_eq!(d, k);
+}
+
+#[test]
+fn test_serde_keymap_round_trip_default() {
+    let k = Keymap::default();
+    let s = serde_json::to_string(&k).unwrap();
+    let d: Keymap = serde_json::from_str(&s).unwrap();
+    assert_eq!(d, k);
+}
+
+#[test]
+fn test_serde_keymap_notation() {
+    let s = r#"{"Ctrl+Z":"Undo","control+meta+Enter":"InsertNewline","shift+PageDown":{"SelectScroll":"PageDown"}}"#;
+    let k: Keymap = serde_json::from_str(s).unwrap();
+    let ctrl_z = Input {
+        key: Key::Char('Z'),
+        ctrl: true,
+        ..Default::default()
+    };
+    assert_eq!(k.get(&ctrl_z), Some(EditorCommand::Undo));
+    let ctrl_alt_enter = Input {
+        key: Key::Enter,
+        ctrl: true,
+        alt: true,
+        shift: false,
+    };
+    assert_eq!(k.get(&ctrl_alt_enter), Some(EditorCommand::InsertNewline));
+    let shift_page_down = Input {
+        key: Key::PageDown,
+        shift: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        k.get(&shift_page_down),
+        Some(EditorCommand::SelectScroll(Scrolling::PageDown)),
+    );
+    assert_eq!(k.bindings().count(), 3);
+}
+
+#[test]
+fn test_serde_keymap_invalid_notation() {
+    for s in [
+        r#"{"ctrl+foo":"Undo"}"#,
+        r#"{"hyper+a":"Undo"}"#,
+        r#"{"ctrl+":"Undo"}"#,
+        r#"{"ctrl+z":"Unknown"}"#,
+    ] {
+        let err = serde_json::from_str::<Keymap>(s).unwrap_err();
+        assert!(err.is_data(), "{}: {}", s, err);
+    }
+}
+
+#[test]
+fn test_serde_macro() {
+    let m: Macro = [Key::Char('a'), Key::Enter]
+        .iter()
+        .map(|&key| Input {
+            key,
+            ..Default::default()
+        })
+        .collect();
+    let s = serde_json::to_string(&m).unwrap();
+    assert_eq!(
+        s,
+        r#"[{"key":{"Char":"a"},"ctrl":false,"alt":false,"shift":false},{"key":"Enter","ctrl":false,"alt":false,"shift":false}]"#,
+    );
+    let d: Macro = serde_json::from_str(&s).unwrap();
+    assert_eq!(d, m);
+}
@@ -1669,3 +1669,1157 @@ fn test_delete_next_word() {
     t.test((1, 0), (1, 0, &["word  ことば 🐶", ""], " x"));
     t.test((1, 2), (1, 2, t.0, ""));
 }
+
+#[test]
+fn test_kill_ring_consecutive_kills() {
+    let mut t = TextArea::from(["foo bar baz qux"]);
+    t.move_cursor(CursorMove::WordForward);
+    t.delete_next_word();
+    t.delete_next_word();
+    assert_eq!(t.yank_text(), "bar baz");
+
+    // Backward kills are prepended
+    t.delete_word();
+    assert_eq!(t.yank_text(), "foo bar baz");
+    assert_eq!(t.kill_ring(), ["foo bar baz"]);
+    assert_eq!(t.lines(), [" qux"]);
+
+    // Moving the cursor starts a new kill
+    t.move_cursor(CursorMove::End);
+    t.move_cursor(CursorMove::Head);
+    t.delete_line_by_end();
+    assert_eq!(t.kill_ring(), [" qux", "foo bar baz"]);
+
+    // Other edits start a new kill
+    let mut t = TextArea::from(["abc def"]);
+    t.delete_next_word();
+    t.insert_char('x');
+    t.move_cursor(CursorMove::Back);
+    t.delete_line_by_end();
+    assert_eq!(t.kill_ring(), ["x def", "abc"]);
+
+    // Undo starts a new kill
+    let mut t = TextArea::from(["abc def"]);
+    t.delete_next_word();
+    t.undo();
+    t.delete_next_word();
+    assert_eq!(t.kill_ring(), [" def", "abc"]);
+}
+
+#[test]
+fn test_yank_pop() {
+    let mut t = TextArea::default();
+    for s in ["a", "b\nc", "d"] {
+        t.set_yank_text(s);
+    }
+
+    assert!(!t.yank_pop());
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["d"]);
+    assert!(t.yank_pop());
+    assert_eq!(t.lines(), ["b", "c"]);
+    assert_eq!(t.cursor(), (1, 1));
+    assert!(t.yank_pop());
+    assert_eq!(t.lines(), ["a"]);
+    assert!(t.yank_pop()); // Cycle to the newest
+    assert_eq!(t.lines(), ["d"]);
+
+    // Yank-pop is not available after other operations
+    t.insert_char('x');
+    assert!(!t.yank_pop());
+    assert_eq!(t.lines(), ["dx"]);
+
+    // Single text cannot be cycled
+    let mut t = TextArea::default();
+    t.set_yank_text("a");
+    assert!(t.paste());
+    assert!(!t.yank_pop());
+
+    // One undo reverts one yank-pop
+    let mut t = TextArea::from(["foo bar"]);
+    t.delete_next_word();
+    t.move_cursor(CursorMove::End);
+    t.delete_word();
+    assert!(t.paste());
+    assert!(t.yank_pop());
+    assert_eq!(t.lines(), [" foo"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), [" bar"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), [" "]);
+
+    // Pasting over the selection is reverted by one undo
+    let mut t = TextArea::from(["foo"]);
+    t.set_yank_text("x");
+    t.select_all();
+    assert!(t.paste());
+    assert_eq!(t.lines(), ["x"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["foo"]);
+}
+
+#[test]
+fn test_yank_pop_with_keymap() {
+    use tui_textarea::{Input, Key};
+
+    let mut t = TextArea::from(["foo bar"]);
+    t.delete_next_word();
+    t.move_cursor(CursorMove::End);
+    t.delete_word();
+    let key = |c, ctrl, alt| Input {
+        key: Key::Char(c),
+        ctrl,
+        alt,
+        shift: false,
+    };
+    assert!(t.input(key('y', true, false)));
+    assert_eq!(t.lines(), [" bar"]);
+    assert!(t.input(key('y', false, true)));
+    assert_eq!(t.lines(), [" foo"]);
+}
+
+#[test]
+fn test_registers() {
+    let mut t = TextArea::from(["foo bar"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::WordForward);
+    t.copy_to_register('a');
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    t.copy_to_register('A');
+    assert_eq!(t.register('a').as_deref(), Some("foo bar"));
+    assert_eq!(t.kill_ring(), Vec::<String>::new());
+
+    // Cut to register does not change the kill ring
+    t.start_selection();
+    t.move_cursor(CursorMove::Head);
+    assert!(t.cut_to_register('b'));
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.register('b').as_deref(), Some("foo bar"));
+    assert_eq!(t.yank_text(), "");
+
+    assert!(t.paste_from_register('b'));
+    assert_eq!(t.lines(), ["foo bar"]);
+    assert!(!t.paste_from_register('c'));
+    assert!(!t.paste_from_register('_'));
+
+    // Black hole register
+    t.select_all();
+    assert!(t.cut_to_register('_'));
+    assert_eq!(t.lines(), [""]);
+    assert_eq!(t.register('_'), None);
+    assert_eq!(t.yank_text(), "");
+
+    // Unnamed register is the kill ring
+    assert!(t.set_register('"', "x\ny"));
+    assert_eq!(t.yank_text(), "x\ny");
+    assert!(t.paste_from_register('"'));
+    assert_eq!(t.lines(), ["x", "y"]);
+
+    // Invalid register names
+    t.select_all();
+    assert!(!t.cut_to_register('1'));
+    assert!(!t.set_register('!', "a"));
+    assert_eq!(t.register('1'), None);
+}
+
+#[test]
+fn test_registers_with_keymap() {
+    use tui_textarea::{EditorCommand, Input, Key};
+
+    let ctrl = |c| Input {
+        key: Key::Char(c),
+        ctrl: true,
+        ..Default::default()
+    };
+    let mut t = TextArea::from(["hello"]);
+    t.chords_mut()
+        .bind([ctrl('r'), ctrl('a')], EditorCommand::CutToRegister('a'));
+    t.chords_mut()
+        .bind([ctrl('r'), ctrl('p')], EditorCommand::PasteFromRegister('a'));
+
+    t.select_all();
+    t.input(ctrl('r'));
+    assert!(t.input(ctrl('a')));
+    assert_eq!(t.lines(), [""]);
+    t.input(ctrl('r'));
+    assert!(t.input(ctrl('p')));
+    assert_eq!(t.lines(), ["hello"]);
+}
+
+#[test]
+fn test_record_and_replay_macro() {
+    use tui_textarea::{Input, Key};
+
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+    let ctrl = |c| Input {
+        key: Key::Char(c),
+        ctrl: true,
+        ..Default::default()
+    };
+    let mut t = TextArea::from(["a", "b", "c", "d"]);
+
+    // Keys which start and stop recording are not recorded
+    t.chords_mut().bind_callback([ctrl('x'), key(Key::Char('('))], |t| {
+        t.start_recording("m");
+        false
+    });
+    t.chords_mut().bind_callback([ctrl('x'), key(Key::Char(')'))], |t| {
+        t.stop_recording();
+        false
+    });
+
+    t.input(ctrl('x'));
+    t.input(key(Key::Char('(')));
+    assert_eq!(t.recording_macro(), Some("m"));
+    for k in [Key::End, Key::Char('!'), Key::Down] {
+        t.input(key(k));
+    }
+    t.input(ctrl('x'));
+    t.input(key(Key::Char(')')));
+    assert_eq!(t.recording_macro(), None);
+    assert_eq!(t.lines(), ["a!", "b", "c", "d"]);
+
+    let inputs: Vec<_> = t.macros()["m"].inputs().iter().map(|i| i.key).collect();
+    assert_eq!(inputs, [Key::End, Key::Char('!'), Key::Down]);
+
+    assert!(t.replay_macro("m", 2));
+    assert_eq!(t.lines(), ["a!", "b!", "c!", "d"]);
+
+    // Replay is undone and redone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a!", "b", "c", "d"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["a!", "b!", "c!", "d"]);
+
+    // Unknown macro and zero count do nothing
+    assert!(!t.replay_macro("unknown", 1));
+    assert!(!t.replay_macro("m", 0));
+    assert!(!t.stop_recording());
+}
+
+#[test]
+fn test_replay_macro_recursion() {
+    use tui_textarea::{Input, Key};
+
+    let f1 = Input {
+        key: Key::F(1),
+        ..Default::default()
+    };
+    let mut t = TextArea::default();
+    t.chords_mut().bind_callback([f1.clone()], |t| t.replay_macro("m", 1));
+    t.macros_mut().insert(
+        "m".to_string(),
+        [Key::Char('a'), Key::F(1)]
+            .iter()
+            .map(|&key| Input {
+                key,
+                ..Default::default()
+            })
+            .collect(),
+    );
+
+    // Replaying the macro from itself is ignored
+    assert!(t.input(f1));
+    assert_eq!(t.lines(), ["a"]);
+}
+
+#[test]
+fn test_record_macro_replay() {
+    use tui_textarea::{Input, Key};
+
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+    let mut t = TextArea::default();
+    t.chords_mut()
+        .bind_callback([key(Key::F(3))], |t| t.replay_macro("a", 1));
+    t.macros_mut()
+        .insert("a".to_string(), [key(Key::Char('x'))].into_iter().collect());
+
+    // Only the input which triggers the replay is recorded, not the replayed inputs
+    t.start_recording("b");
+    assert!(t.input(key(Key::F(3))));
+    t.stop_recording();
+    assert_eq!(t.lines(), ["x"]);
+    assert_eq!(t.macros()["b"].inputs(), [key(Key::F(3))]);
+
+    assert!(t.replay_macro("b", 1));
+    assert_eq!(t.lines(), ["xx"]);
+}
+
+#[test]
+fn test_overwrite_mode() {
+    use tui_textarea::{Input, Key};
+
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+    let mut t = TextArea::from(["abcd", "ef"]);
+    assert!(!t.is_overwrite());
+    t.input(key(Key::Insert));
+    assert!(t.is_overwrite());
+
+    for c in "xy".chars() {
+        assert!(t.input(key(Key::Char(c))));
+    }
+    assert_eq!(t.lines(), ["xycd", "ef"]);
+    assert_eq!(t.cursor(), (0, 2));
+
+    // Each replacement is undone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["xbcd", "ef"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["xycd", "ef"]);
+
+    // Characters are appended at end of line
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor(CursorMove::Head);
+    for c in "ghi".chars() {
+        t.input(key(Key::Char(c)));
+    }
+    assert_eq!(t.lines(), ["xycd", "ghi"]);
+
+    // Backspace restores the replaced characters in the current run
+    t.input(key(Key::Backspace));
+    assert_eq!(t.lines(), ["xycd", "gh"]);
+    t.input(key(Key::Backspace));
+    assert_eq!(t.lines(), ["xycd", "gf"]);
+    assert_eq!(t.cursor(), (1, 1));
+    t.input(key(Key::Backspace));
+    assert_eq!(t.lines(), ["xycd", "ef"]);
+    assert_eq!(t.cursor(), (1, 0));
+
+    // Outside the run, backspace deletes a character as usual
+    t.input(key(Key::Backspace));
+    assert_eq!(t.lines(), ["xycdef"]);
+
+    // After moving the cursor, a new run starts
+    t.move_cursor(CursorMove::Head);
+    t.input(key(Key::Char('1')));
+    t.move_cursor(CursorMove::End);
+    t.move_cursor(CursorMove::Head);
+    t.move_cursor(CursorMove::Forward);
+    t.input(key(Key::Char('2')));
+    t.input(key(Key::Backspace));
+    assert_eq!(t.lines(), ["1ycdef"]);
+    t.input(key(Key::Backspace));
+    assert_eq!(t.lines(), ["ycdef"]);
+
+    // Newlines are inserted
+    t.input(key(Key::Enter));
+    assert_eq!(t.lines(), ["", "ycdef"]);
+
+    t.input(key(Key::Insert));
+    assert!(!t.is_overwrite());
+    t.input(key(Key::Char('z')));
+    assert_eq!(t.lines(), ["", "zycdef"]);
+}
+
+#[test]
+fn test_overwrite_selection() {
+    let mut t = TextArea::from(["abcd"]);
+    t.set_overwrite(true);
+    t.start_selection();
+    t.move_cursor(CursorMove::Forward);
+    t.move_cursor(CursorMove::Forward);
+    t.execute(tui_textarea::EditorCommand::InsertChar('x'));
+    assert_eq!(t.lines(), ["xcd"]);
+}
+
+#[test]
+fn test_overwrite_with_auto_pairs() {
+    use tui_textarea::{Input, Key};
+
+    let key = |key| Input {
+        key,
+        ..Default::default()
+    };
+    let mut t = TextArea::from(["abcd"]);
+    t.set_overwrite(true);
+    t.set_auto_pairs([('(', ')')]);
+
+    // Opening character replaces one character without inserting its closing character
+    t.input(key(Key::Char('(')));
+    assert_eq!(t.lines(), ["(bcd"]);
+    assert_eq!(t.cursor(), (0, 1));
+    t.input(key(Key::Char(')')));
+    assert_eq!(t.lines(), ["()cd"]);
+    assert_eq!(t.cursor(), (0, 2));
+
+    // Backspace restores the replaced characters even if they form an empty pair
+    t.input(key(Key::Backspace));
+    assert_eq!(t.lines(), ["(bcd"]);
+    assert_eq!(t.cursor(), (0, 1));
+    t.input(key(Key::Backspace));
+    assert_eq!(t.lines(), ["abcd"]);
+    assert_eq!(t.cursor(), (0, 0));
+
+    let mut t = TextArea::from(["a)"]);
+    t.set_overwrite(true);
+    t.set_auto_pairs([('(', ')')]);
+    t.input(key(Key::Char('(')));
+    assert_eq!(t.lines(), ["()"]);
+    t.input(key(Key::Backspace));
+    assert_eq!(t.lines(), ["a)"]);
+    assert_eq!(t.cursor(), (0, 0));
+
+    // Smart backspace doesn't delete the spaces before the replaced character
+    let mut t = TextArea::from(["     a"]);
+    t.set_overwrite(true);
+    t.set_smart_backspace(true);
+    t.input(key(Key::Char(' ')));
+    assert_eq!(t.lines(), ["     a"]);
+    assert_eq!(t.cursor(), (0, 1));
+    t.input(key(Key::Char(' ')));
+    assert_eq!(t.lines(), ["     a"]);
+    t.input(key(Key::Backspace));
+    assert_eq!(t.lines(), ["     a"]);
+    assert_eq!(t.cursor(), (0, 1));
+}
+
+#[test]
+fn test_auto_indent() {
+    use tui_textarea::BracketIndent;
+
+    // Disabled by default
+    let mut t = TextArea::from(["  foo"]);
+    assert!(!t.auto_indent());
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    assert_eq!(t.lines(), ["  foo", ""]);
+
+    // Copy the indentation of the current line
+    let mut t = TextArea::from(["  foo"]);
+    t.set_auto_indent(true);
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    t.insert_str("bar");
+    assert_eq!(t.lines(), ["  foo", "  bar"]);
+    assert_eq!(t.cursor(), (1, 5));
+
+    // Split in the middle of line
+    t.move_cursor(CursorMove::Back);
+    t.insert_newline();
+    assert_eq!(t.lines(), ["  foo", "  ba", "  r"]);
+    assert_eq!(t.cursor(), (2, 2));
+
+    // Newline and indentation are undone at once
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["  foo", "  bar"]);
+    assert_eq!(t.cursor(), (1, 4));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["  foo", "  ba", "  r"]);
+
+    // Indentation is normalized with the tab settings
+    let mut t = TextArea::from(["\t  foo"]);
+    t.set_auto_indent(true);
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    assert_eq!(t.lines(), ["\t  foo", "      "]);
+    t.set_hard_tab_indent(true);
+    t.insert_newline();
+    assert_eq!(t.lines(), ["\t  foo", "      ", "\t  "]);
+
+    // Indent rule
+    let mut t = TextArea::from(["if x:"]);
+    t.set_auto_indent(true);
+    t.set_indent_rule(BracketIndent::default());
+    assert!(t.indent_rule().is_some());
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    t.insert_str("f(");
+    t.insert_newline();
+    t.insert_str("1,");
+    t.insert_newline();
+    t.insert_char(')');
+    assert_eq!(t.lines(), ["if x:", "    f(", "        1,", "    )"]);
+
+    // Dedent is undone at once and the closing bracket after text is not dedented
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["if x:", "    f(", "        1,", "        "]);
+    t.insert_str("g");
+    t.insert_char(']');
+    assert_eq!(t.lines(), ["if x:", "    f(", "        1,", "        g]"]);
+
+    t.remove_indent_rule();
+    assert!(t.indent_rule().is_none());
+    t.move_cursor(CursorMove::Up);
+    t.move_cursor(CursorMove::Up);
+    t.move_cursor(CursorMove::End);
+    t.insert_newline();
+    assert_eq!(t.lines()[2], "    ");
+}
+
+#[test]
+fn test_indent_dedent_lines() {
+    use tui_textarea::{Input, Key};
+
+    // Cursor line
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.indent_lines());
+    assert_eq!(t.lines(), ["    ab", "cd"]);
+    assert_eq!(t.cursor(), (0, 5));
+    assert!(t.dedent_lines());
+    assert_eq!(t.lines(), ["ab", "cd"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(!t.dedent_lines());
+
+    // The line where the selection ends at its head is not included
+    let mut t = TextArea::from(["ab", "cd", "ef"]);
+    t.set_hard_tab_indent(true);
+    t.start_selection();
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor(CursorMove::Down);
+    assert!(t.indent_lines());
+    assert_eq!(t.lines(), ["\tab", "\tcd", "ef"]);
+    assert_eq!(t.selection_range(), Some(((0, 1), (2, 0))));
+    assert!(t.indent_lines());
+    assert_eq!(t.lines(), ["\t\tab", "\t\tcd", "ef"]);
+
+    // All lines are reverted by one undo
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["\tab", "\tcd", "ef"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab", "cd", "ef"]);
+
+    // Dedent removes spaces up to the tab length
+    let mut t = TextArea::from(["      a", "  b", "c"]);
+    t.set_tab_length(4);
+    t.select_all();
+    assert!(t.dedent_lines());
+    assert_eq!(t.lines(), ["  a", "b", "c"]);
+    assert_eq!(t.selection_range(), Some(((0, 0), (2, 1))));
+
+    // Tab and Shift+Tab in the default keymap
+    let tab = Input {
+        key: Key::Tab,
+        ..Default::default()
+    };
+    let backtab = Input {
+        key: Key::Tab,
+        shift: true,
+        ..Default::default()
+    };
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.select_all();
+    assert!(t.input(tab.clone()));
+    assert_eq!(t.lines(), ["    ab", "    cd"]);
+    assert!(t.input(backtab.clone()));
+    assert_eq!(t.lines(), ["ab", "cd"]);
+    t.cancel_selection();
+    assert!(t.input(tab));
+    assert_eq!(t.lines(), ["ab", "cd  "]);
+    assert!(!t.input(backtab));
+    assert_eq!(t.lines(), ["ab", "cd  "]);
+}
+
+#[test]
+fn test_smart_backspace() {
+    // Disabled by default
+    let mut t = TextArea::from(["        a"]);
+    assert!(!t.smart_backspace());
+    t.move_cursor(CursorMove::Jump(0, 8));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["       a"]);
+
+    // Delete back to the previous tab stop in leading whitespaces
+    let mut t = TextArea::from(["          a"]);
+    t.set_smart_backspace(true);
+    t.move_cursor(CursorMove::Jump(0, 10));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["        a"]);
+    assert_eq!(t.cursor(), (0, 8));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["    a"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["        a"]);
+    assert_eq!(t.cursor(), (0, 8));
+
+    // Cursor in the middle of the leading whitespaces
+    t.move_cursor(CursorMove::Jump(0, 6));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["      a"]);
+    assert_eq!(t.cursor(), (0, 4));
+
+    // Tab length is respected
+    let mut t = TextArea::from(["      a"]);
+    t.set_smart_backspace(true);
+    t.set_tab_length(2);
+    t.move_cursor(CursorMove::Jump(0, 6));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["    a"]);
+
+    // Mixed tabs and spaces. A hard tab is deleted as one character
+    let mut t = TextArea::from(["\t  \t   a"]);
+    t.set_smart_backspace(true);
+    t.move_cursor(CursorMove::Jump(0, 7));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["\t  \ta"]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["\t  a"]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["\ta"]);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["a"]);
+
+    // Spaces after wide characters are not indentation
+    let mut t = TextArea::from(["あい  "]);
+    t.set_smart_backspace(true);
+    t.move_cursor(CursorMove::End);
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["あい "]);
+
+    // Wide characters after the cursor don't matter
+    let mut t = TextArea::from(["        あ"]);
+    t.set_smart_backspace(true);
+    t.move_cursor(CursorMove::Jump(0, 8));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["    あ"]);
+}
+
+#[test]
+fn test_insert_tab_to_tab_stop() {
+    // Spaces are inserted up to the next tab stop by the display width before the cursor
+    for (line, col, want, cursor) in [
+        ("ab", 2, "ab  ", 4),
+        ("abcd", 4, "abcd    ", 8),
+        ("あ", 1, "あ  ", 3),
+        ("\ta", 2, "\ta   ", 5),
+        ("a\tb", 3, "a\tb   ", 6),
+        ("\tab", 1, "\t    ab", 5),
+    ] {
+        let mut t = TextArea::from([line]);
+        t.move_cursor(CursorMove::Jump(0, col));
+        assert!(t.insert_tab(), "{:?}", line);
+        assert_eq!(t.lines(), [want], "{:?}", line);
+        assert_eq!(t.cursor(), (0, cursor), "{:?}", line);
+    }
+}
+
+#[test]
+fn test_auto_pairs() {
+    let pairs = [('(', ')'), ('[', ']'), ('{', '}'), ('"', '"')];
+
+    // Disabled by default
+    let mut t = TextArea::default();
+    assert!(t.auto_pairs().is_empty());
+    t.insert_char('(');
+    assert_eq!(t.lines(), ["("]);
+
+    let mut t = TextArea::default();
+    t.set_auto_pairs(pairs);
+    assert_eq!(t.auto_pairs(), pairs);
+
+    // Opening character inserts the pair as one edit
+    t.insert_char('f');
+    t.insert_char('(');
+    t.insert_char('[');
+    assert_eq!(t.lines(), ["f([])"]);
+    assert_eq!(t.cursor(), (0, 3));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["f()"]);
+    assert_eq!(t.cursor(), (0, 2));
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["f([])"]);
+    t.move_cursor(CursorMove::Jump(0, 3));
+
+    // Closing character steps over
+    t.insert_char('1');
+    t.insert_char(']');
+    t.insert_char(')');
+    assert_eq!(t.lines(), ["f([1])"]);
+    assert_eq!(t.cursor(), (0, 6));
+    t.insert_char(')');
+    assert_eq!(t.lines(), ["f([1]))"]);
+
+    // Quotes are not paired after alphanumeric characters
+    let mut t = TextArea::default();
+    t.set_auto_pairs(pairs);
+    t.insert_char('"');
+    t.insert_str("it");
+    t.insert_char('"');
+    assert_eq!(t.lines(), ["\"it\""]);
+    assert_eq!(t.cursor(), (0, 4));
+    t.insert_char('a');
+    t.insert_char('"');
+    assert_eq!(t.lines(), ["\"it\"a\""]);
+
+    // Backspace deletes an empty pair as one edit
+    let mut t = TextArea::from(["x{}", "{a}"]);
+    t.set_auto_pairs(pairs);
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["x", "{a}"]);
+    assert_eq!(t.cursor(), (0, 1));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["x{}", "{a}"]);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    assert!(t.delete_char());
+    assert_eq!(t.lines(), ["x{}", "a}"]);
+}
+
+#[test]
+fn test_auto_pairs_wrap_selection() {
+    let mut t = TextArea::from(["foo bar", "baz"]);
+    t.set_auto_pairs([('(', ')'), ('"', '"')]);
+
+    // Single line
+    t.move_cursor(CursorMove::Jump(0, 4));
+    t.start_selection();
+    t.move_cursor(CursorMove::End);
+    t.insert_char('(');
+    assert_eq!(t.lines(), ["foo (bar)", "baz"]);
+    assert_eq!(t.selection_range(), Some(((0, 5), (0, 8))));
+    assert_eq!(t.cursor(), (0, 8));
+
+    // The wrapped text is still selected so it can be wrapped again
+    t.insert_char('"');
+    assert_eq!(t.lines(), ["foo (\"bar\")", "baz"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["foo (bar)", "baz"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["foo bar", "baz"]);
+
+    // Multiple lines selected backward
+    let mut t = TextArea::from(["foo bar", "baz"]);
+    t.set_auto_pairs([('(', ')')]);
+    t.move_cursor(CursorMove::Jump(1, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 4));
+    t.insert_char('(');
+    assert_eq!(t.lines(), ["foo (bar", "ba)z"]);
+    assert_eq!(t.cursor(), (0, 5));
+    assert_eq!(t.selection_range(), Some(((0, 5), (1, 2))));
+
+    // Characters which are not opening characters replace the selection
+    t.insert_char('x');
+    assert_eq!(t.lines(), ["foo (x)z"]);
+}
+
+#[test]
+fn test_move_lines() {
+    // Cursor line
+    let mut t = TextArea::from(["a", "b", "c"]);
+    assert!(!t.move_lines_up());
+    assert!(t.move_lines_down());
+    assert_eq!(t.lines(), ["b", "a", "c"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.move_lines_down());
+    assert_eq!(t.lines(), ["b", "c", "a"]);
+    assert!(!t.move_lines_down());
+
+    // Each move is reverted by one undo
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["b", "a", "c"]);
+    assert_eq!(t.cursor(), (1, 0));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "b", "c"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["b", "a", "c"]);
+
+    // Selected lines move together and the selection moves with them
+    let mut t = TextArea::from(["a", "bb", "cc", "d"]);
+    t.move_cursor(CursorMove::Jump(1, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(2, 2));
+    assert!(t.move_lines_up());
+    assert_eq!(t.lines(), ["bb", "cc", "a", "d"]);
+    assert_eq!(t.selection_range(), Some(((0, 1), (1, 2))));
+    assert!(!t.move_lines_up());
+    assert!(t.move_lines_down());
+    assert!(t.move_lines_down());
+    assert_eq!(t.lines(), ["a", "d", "bb", "cc"]);
+    assert_eq!(t.selection_range(), Some(((2, 1), (3, 2))));
+
+    // The line where the selection ends at its head is not moved
+    let mut t = TextArea::from(["a", "b", "c"]);
+    t.start_selection();
+    t.move_cursor(CursorMove::Down);
+    assert!(t.move_lines_down());
+    assert_eq!(t.lines(), ["b", "a", "c"]);
+    assert_eq!(t.selection_range(), Some(((1, 0), (2, 0))));
+    assert!(t.move_lines_down());
+    assert_eq!(t.lines(), ["b", "c", "a"]);
+    assert_eq!(t.selection_range(), Some(((2, 0), (2, 1))));
+}
+
+#[test]
+fn test_duplicate_lines() {
+    let mut t = TextArea::from(["ab", "cd"]);
+    t.move_cursor(CursorMove::Forward);
+    assert!(t.duplicate_lines());
+    assert_eq!(t.lines(), ["ab", "ab", "cd"]);
+    assert_eq!(t.cursor(), (1, 1));
+
+    t.select_all();
+    assert!(t.duplicate_lines());
+    assert_eq!(t.lines(), ["ab", "ab", "cd", "ab", "ab", "cd"]);
+    assert_eq!(t.selection_range(), Some(((3, 0), (5, 2))));
+
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab", "ab", "cd"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["ab", "cd"]);
+    assert_eq!(t.cursor(), (0, 0));
+
+    // Empty line
+    let mut t = TextArea::default();
+    assert!(t.duplicate_lines());
+    assert_eq!(t.lines(), ["", ""]);
+    assert_eq!(t.cursor(), (1, 0));
+}
+
+#[test]
+fn test_join_lines() {
+    for (lines, want, col) in [
+        (&["a", "b"][..], "a b", 1),
+        (&["a", "    b"][..], "a b", 1),
+        (&["a  ", "b"][..], "a  b", 3),
+        (&["", "b"][..], "b", 0),
+        (&["a", ""][..], "a", 1),
+        (&["f(", ")"][..], "f()", 2),
+        (&["あい", "\tう"][..], "あい う", 2),
+    ] {
+        let mut t = TextArea::from(lines.iter().copied());
+        assert!(t.join_lines(), "{:?}", lines);
+        assert_eq!(t.lines(), [want], "{:?}", lines);
+        assert_eq!(t.cursor(), (0, col), "{:?}", lines);
+        assert!(t.undo(), "{:?}", lines);
+        assert_eq!(t.lines(), lines, "{:?}", lines);
+    }
+
+    // Nothing to join at the last line
+    let mut t = TextArea::from(["a", "b"]);
+    t.move_cursor(CursorMove::Bottom);
+    assert!(!t.join_lines());
+    assert_eq!(t.lines(), ["a", "b"]);
+
+    // Selected lines are joined into one line
+    let mut t = TextArea::from(["a", "b", "c", "d"]);
+    t.move_cursor(CursorMove::Down);
+    t.start_selection();
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor(CursorMove::End);
+    assert!(t.join_lines());
+    assert_eq!(t.lines(), ["a", "b c", "d"]);
+    assert_eq!(t.cursor(), (1, 1));
+    assert_eq!(t.selection_range(), None);
+}
+
+#[test]
+fn test_sort_reverse_dedup_lines() {
+    // All lines are transformed without selection
+    let mut t = TextArea::from(["b", "a", "c", "a"]);
+    t.move_cursor(CursorMove::Bottom);
+    assert!(t.sort_lines());
+    assert_eq!(t.lines(), ["a", "a", "b", "c"]);
+    assert_eq!(t.cursor(), (3, 0));
+    assert!(!t.sort_lines());
+    assert!(t.dedup_lines());
+    assert_eq!(t.lines(), ["a", "b", "c"]);
+    assert_eq!(t.cursor(), (2, 0));
+    assert!(!t.dedup_lines());
+    assert!(t.reverse_lines());
+    assert_eq!(t.lines(), ["c", "b", "a"]);
+
+    // Each transformation is reverted by one undo
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "b", "c"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "a", "b", "c"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["b", "a", "c", "a"]);
+    assert!(!t.undo());
+
+    // Only selected lines are transformed and the result is selected
+    let mut t = TextArea::from(["z", "c", "b", "c", "a"]);
+    t.move_cursor(CursorMove::Down);
+    t.start_selection();
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor(CursorMove::Down);
+    t.move_cursor(CursorMove::End);
+    assert!(t.dedup_lines());
+    assert_eq!(t.lines(), ["z", "c", "b", "a"]);
+    assert_eq!(t.selection_range(), Some(((1, 0), (2, 1))));
+    assert!(t.sort_lines());
+    assert_eq!(t.lines(), ["z", "b", "c", "a"]);
+    assert!(t.reverse_lines());
+    assert_eq!(t.lines(), ["z", "c", "b", "a"]);
+
+    // Single line is not changed
+    let mut t = TextArea::from(["a"]);
+    assert!(!t.reverse_lines());
+    assert!(!t.sort_lines());
+}
+
+#[test]
+fn test_transform_selection() {
+    use tui_textarea::{Case, TextObject};
+
+    // Selection over multiple lines is kept over the transformed text
+    let mut t = TextArea::from(["let fooBar = 1;", "let bazQux = 2;"]);
+    t.move_cursor(CursorMove::Jump(0, 4));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(1, 10));
+    assert!(t.convert_case(Case::Snake));
+    assert_eq!(t.lines(), ["let foo_bar = 1;", "let baz_qux = 2;"]);
+    assert_eq!(t.selection_range(), Some(((0, 4), (1, 11))));
+    assert_eq!(t.cursor(), (1, 11));
+    assert!(t.convert_case(Case::Upper));
+    assert_eq!(t.lines(), ["let FOO_BAR = 1;", "LET BAZ_QUX = 2;"]);
+    assert!(!t.convert_case(Case::Upper));
+
+    // Each transformation is reverted by one undo
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["let foo_bar = 1;", "let baz_qux = 2;"]);
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["let fooBar = 1;", "let bazQux = 2;"]);
+    assert!(t.redo());
+    assert_eq!(t.lines(), ["let foo_bar = 1;", "let baz_qux = 2;"]);
+
+    // Backward selection keeps its direction
+    let mut t = TextArea::from(["a user_id b"]);
+    t.move_cursor(CursorMove::Jump(0, 9));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 2));
+    assert!(t.convert_case(Case::Camel));
+    assert_eq!(t.lines(), ["a userId b"]);
+    assert_eq!(t.cursor(), (0, 2));
+    assert_eq!(t.selection_range(), Some(((0, 2), (0, 8))));
+
+    // Word at the cursor without selection
+    let mut t = TextArea::from(["foo barBaz"]);
+    t.move_cursor(CursorMove::Jump(0, 5));
+    assert!(t.convert_case(Case::Kebab));
+    assert_eq!(t.lines(), ["foo bar-baz"]);
+    assert_eq!(t.selection_range(), Some(((0, 4), (0, 11))));
+
+    // Text object
+    let mut t = TextArea::from(["f(hello, world)"]);
+    t.move_cursor(CursorMove::Jump(0, 3));
+    assert!(t.transform_text_object(TextObject::InnerBracket('('), |s| Case::Title.convert(s)));
+    assert_eq!(t.lines(), ["f(Hello, World)"]);
+    assert!(!t.transform_text_object(TextObject::InnerQuote('"'), |s| s.to_uppercase()));
+
+    // Replacement can change the number of lines
+    let mut t = TextArea::from(["a,b,c"]);
+    t.select_all();
+    assert!(t.transform_selection(|s| s.replace(',', "\n")));
+    assert_eq!(t.lines(), ["a", "b", "c"]);
+    assert_eq!(t.selection_range(), Some(((0, 0), (2, 1))));
+    assert!(t.transform_selection(|s| s.replace('\n', "")));
+    assert_eq!(t.lines(), ["abc"]);
+    assert_eq!(t.selection_range(), Some(((0, 0), (0, 3))));
+}
+
+#[test]
+fn test_toggle_line_comment() {
+    // Markers are aligned with the minimum indentation and blank lines are skipped
+    let mut t = TextArea::from(["if x:", "    f()", "", "  g()"]);
+    t.move_cursor(CursorMove::Jump(1, 5));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(3, 3));
+    t.set_line_comment("#");
+    assert!(t.toggle_line_comment());
+    assert_eq!(t.lines(), ["if x:", "  #   f()", "", "  # g()"]);
+    assert_eq!(t.selection_range(), Some(((1, 7), (3, 5))));
+
+    // Already commented lines are uncommented
+    assert!(t.toggle_line_comment());
+    assert_eq!(t.lines(), ["if x:", "    f()", "", "  g()"]);
+    assert_eq!(t.selection_range(), Some(((1, 5), (3, 3))));
+
+    // Partially commented lines are commented
+    let mut t = TextArea::from(["-- a", "b", "--c"]);
+    t.set_line_comment("--");
+    t.select_all();
+    assert!(t.toggle_line_comment());
+    assert_eq!(t.lines(), ["-- -- a", "-- b", "-- --c"]);
+    assert!(t.toggle_line_comment());
+    assert_eq!(t.lines(), ["-- a", "b", "--c"]);
+
+    // Cursor line and one undo
+    let mut t = TextArea::from(["a", "b"]);
+    t.move_cursor(CursorMove::Down);
+    assert!(t.toggle_line_comment());
+    assert_eq!(t.lines(), ["a", "// b"]);
+    assert_eq!(t.cursor(), (1, 3));
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "b"]);
+    assert!(!t.undo());
+
+    // Markers are aligned by display width in indentation with mixed tabs and spaces
+    let mut t = TextArea::from(["\tfoo", "    bar", "\t  baz"]);
+    t.select_all();
+    assert!(t.toggle_line_comment());
+    assert_eq!(t.lines(), ["\t// foo", "    // bar", "\t//   baz"]);
+    assert!(t.toggle_line_comment());
+    assert_eq!(t.lines(), ["\tfoo", "    bar", "\t  baz"]);
+
+    // Only blank lines
+    let mut t = TextArea::from(["  "]);
+    assert!(!t.toggle_line_comment());
+
+    // Ctrl+/ in the default keymap
+    let mut t = TextArea::from(["a"]);
+    let input = tui_textarea::Input {
+        key: tui_textarea::Key::Char('/'),
+        ctrl: true,
+        ..Default::default()
+    };
+    assert!(t.input(input));
+    assert_eq!(t.lines(), ["// a"]);
+}
+
+#[test]
+fn test_toggle_block_comment() {
+    // Tokens wrap the non-blank lines
+    let mut t = TextArea::from(["", "    a();  ", "    b();", ""]);
+    t.select_all();
+    assert!(t.toggle_block_comment());
+    assert_eq!(t.lines(), ["", "    /* a();  ", "    b(); */", ""]);
+    assert!(t.toggle_block_comment());
+    assert_eq!(t.lines(), ["", "    a();  ", "    b();", ""]);
+    assert_eq!(t.selection_range(), Some(((0, 0), (3, 0))));
+
+    // Single line with the cursor moving with the text
+    let mut t = TextArea::from(["  x = 1;"]);
+    t.move_cursor(CursorMove::Jump(0, 4));
+    assert!(t.toggle_block_comment());
+    assert_eq!(t.lines(), ["  /* x = 1; */"]);
+    assert_eq!(t.cursor(), (0, 7));
+    assert!(t.toggle_block_comment());
+    assert_eq!(t.lines(), ["  x = 1;"]);
+    assert_eq!(t.cursor(), (0, 4));
+
+    // Tokens without spaces are also detected
+    let mut t = TextArea::from(["/*x*/"]);
+    assert!(t.toggle_block_comment());
+    assert_eq!(t.lines(), ["x"]);
+    let mut t = TextArea::from(["/**/"]);
+    assert!(t.toggle_block_comment());
+    assert_eq!(t.lines(), [""]);
+    let mut t = TextArea::from(["/*/"]);
+    assert!(t.toggle_block_comment());
+    assert_eq!(t.lines(), ["/* /*/ */"]);
+
+    // Toggling is reverted by one undo
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["/*/"]);
+
+    t.remove_block_comment();
+    assert!(!t.toggle_block_comment());
+}
+
+#[test]
+fn test_reflow_paragraph() {
+    // Only the paragraph at the cursor is reflowed and the cursor stays at the same character
+    let mut t = TextArea::from(["aaa bbb", "ccc ddd eee", "", "fff ggg"]);
+    t.set_fill_column(10);
+    t.move_cursor(CursorMove::Jump(1, 4));
+    assert!(t.reflow_paragraph());
+    assert_eq!(t.lines(), ["aaa bbb", "ccc ddd", "eee", "", "fff ggg"]);
+    assert_eq!(t.cursor(), (1, 4));
+    assert!(!t.reflow_paragraph());
+
+    // Reflowing is reverted by one undo
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["aaa bbb", "ccc ddd eee", "", "fff ggg"]);
+
+    // Short lines are joined
+    t.set_fill_column(80);
+    t.move_cursor(CursorMove::Jump(0, 5));
+    assert!(t.reflow_paragraph());
+    assert_eq!(t.lines(), ["aaa bbb ccc ddd eee", "", "fff ggg"]);
+    assert_eq!(t.cursor(), (0, 5));
+
+    // Nothing to reflow on blank line
+    t.move_cursor(CursorMove::Jump(1, 0));
+    assert!(!t.reflow_paragraph());
+
+    // Comment prefixes and hanging indentation are kept
+    let mut t = TextArea::from([
+        "    /// - aaa bbb ccc",
+        "    ///   ddd",
+        "    ///",
+        "    /// eee",
+    ]);
+    t.set_fill_column(20);
+    assert!(t.reflow_paragraph());
+    assert_eq!(
+        t.lines(),
+        [
+            "    /// - aaa bbb",
+            "    ///   ccc ddd",
+            "    ///",
+            "    /// eee",
+        ],
+    );
+
+    // All paragraphs in the selected lines are reflowed and selected
+    let mut t = TextArea::from(["# a", "# b", "", "c", "d", "e"]);
+    t.set_line_comment("#");
+    t.move_cursor(CursorMove::Jump(4, 1));
+    t.start_selection();
+    t.move_cursor(CursorMove::Top);
+    assert!(t.reflow_paragraph());
+    assert_eq!(t.lines(), ["# a b", "", "c d", "e"]);
+    assert_eq!(t.selection_range(), Some(((0, 0), (2, 3))));
+}
+
+#[test]
+fn test_whitespace_cleanup() {
+    // The cursor stays at the same text and the cleanup is reverted by one undo
+    let mut t = TextArea::from(["a  ", "", "", "  b \t", "", "", "c", "", " "]);
+    t.move_cursor(CursorMove::Jump(3, 3));
+    assert!(t.trim_trailing_whitespace());
+    assert_eq!(t.lines(), ["a", "", "", "  b", "", "", "c", "", ""]);
+    assert_eq!(t.cursor(), (3, 3));
+    assert!(t.collapse_blank_lines());
+    assert_eq!(t.lines(), ["a", "", "  b", "", "c", ""]);
+    assert_eq!(t.cursor(), (2, 3));
+    assert!(t.ensure_final_newline());
+    assert_eq!(t.lines(), ["a", "", "  b", "", "c"]);
+    assert_eq!(t.cursor(), (2, 3));
+    assert!(!t.ensure_final_newline());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a", "", "  b", "", "c", ""]);
+    assert!(t.undo());
+    assert!(t.undo());
+    assert_eq!(t.lines(), ["a  ", "", "", "  b \t", "", "", "c", "", " "]);
+
+    // The cursor in the removed blank lines moves to the end of the text
+    let mut t = TextArea::from(["a", "", "  "]);
+    t.move_cursor(CursorMove::Bottom);
+    assert!(t.ensure_final_newline());
+    assert_eq!(t.lines(), ["a"]);
+    assert_eq!(t.cursor(), (0, 1));
+
+    // Only the selected lines are cleaned up and the selection is kept
+    let mut t = TextArea::from(["\ta ", "\t\tb ", "\tc "]);
+    t.move_cursor(CursorMove::Jump(1, 2));
+    t.start_selection();
+    t.move_cursor(CursorMove::Jump(0, 1));
+    assert!(t.normalize_indent());
+    assert_eq!(t.lines(), ["    a ", "        b ", "\tc "]);
+    assert_eq!(t.selection_range(), Some(((0, 4), (1, 8))));
+    assert!(t.trim_trailing_whitespace());
+    assert_eq!(t.lines(), ["    a", "        b", "\tc "]);
+    assert_eq!(t.selection_range(), Some(((0, 4), (1, 8))));
+
+    // Nothing to clean up
+    t.set_tab_length(0);
+    assert!(!t.normalize_indent());
+    let mut t = TextArea::default();
+    assert!(!t.trim_trailing_whitespace());
+    assert!(!t.collapse_blank_lines());
+    assert!(!t.ensure_final_newline());
+}